@@ -0,0 +1,408 @@
+//! Generates `units::METER`, `units::POUND_FORCE`, ..., the members of the
+//! systems of units and the quantity kind graph from the bundled QUDT
+//! ontologies, so that neither the unit catalog nor the quantity kinds need
+//! RDF parsing at run time.
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/rdf.rs"]
+#[allow(dead_code)]
+mod rdf;
+
+#[path = "src/qudt_records.rs"]
+mod qudt_records;
+
+use qudt_records::{KindRecord, UnitRecord, kind_records, link_quantity_kinds, unit_records};
+use rdf::{QUDT_NS, RDF_TYPE, RDFS_NS, Triple};
+
+const SOURCES: [&str; 7] = [
+    "src/ontology/unit",
+    "src/ontology/qudt-quantity",
+    "src/ontology/quantity",
+    "src/ontology/quantity_kind_dimensions.rs",
+    "src/rdf.rs",
+    "src/qudt_records.rs",
+    "build.rs",
+];
+
+/// A `qudt:SystemOfUnits`, mirroring `unit_system::UnitSystemConstant`
+struct SystemRecord {
+    iri: String,
+    name: String,
+    label: String,
+    abbreviation: Option<String>,
+    /// typed `qudt:SystemOfNaturalUnits`
+    natural: bool,
+    /// `(property, unit local names)`, e.g. `("systemBaseUnit", ["Meter", ...])`
+    members: Vec<(&'static str, Vec<String>)>,
+}
+
+/// A `qudt:CurrencyUnit`, mirroring `currency::Currency`
+struct CurrencyRecord {
+    name: String,
+    label: String,
+    code: Option<String>,
+    numeric_code: Option<String>,
+    exponent: Option<u32>,
+    description: Option<String>,
+}
+
+/// The `qudt:system...Unit` properties, in the order of `UnitSystemConstant`
+const SYSTEM_MEMBERS: [(&str, &str); 6] = [
+    ("systemBaseUnit", "base_units"),
+    ("systemCoherentDerivedUnit", "coherent_derived_units"),
+    ("systemDerivedUnit", "derived_units"),
+    ("systemAllowedUnit", "allowed_units"),
+    ("systemDefinedUnit", "defined_units"),
+    ("systemPrefixUnit", "prefix_units"),
+];
+
+fn main() {
+    for source in SOURCES {
+        println!("cargo:rerun-if-changed={}", source);
+    }
+    let parse = |path: &str| {
+        let src = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        rdf::parse_rdf_xml(&src).unwrap_or_else(|e| panic!("{}: {}", path, e))
+    };
+    let unit_triples = parse("src/ontology/unit");
+    let mut kind_triples = parse("src/ontology/qudt-quantity");
+    let kinds = kind_records(&kind_triples);
+    kind_triples.extend(parse("src/ontology/quantity"));
+
+    let mut units = unit_records(&unit_triples, |_| false);
+    link_quantity_kinds(&mut units, &kind_triples);
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("unit_constants.rs");
+    fs::write(out, generate(&units)).unwrap();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("unit_systems.rs");
+    fs::write(out, generate_systems(&system_records(&unit_triples))).unwrap();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("currencies.rs");
+    fs::write(out, generate_currencies(&currency_records(&unit_triples))).unwrap();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("quantity_kinds.rs");
+    fs::write(out, generate_kinds(&kinds)).unwrap();
+}
+
+fn system_records(triples: &[Triple]) -> Vec<SystemRecord> {
+    // `SystemOfUnits_Atomic` is only typed `qudt:SystemOfNaturalUnits`
+    let mut iris: Vec<(&str, bool)> = Vec::new();
+    for triple in triples.iter().filter(|t| t.predicate == RDF_TYPE) {
+        if let (Some(iri), Some(class)) = (triple.subject.as_iri(), triple.object.as_iri())
+            && let Some(class @ ("SystemOfUnits" | "SystemOfNaturalUnits")) =
+                class.strip_prefix(QUDT_NS)
+        {
+            let natural = class == "SystemOfNaturalUnits";
+            match iris.iter_mut().find(|(i, _)| *i == iri) {
+                Some(entry) => entry.1 |= natural,
+                None => iris.push((iri, natural)),
+            }
+        }
+    }
+    let mut systems: Vec<SystemRecord> = iris
+        .into_iter()
+        .map(|(iri, natural)| SystemRecord {
+            iri: iri.to_string(),
+            name: rdf::local_name(iri)
+                .trim_start_matches("SystemOfUnits_")
+                .to_string(),
+            label: String::new(),
+            abbreviation: None,
+            natural,
+            members: SYSTEM_MEMBERS
+                .iter()
+                .map(|(p, _)| (*p, Vec::new()))
+                .collect(),
+        })
+        .collect();
+    for system in systems.iter_mut() {
+        for triple in triples
+            .iter()
+            .filter(|t| t.subject.as_iri() == Some(system.iri.as_str()))
+        {
+            let literal = triple.object.as_literal().map(|s| s.trim().to_string());
+            match triple
+                .predicate
+                .strip_prefix(QUDT_NS)
+                .or(triple.predicate.strip_prefix(RDFS_NS))
+            {
+                Some("label") => system.label = literal.unwrap_or_default(),
+                Some("abbreviation") => system.abbreviation = literal,
+                Some(property) => {
+                    if let (Some((_, units)), Some(unit)) = (
+                        system.members.iter_mut().find(|(p, _)| *p == property),
+                        triple.object.as_iri(),
+                    ) {
+                        units.push(rdf::local_name(unit).to_string());
+                    }
+                }
+                None => {}
+            }
+        }
+        if system.label.is_empty() {
+            system.label = system.name.clone();
+        }
+    }
+    systems
+}
+
+fn currency_records(triples: &[Triple]) -> Vec<CurrencyRecord> {
+    let currency_unit = format!("{}CurrencyUnit", QUDT_NS);
+    let mut iris: Vec<&str> = Vec::new();
+    for triple in triples.iter().filter(|t| t.predicate == RDF_TYPE) {
+        if let Some(iri) = triple.subject.as_iri()
+            && triple.object.as_iri() == Some(currency_unit.as_str())
+            && !iris.contains(&iri)
+        {
+            iris.push(iri);
+        }
+    }
+    let mut by_subject: HashMap<&str, Vec<&Triple>> = HashMap::new();
+    for triple in triples {
+        if let Some(subject) = triple.subject.as_iri()
+            && iris.contains(&subject)
+        {
+            by_subject.entry(subject).or_default().push(triple);
+        }
+    }
+    iris.into_iter()
+        .map(|iri| {
+            let literals = |property: String| -> Vec<String> {
+                by_subject[iri]
+                    .iter()
+                    .filter(|t| t.predicate == property)
+                    .filter_map(|t| t.object.as_literal())
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            };
+            let name = rdf::local_name(iri).to_string();
+            CurrencyRecord {
+                label: literals(format!("{}label", RDFS_NS))
+                    .pop()
+                    .unwrap_or_else(|| name.clone()),
+                name,
+                // ISO 4217 alphabetic codes; `Million USD` is not one
+                code: literals(format!("{}abbreviation", QUDT_NS))
+                    .into_iter()
+                    .find(|s| s.len() == 3 && s.chars().all(|c| c.is_ascii_uppercase())),
+                // `USDollar` also carries the QUDT code 0555
+                numeric_code: literals(format!("{}code", QUDT_NS))
+                    .into_iter()
+                    .find(|s| s.len() == 3 && s.chars().all(|c| c.is_ascii_digit())),
+                exponent: literals(format!("{}currencyExponent", QUDT_NS))
+                    .first()
+                    .and_then(|s| s.parse().ok()),
+                description: literals(format!("{}description", QUDT_NS))
+                    .into_iter()
+                    .next(),
+            }
+        })
+        .collect()
+}
+
+/// `PoundForce` → `POUND_FORCE`, `CFAFrancBEAC` → `CFA_FRANC_BEAC`
+fn constant_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut constant = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !constant.ends_with('_') {
+                constant.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 && !constant.ends_with('_') {
+            let previous = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_lower)
+            {
+                constant.push('_');
+            }
+        }
+        constant.push(c.to_ascii_uppercase());
+    }
+    if constant.starts_with(|c: char| c.is_ascii_digit()) {
+        constant.insert(0, '_');
+    }
+    constant
+}
+
+fn generate(units: &[UnitRecord]) -> String {
+    let option = |value: &Option<String>| match value {
+        Some(s) => format!("Some({:?})", s),
+        None => "None".to_string(),
+    };
+    let mut code = String::from("// Generated by build.rs from the QUDT ontologies, do not edit\n");
+    let mut names = HashSet::new();
+    for unit in units {
+        let constant = constant_name(&unit.name);
+        assert!(
+            names.insert(constant.clone()),
+            "duplicate unit constant {}",
+            constant
+        );
+        let dimension = match unit.dimension {
+            Some(exponents) => format!("Some(Dimension::PowerLawIA({:?}))", exponents),
+            None => "None".to_string(),
+        };
+        let label = unit.label.as_deref().unwrap_or(&unit.name);
+        let multiplier = match unit.multiplier {
+            Some(m) => format!("Some({:?})", m),
+            None => "None".to_string(),
+        };
+        writeln!(code, "\n/// {}", label.replace(char::is_whitespace, " ")).unwrap();
+        writeln!(
+            code,
+            "pub const {}: UnitConstant = UnitConstant {{",
+            constant
+        )
+        .unwrap();
+        writeln!(code, "    iri: {:?},", unit.iri).unwrap();
+        writeln!(code, "    name: {:?},", unit.name).unwrap();
+        writeln!(code, "    label: {:?},", label).unwrap();
+        writeln!(code, "    symbol: {},", option(&unit.symbol)).unwrap();
+        writeln!(code, "    abbreviation: {},", option(&unit.abbreviation)).unwrap();
+        writeln!(code, "    multiplier: {},", multiplier).unwrap();
+        writeln!(code, "    offset: {:?},", unit.offset.unwrap_or(0.0)).unwrap();
+        writeln!(code, "    quantity_kinds: &{:?},", unit.quantity_kinds).unwrap();
+        writeln!(code, "    unit_types: &{:?},", unit.unit_types).unwrap();
+        writeln!(code, "    dimension: {},", dimension).unwrap();
+        writeln!(
+            code,
+            "    unece_common_code: {},",
+            option(&unit.unece_common_code)
+        )
+        .unwrap();
+        writeln!(code, "}};").unwrap();
+    }
+    writeln!(
+        code,
+        "\n/// Every unit of the bundled QUDT unit vocabulary, in document order"
+    )
+    .unwrap();
+    writeln!(code, "pub const UNITS: &[UnitConstant] = &[").unwrap();
+    for unit in units {
+        writeln!(code, "    {},", constant_name(&unit.name)).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
+
+fn generate_systems(systems: &[SystemRecord]) -> String {
+    let mut code =
+        String::from("// Generated by build.rs from the QUDT unit ontology, do not edit\n");
+    writeln!(
+        code,
+        "\n/// Every system of units of the bundled QUDT unit vocabulary, in document order"
+    )
+    .unwrap();
+    writeln!(code, "pub const UNIT_SYSTEMS: &[UnitSystemConstant] = &[").unwrap();
+    for system in systems {
+        writeln!(code, "    UnitSystemConstant {{").unwrap();
+        writeln!(code, "        iri: {:?},", system.iri).unwrap();
+        writeln!(code, "        name: {:?},", system.name).unwrap();
+        writeln!(code, "        label: {:?},", system.label).unwrap();
+        match &system.abbreviation {
+            Some(s) => writeln!(code, "        abbreviation: Some({:?}),", s).unwrap(),
+            None => writeln!(code, "        abbreviation: None,").unwrap(),
+        }
+        writeln!(code, "        natural: {},", system.natural).unwrap();
+        for ((_, field), (_, units)) in SYSTEM_MEMBERS.iter().zip(&system.members) {
+            writeln!(code, "        {}: &{:?},", field, units).unwrap();
+        }
+        writeln!(code, "    }},").unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
+
+fn generate_currencies(currencies: &[CurrencyRecord]) -> String {
+    let option = |value: &Option<String>| match value {
+        Some(s) => format!("Some({:?})", s),
+        None => "None".to_string(),
+    };
+    let mut code =
+        String::from("// Generated by build.rs from the QUDT unit ontology, do not edit\n");
+    writeln!(
+        code,
+        "\n/// Every currency unit of the bundled QUDT unit vocabulary, in document order"
+    )
+    .unwrap();
+    writeln!(code, "pub const CURRENCIES: &[Currency] = &[").unwrap();
+    for currency in currencies {
+        writeln!(code, "    Currency {{").unwrap();
+        writeln!(code, "        name: {:?},", currency.name).unwrap();
+        writeln!(code, "        label: {:?},", currency.label).unwrap();
+        writeln!(code, "        code: {},", option(&currency.code)).unwrap();
+        writeln!(
+            code,
+            "        numeric_code: {},",
+            option(&currency.numeric_code)
+        )
+        .unwrap();
+        match currency.exponent {
+            Some(exponent) => writeln!(code, "        exponent: Some({}),", exponent).unwrap(),
+            None => writeln!(code, "        exponent: None,").unwrap(),
+        }
+        writeln!(
+            code,
+            "        description: {},",
+            option(&currency.description)
+        )
+        .unwrap();
+        writeln!(code, "    }},").unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
+
+fn generate_kinds(kinds: &[KindRecord]) -> String {
+    let mut code =
+        String::from("// Generated by build.rs from the QUDT quantity ontology, do not edit\n");
+    writeln!(
+        code,
+        "\n/// Every quantity kind of the bundled QUDT quantity vocabulary, in document order"
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "pub const QUANTITY_KINDS: &[QuantityKindConstant] = &["
+    )
+    .unwrap();
+    for kind in kinds {
+        writeln!(code, "    QuantityKindConstant {{").unwrap();
+        writeln!(code, "        iri: {:?},", kind.iri).unwrap();
+        writeln!(code, "        name: {:?},", kind.name).unwrap();
+        writeln!(code, "        label: {:?},", kind.label).unwrap();
+        match &kind.symbol {
+            Some(s) => writeln!(code, "        symbol: Some({:?}),", s).unwrap(),
+            None => writeln!(code, "        symbol: None,").unwrap(),
+        }
+        writeln!(
+            code,
+            "        generalizations: &{:?},",
+            kind.generalizations
+        )
+        .unwrap();
+        writeln!(code, "        exact_matches: &{:?},", kind.exact_matches).unwrap();
+        writeln!(code, "        close_matches: &{:?},", kind.close_matches).unwrap();
+        writeln!(code, "        categories: &{:?},", kind.categories).unwrap();
+        match kind.dimension {
+            Some(exponents) => writeln!(
+                code,
+                "        dimension: Some(Dimension::PowerLawIA({:?})),",
+                exponents
+            )
+            .unwrap(),
+            None => writeln!(code, "        dimension: None,").unwrap(),
+        }
+        writeln!(code, "    }},").unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    code
+}
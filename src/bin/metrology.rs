@@ -0,0 +1,23 @@
+//! The `metrology` command-line tool, see `metrology4rs::cli`
+use metrology4rs::cli::{self, CliError};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::run(&args) {
+        Ok(text) => {
+            if !text.is_empty() {
+                println!("{}", text);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error @ CliError::Usage(_)) => {
+            eprintln!("error: {}\n\n{}", error, cli::USAGE);
+            ExitCode::from(2)
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
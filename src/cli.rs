@@ -0,0 +1,477 @@
+#![doc = include_str!("../doc/cli.md")]
+use crate::dimensional_analysis::{NamedAnalysis, Variable};
+use crate::expression::DerivedUnit;
+use crate::format::round_significant;
+use crate::problem::{Problem, ProblemError, Report, ReportFormat, solve_files, write_reports};
+use crate::quantity::Quantity;
+use crate::quantity_kind::quantity_kinds;
+use crate::repl::Session;
+use crate::units::UnitError;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::{Value, json};
+use std::fmt;
+use std::path::Path;
+
+pub const USAGE: &str = "\
+usage: metrology [--json] <command> [arguments]
+
+commands:
+  convert <value> <from> <to>           convert a value, e.g. `convert 3.2 psi kPa`
+  dim <unit expression>                 dimension of a unit, e.g. `dim \"N*m/s\"`
+  analyze --target <[name=]spec> --dep <name=spec>...
+                                        solve a dimensional analysis
+  pi-groups <problem file>              Pi groups of a TOML, YAML or JSON problem file
+  batch [--format json|markdown|latex] [--output <file>] <problem file>...
+                                        solve problem files and write a report
+  repl                                  interactive calculator with units and uncertainties
+  help                                  show this message
+
+A spec is a dimension (`LT^-2`), a quantity kind (`acceleration`) or a unit
+(`m/s^2`); prefix it with `dim:`, `kind:` or `unit:` to pick one reading.
+--json prints JSON instead of tables and Markdown.";
+
+/// Errors of the command-line tool
+#[derive(Clone, Debug, PartialEq)]
+pub enum CliError {
+    /// Wrong arguments; the tool prints the usage
+    Usage(String),
+    Unit(UnitError),
+    /// A problem file that cannot be read or parsed
+    File {
+        path: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}", message),
+            CliError::Unit(error) => write!(f, "{}", error),
+            CliError::File { path, message } => write!(f, "{}: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<ProblemError> for CliError {
+    fn from(error: ProblemError) -> Self {
+        match error {
+            ProblemError::InFile { path, error } => CliError::File {
+                path,
+                message: error.to_string(),
+            },
+            error => CliError::File {
+                path: "-".to_string(),
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+impl From<UnitError> for CliError {
+    fn from(error: UnitError) -> Self {
+        CliError::Unit(error)
+    }
+}
+
+/// The result of a command, printed as a table or as JSON
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output {
+    pub json: Value,
+    pub table: String,
+}
+
+/// Run the tool on the arguments that follow the program name and return
+/// the text to print
+pub fn run(args: &[String]) -> Result<String, CliError> {
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|a| *a != "--json")
+        .collect();
+    let output = match args.split_first() {
+        Some((&"convert", rest)) => convert(rest)?,
+        Some((&"dim", rest)) => dim(rest)?,
+        Some((&"analyze", rest)) => analyze(rest)?,
+        Some((&"pi-groups", rest)) => pi_groups(rest)?,
+        Some((&"batch", rest)) => return batch(rest, json),
+        Some((&"repl", _)) => {
+            repl()?;
+            return Ok(String::new());
+        }
+        Some((&"help", _)) | Some((&"--help", _)) | Some((&"-h", _)) => {
+            return Ok(USAGE.to_string());
+        }
+        Some((command, _)) => {
+            return Err(CliError::Usage(format!("unknown command `{}`", command)));
+        }
+        None => return Err(CliError::Usage("missing command".to_string())),
+    };
+    Ok(match json {
+        true => serde_json::to_string_pretty(&output.json).expect("JSON values serialize"),
+        false => output.table,
+    })
+}
+
+/// `convert <value> <from> <to>`
+pub fn convert(args: &[&str]) -> Result<Output, CliError> {
+    let [value, from, to] = args else {
+        return Err(CliError::Usage(
+            "convert takes a value and two units".to_string(),
+        ));
+    };
+    let value: f64 = value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("`{}` is not a number", value)))?;
+    let (from, to) = (DerivedUnit::parse(from)?, DerivedUnit::parse(to)?);
+    let result = Quantity::new(value, &from.to_unit()).convert_to(&to.to_unit())?;
+    let dimension = from.dimension().map(|d| d.to_string());
+    Ok(Output {
+        json: json!({
+            "value": value,
+            "from": from.symbol(),
+            "to": to.symbol(),
+            "result": result.value,
+            "dimension": dimension,
+        }),
+        table: format!(
+            "{} {} = {} {}",
+            number(value),
+            from.symbol(),
+            number(result.value),
+            to.symbol()
+        ),
+    })
+}
+
+/// `dim <unit expression>`
+pub fn dim(args: &[&str]) -> Result<Output, CliError> {
+    let [expression] = args else {
+        return Err(CliError::Usage("dim takes one unit expression".to_string()));
+    };
+    let unit = DerivedUnit::parse(expression)?;
+    let dimension = unit
+        .dimension()
+        .ok_or_else(|| UnitError::UnknownUnit(expression.to_string()))?;
+    let multiplier = unit.conversion_multiplier();
+    // every kind shares the dimension of a dimensionless unit, listing them is noise
+    let kinds: Vec<&str> = match dimension.is_dimensionless() {
+        true => Vec::new(),
+        false => quantity_kinds()
+            .iter()
+            .filter(|k| k.dimension.as_ref() == Some(&dimension))
+            .map(|k| k.name.as_str())
+            .collect(),
+    };
+    let rows = vec![
+        vec!["expression".to_string(), expression.to_string()],
+        vec!["unit".to_string(), unit.symbol()],
+        vec!["dimension".to_string(), dimension.to_string()],
+        vec![
+            "SI factor".to_string(),
+            multiplier.map_or("-".to_string(), number),
+        ],
+        vec!["quantity kinds".to_string(), kinds.join(", ")],
+    ];
+    Ok(Output {
+        json: json!({
+            "expression": expression,
+            "unit": unit.symbol(),
+            "dimension": dimension,
+            "dimension_string": dimension.to_string(),
+            "si_multiplier": multiplier,
+            "quantity_kinds": kinds,
+        }),
+        table: table(&[], &rows),
+    })
+}
+
+/// `analyze --target <[name=]spec> --dep <name=spec>...`
+pub fn analyze(args: &[&str]) -> Result<Output, CliError> {
+    let mut target = None;
+    let mut dependencies = Vec::new();
+    let mut rest = args.iter();
+    while let Some(&arg) = rest.next() {
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(value)),
+            _ => (arg, None),
+        };
+        let value = match value.or_else(|| rest.next().copied()) {
+            Some(value) => value,
+            None => return Err(CliError::Usage(format!("missing value after `{}`", option))),
+        };
+        match option {
+            "--target" => target = Some(variable(value)?),
+            "--dep" => dependencies.push(variable(value)?),
+            other => return Err(CliError::Usage(format!("unknown option `{}`", other))),
+        }
+    }
+    let target = target.ok_or_else(|| CliError::Usage("missing --target".to_string()))?;
+    Ok(analysis_report(&NamedAnalysis {
+        target,
+        dependencies,
+    }))
+}
+
+/// `pi-groups <problem file>`, see `problem::Problem`
+pub fn pi_groups(args: &[&str]) -> Result<Output, CliError> {
+    let [path] = args else {
+        return Err(CliError::Usage(
+            "pi-groups takes one problem file".to_string(),
+        ));
+    };
+    let report = solve_files(&[path])?.remove(0);
+    Ok(report_output(&report))
+}
+
+/// `batch [--format json|markdown|latex] [--output <file>] <problem file>...`;
+/// with `--output` the reports are written to the file and a summary is
+/// returned
+pub fn batch(args: &[&str], json: bool) -> Result<String, CliError> {
+    let mut format = if json {
+        ReportFormat::Json
+    } else {
+        ReportFormat::Markdown
+    };
+    let mut output = None;
+    let mut paths = Vec::new();
+    let mut rest = args.iter();
+    while let Some(&arg) = rest.next() {
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(value)),
+            _ => (arg, None),
+        };
+        if !option.starts_with('-') {
+            paths.push(arg);
+            continue;
+        }
+        let value = match value.or_else(|| rest.next().copied()) {
+            Some(value) => value,
+            None => return Err(CliError::Usage(format!("missing value after `{}`", option))),
+        };
+        match option {
+            "--format" | "-f" => format = value.parse().map_err(CliError::Usage)?,
+            "--output" | "-o" => output = Some(value),
+            other => return Err(CliError::Usage(format!("unknown option `{}`", other))),
+        }
+    }
+    if paths.is_empty() {
+        return Err(CliError::Usage(
+            "batch takes at least one problem file".to_string(),
+        ));
+    }
+    let reports = solve_files(&paths)?;
+    let text = write_reports(&reports, format);
+    match output {
+        Some(path) => {
+            std::fs::write(path, text + "\n").map_err(|e| CliError::File {
+                path: path.to_string(),
+                message: e.to_string(),
+            })?;
+            Ok(format!(
+                "{} problems solved, written to {}",
+                reports.len(),
+                path
+            ))
+        }
+        None => Ok(text),
+    }
+}
+
+/// Read a problem file and resolve its variables, see `problem::Problem::read`
+pub fn read_problem(path: &Path) -> Result<NamedAnalysis, CliError> {
+    let in_file = |error: ProblemError| ProblemError::InFile {
+        path: path.display().to_string(),
+        error: Box::new(error),
+    };
+    Ok(Problem::read(path)?.analysis().map_err(in_file)?)
+}
+
+/// `repl`: read lines until `:quit` or end of input and print the results,
+/// completing unit names, variables and functions with the tab key
+pub fn repl() -> Result<(), CliError> {
+    let error = |e: ReadlineError| CliError::File {
+        path: "stdin".to_string(),
+        message: e.to_string(),
+    };
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new().map_err(error)?;
+    editor.set_helper(Some(ReplHelper {
+        session: Session::new(),
+    }));
+    println!("metrology calculator, :help for help");
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => return Ok(()),
+            Err(e) => return Err(error(e)),
+        };
+        let line = line.trim();
+        if line == ":quit" || line == ":q" {
+            return Ok(());
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        let session = &mut editor.helper_mut().expect("helper is set").session;
+        match session.run(line) {
+            Ok(output) => output.iter().for_each(|text| println!("{}", text)),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
+
+struct ReplHelper {
+    session: Session,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self.session.complete(line, pos);
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .map(|c| Pair {
+                    display: c.display,
+                    replacement: c.replacement,
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+// `name=spec`, or a bare spec that also serves as the name
+fn variable(text: &str) -> Result<Variable, CliError> {
+    let (name, spec) = text.split_once('=').unwrap_or((text, text));
+    Ok(Variable::parse(name.trim(), spec)?)
+}
+
+/// The variables, the solution and the Pi groups of an analysis
+pub fn analysis_report(problem: &NamedAnalysis) -> Output {
+    report_output(&Report::new(problem.clone()))
+}
+
+fn report_output(report: &Report) -> Output {
+    let names = report.analysis.names();
+    let variables = std::iter::once(&report.analysis.target).chain(&report.analysis.dependencies);
+    let mut text = String::new();
+    if let Some(title) = &report.title {
+        text.push_str(&format!("{}\n\n", title));
+    }
+    text.push_str(&table(
+        &["variable", "dimension"],
+        &variables
+            .map(|v| vec![v.name.clone(), v.dimension.to_string()])
+            .collect::<Vec<_>>(),
+    ));
+    text.push('\n');
+    text.push_str(&report.relation());
+    text.push('\n');
+    if let Some(diagnosis) = &report.diagnosis {
+        text.push_str(&format!("{}\n", diagnosis));
+    }
+    for line in report.redundancy.format(&names) {
+        text.push_str(&format!("redundant: {}\n", line));
+    }
+    if !report.pi_groups.is_empty() {
+        text.push('\n');
+        let rows: Vec<Vec<String>> = report
+            .pi_groups
+            .iter()
+            .enumerate()
+            .map(|(i, g)| {
+                let mut row = vec![format!("Π{}", i + 1), g.format(&names)];
+                row.extend(report.reading(i));
+                row
+            })
+            .collect();
+        let readings = report.readings.iter().any(|r| !r.is_empty());
+        let headers: &[&str] = if readings {
+            &["group", "product", "reading"]
+        } else {
+            &["group", "product"]
+        };
+        text.push_str(&table(headers, &rows));
+    }
+    Output {
+        json: report.to_json(),
+        table: text.trim_end().to_string(),
+    }
+}
+
+// a value rounded to 12 significant digits, without float noise
+fn number(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+    let rounded = round_significant(value, 12);
+    match rounded.abs() {
+        a if !(1e-6..1e15).contains(&a) => format!("{:e}", rounded),
+        _ => rounded.to_string(),
+    }
+}
+
+// columns padded to the widest cell
+fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain([headers.len()])
+        .max()
+        .unwrap_or(0);
+    let width = |j: usize| {
+        rows.iter()
+            .filter_map(|r| r.get(j))
+            .map(|c| c.chars().count())
+            .chain(headers.get(j).map(|h| h.chars().count()))
+            .max()
+            .unwrap_or(0)
+    };
+    let widths: Vec<usize> = (0..columns).map(width).collect();
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{}{}", c, " ".repeat(w - c.chars().count())))
+            .collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+    let mut text = String::new();
+    if !headers.is_empty() {
+        text.push_str(&line(headers.to_vec()));
+        let rules: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        text.push_str(&line(rules.iter().map(String::as_str).collect()));
+    }
+    for row in rows {
+        text.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    text
+}
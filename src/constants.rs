@@ -0,0 +1,539 @@
+#![doc = include_str!("../doc/constants.md")]
+use crate::dimensional::Dimension;
+use crate::expression::DerivedUnit;
+use crate::matrix::Matrix;
+use crate::quantity::{Quantity, power_product};
+use crate::units::{Unit, UnitError, catalog};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// CODATA 2022 values in the column layout of NIST's `allascii.txt`
+pub const CODATA_2022: &str = include_str!("data/codata2022.txt");
+/// CODATA 2018 values of the same constants, for comparison with older results
+pub const CODATA_2018: &str = include_str!("data/codata2018.txt");
+
+/// A fundamental physical constant of a CODATA adjustment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constant {
+    /// NIST name, e.g. `Newtonian constant of gravitation`
+    pub name: String,
+    pub symbol: Option<String>,
+    pub value: f64,
+    /// Standard uncertainty, zero for exact constants
+    pub uncertainty: f64,
+    /// Exact by definition of the SI, or derived from exact constants only
+    pub exact: bool,
+    /// The unit as listed by NIST, e.g. `m^3 kg^-1 s^-2`
+    pub unit_symbol: String,
+    /// QUDT unit of `value`, `None` if the listed unit is not in the catalog
+    pub unit: Option<Unit>,
+    pub dimension: Option<Dimension>,
+}
+
+/// The constants of a CODATA listing
+#[derive(Clone, Debug, Default)]
+pub struct Codata {
+    constants: Vec<Constant>,
+    index: HashMap<String, usize>,
+}
+
+/// Symbols for the NIST names
+const SYMBOLS: &[(&str, &str)] = &[
+    ("speed of light in vacuum", "c"),
+    ("Planck constant", "h"),
+    ("reduced Planck constant", "ħ"),
+    ("elementary charge", "e"),
+    ("Boltzmann constant", "k_B"),
+    ("Avogadro constant", "N_A"),
+    ("hyperfine transition frequency of Cs-133", "Δν_Cs"),
+    ("luminous efficacy", "K_cd"),
+    ("Newtonian constant of gravitation", "G"),
+    ("fine-structure constant", "α"),
+    ("inverse fine-structure constant", "α^-1"),
+    ("Rydberg constant", "R∞"),
+    ("electron mass", "m_e"),
+    ("proton mass", "m_p"),
+    ("neutron mass", "m_n"),
+    ("deuteron mass", "m_d"),
+    ("alpha particle mass", "m_α"),
+    ("muon mass", "m_μ"),
+    ("tau mass", "m_τ"),
+    ("atomic mass constant", "m_u"),
+    ("molar mass constant", "M_u"),
+    ("electron relative atomic mass", "A_r(e)"),
+    ("proton relative atomic mass", "A_r(p)"),
+    ("neutron relative atomic mass", "A_r(n)"),
+    ("proton-electron mass ratio", "m_p/m_e"),
+    ("Bohr radius", "a_0"),
+    ("Hartree energy", "E_h"),
+    ("classical electron radius", "r_e"),
+    ("Compton wavelength", "λ_C"),
+    ("Thomson cross section", "σ_e"),
+    ("Bohr magneton", "μ_B"),
+    ("nuclear magneton", "μ_N"),
+    ("electron magnetic moment", "μ_e"),
+    ("electron g factor", "g_e"),
+    ("vacuum mag. permeability", "μ_0"),
+    ("vacuum electric permittivity", "ε_0"),
+    ("characteristic impedance of vacuum", "Z_0"),
+    ("Faraday constant", "F"),
+    ("molar gas constant", "R"),
+    ("Stefan-Boltzmann constant", "σ"),
+    ("Wien wavelength displacement law constant", "b"),
+    ("first radiation constant", "c_1"),
+    ("second radiation constant", "c_2"),
+    ("Josephson constant", "K_J"),
+    ("von Klitzing constant", "R_K"),
+    ("magnetic flux quantum", "Φ_0"),
+    ("conductance quantum", "G_0"),
+    ("Loschmidt constant (273.15 K, 101.325 kPa)", "n_0"),
+    ("Planck length", "l_P"),
+    ("Planck mass", "m_P"),
+    ("Planck time", "t_P"),
+    ("Planck temperature", "T_P"),
+    ("standard acceleration of gravity", "g_n"),
+    ("standard atmosphere", "atm"),
+    ("electron volt", "eV"),
+    ("Fermi coupling constant", "G_F/(ħc)^3"),
+    ("weak mixing angle", "sin²θ_W"),
+];
+
+/// NIST unit symbols that the expression parser does not resolve
+const UNIT_ALIASES: &[(&str, &str)] = &[("u", "UnifiedAtomicMassUnit"), ("E_h", "Hartree")];
+
+/// The adjusted constants of the CODATA least-squares adjustment. Since the
+/// 2019 redefinition of the SI the other constants of the list follow from
+/// these and the exact h, e, k_B, N_A. Their mutual correlations are read
+/// from the listing (`QUOTIENTS`).
+pub const ADJUSTED: &[&str] = &[
+    "fine-structure constant",
+    "Rydberg constant",
+    "electron relative atomic mass",
+    "proton relative atomic mass",
+    "neutron relative atomic mass",
+    "deuteron relative atomic mass",
+    "alpha particle relative atomic mass",
+    "muon-electron mass ratio",
+    "electron g factor",
+    "tau mass",
+    "Newtonian constant of gravitation",
+    "Fermi coupling constant",
+    "weak mixing angle",
+];
+
+const ALPHA: &str = "fine-structure constant";
+const RYDBERG: &str = "Rydberg constant";
+const A_E: &str = "electron relative atomic mass";
+const A_P: &str = "proton relative atomic mass";
+const G: &str = "Newtonian constant of gravitation";
+
+/// Constants listed as the quotient `a/b` of two adjusted constants. CODATA
+/// computes the uncertainty of a quotient with the covariance of `a` and
+/// `b`, so that `u_r(a/b)² = u_r(a)² + u_r(b)² - 2 r u_r(a) u_r(b)` gives
+/// their correlation coefficient `r`.
+const QUOTIENTS: &[(&str, &str, &str)] = &[
+    ("proton-electron mass ratio", A_P, A_E),
+    (
+        "neutron-electron mass ratio",
+        "neutron relative atomic mass",
+        A_E,
+    ),
+    (
+        "deuteron-electron mass ratio",
+        "deuteron relative atomic mass",
+        A_E,
+    ),
+    (
+        "alpha particle-electron mass ratio",
+        "alpha particle relative atomic mass",
+        A_E,
+    ),
+    (
+        "neutron-proton mass ratio",
+        "neutron relative atomic mass",
+        A_P,
+    ),
+    (
+        "deuteron-proton mass ratio",
+        "deuteron relative atomic mass",
+        A_P,
+    ),
+    (
+        "alpha particle-proton mass ratio",
+        "alpha particle relative atomic mass",
+        A_P,
+    ),
+];
+
+/// The largest error of a correlation coefficient, due to the rounding of
+/// the listed uncertainties, for it to be used
+const CORRELATION_RESOLUTION: f64 = 0.1;
+
+// half a unit in the last of the two significant digits NIST lists, relative
+// to the uncertainty
+fn rounding(uncertainty: f64) -> f64 {
+    let mantissa = uncertainty / 10f64.powf(uncertainty.log10().floor() - 1.0);
+    0.5 / mantissa.round()
+}
+
+/// Constants as powers of the adjusted constants times exact factors,
+/// e.g. `m_e = 2 h R∞ / (c α²)`
+const DEPENDENCES: &[(&str, &[(&str, f64)])] = &[
+    ("inverse fine-structure constant", &[(ALPHA, -1.0)]),
+    ("vacuum mag. permeability", &[(ALPHA, 1.0)]),
+    ("vacuum electric permittivity", &[(ALPHA, -1.0)]),
+    ("characteristic impedance of vacuum", &[(ALPHA, 1.0)]),
+    ("Hartree energy", &[(RYDBERG, 1.0)]),
+    ("Hartree energy in eV", &[(RYDBERG, 1.0)]),
+    ("atomic unit of energy", &[(RYDBERG, 1.0)]),
+    ("Rydberg constant times hc in eV", &[(RYDBERG, 1.0)]),
+    ("Bohr radius", &[(ALPHA, 1.0), (RYDBERG, -1.0)]),
+    ("atomic unit of length", &[(ALPHA, 1.0), (RYDBERG, -1.0)]),
+    (
+        "classical electron radius",
+        &[(ALPHA, 3.0), (RYDBERG, -1.0)],
+    ),
+    ("Thomson cross section", &[(ALPHA, 6.0), (RYDBERG, -2.0)]),
+    ("Compton wavelength", &[(ALPHA, 2.0), (RYDBERG, -1.0)]),
+    (
+        "reduced Compton wavelength",
+        &[(ALPHA, 2.0), (RYDBERG, -1.0)],
+    ),
+    ("Bohr magneton", &[(ALPHA, 2.0), (RYDBERG, -1.0)]),
+    (
+        "electron charge to mass quotient",
+        &[(ALPHA, 2.0), (RYDBERG, -1.0)],
+    ),
+    (
+        "electron magnetic moment",
+        &[("electron g factor", 1.0), (ALPHA, 2.0), (RYDBERG, -1.0)],
+    ),
+    ("electron mass", &[(ALPHA, -2.0), (RYDBERG, 1.0)]),
+    (
+        "electron mass energy equivalent in MeV",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0)],
+    ),
+    (
+        "atomic mass constant",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0), (A_E, -1.0)],
+    ),
+    (
+        "atomic mass constant energy equivalent in MeV",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0), (A_E, -1.0)],
+    ),
+    (
+        "molar mass constant",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0), (A_E, -1.0)],
+    ),
+    (
+        "proton mass",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0), (A_P, 1.0), (A_E, -1.0)],
+    ),
+    (
+        "proton mass energy equivalent in MeV",
+        &[(ALPHA, -2.0), (RYDBERG, 1.0), (A_P, 1.0), (A_E, -1.0)],
+    ),
+    ("proton-electron mass ratio", &[(A_P, 1.0), (A_E, -1.0)]),
+    (
+        "proton charge to mass quotient",
+        &[(ALPHA, 2.0), (RYDBERG, -1.0), (A_P, -1.0), (A_E, 1.0)],
+    ),
+    (
+        "nuclear magneton",
+        &[(ALPHA, 2.0), (RYDBERG, -1.0), (A_P, -1.0), (A_E, 1.0)],
+    ),
+    (
+        "neutron mass",
+        &[
+            (ALPHA, -2.0),
+            (RYDBERG, 1.0),
+            ("neutron relative atomic mass", 1.0),
+            (A_E, -1.0),
+        ],
+    ),
+    (
+        "neutron-proton mass ratio",
+        &[("neutron relative atomic mass", 1.0), (A_P, -1.0)],
+    ),
+    (
+        "deuteron mass",
+        &[
+            (ALPHA, -2.0),
+            (RYDBERG, 1.0),
+            ("deuteron relative atomic mass", 1.0),
+            (A_E, -1.0),
+        ],
+    ),
+    (
+        "alpha particle mass",
+        &[
+            (ALPHA, -2.0),
+            (RYDBERG, 1.0),
+            ("alpha particle relative atomic mass", 1.0),
+            (A_E, -1.0),
+        ],
+    ),
+    (
+        "muon mass",
+        &[
+            (ALPHA, -2.0),
+            (RYDBERG, 1.0),
+            ("muon-electron mass ratio", 1.0),
+        ],
+    ),
+    ("tau mass energy equivalent", &[("tau mass", 1.0)]),
+    ("Planck length", &[(G, 0.5)]),
+    ("Planck time", &[(G, 0.5)]),
+    ("Planck mass", &[(G, -0.5)]),
+    ("Planck temperature", &[(G, -0.5)]),
+];
+
+// `1.054 571 817...e-34` → 1.054571817e-34
+fn parse_number(text: &str) -> Option<f64> {
+    text.replace(' ', "").replace("...", "").parse().ok()
+}
+
+fn parse_unit(symbol: &str) -> Option<Unit> {
+    if symbol.is_empty() {
+        return catalog().get("Unitless").cloned();
+    }
+    if let Some((_, name)) = UNIT_ALIASES.iter().find(|(s, _)| *s == symbol) {
+        return catalog().get(name).cloned();
+    }
+    DerivedUnit::parse(symbol).ok().map(|unit| unit.to_unit())
+}
+
+impl Codata {
+    /// Read a listing in the layout of NIST's `allascii.txt`: name, value,
+    /// uncertainty and unit in fixed columns below a dashed line
+    pub fn parse(text: &str) -> Result<Codata, UnitError> {
+        let mut codata = Codata::default();
+        let column = |chars: &[char], range: std::ops::Range<usize>| -> String {
+            let end = range.end.min(chars.len());
+            chars.get(range.start..end).map_or(String::new(), |c| {
+                c.iter().collect::<String>().trim().to_string()
+            })
+        };
+        for (number, line) in text
+            .lines()
+            .enumerate()
+            .skip_while(|(_, l)| !l.starts_with("-----"))
+            .skip(1)
+        {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let chars: Vec<char> = line.chars().collect();
+            let invalid = |message: &str| UnitError::InvalidExpression {
+                expression: line.to_string(),
+                position: number + 1,
+                message: message.to_string(),
+            };
+            let name = column(&chars, 0..60);
+            let value =
+                parse_number(&column(&chars, 60..85)).ok_or_else(|| invalid("invalid value"))?;
+            let uncertainty_text = column(&chars, 85..110);
+            let exact = uncertainty_text == "(exact)";
+            let uncertainty = match exact {
+                true => 0.0,
+                false => {
+                    parse_number(&uncertainty_text).ok_or_else(|| invalid("invalid uncertainty"))?
+                }
+            };
+            let unit_symbol = column(&chars, 110..chars.len().max(110));
+            let unit = parse_unit(&unit_symbol);
+            codata.insert(Constant {
+                symbol: SYMBOLS
+                    .iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, s)| s.to_string()),
+                name,
+                value,
+                uncertainty,
+                exact,
+                dimension: unit.as_ref().and_then(|u| u.dimension.clone()),
+                unit,
+                unit_symbol,
+            });
+        }
+        Ok(codata)
+    }
+
+    /// Add a constant, replacing any constant with the same name
+    pub fn insert(&mut self, constant: Constant) {
+        match self.index.get(&constant.name) {
+            Some(&i) => self.constants[i] = constant,
+            None => {
+                let i = self.constants.len();
+                self.index.insert(constant.name.clone(), i);
+                if let Some(symbol) = &constant.symbol {
+                    self.index.entry(symbol.clone()).or_insert(i);
+                    self.index.entry(symbol.replace('_', "")).or_insert(i);
+                }
+                self.constants.push(constant);
+            }
+        }
+    }
+
+    /// Look up a constant by NIST name or symbol (`G`, `k_B`, `kB`, `R∞`)
+    pub fn get(&self, name: &str) -> Option<&Constant> {
+        self.index.get(name).map(|&i| &self.constants[i])
+    }
+
+    /// Get a constant or fail with `UnitError::UnknownConstant`
+    pub fn require(&self, name: &str) -> Result<&Constant, UnitError> {
+        self.get(name)
+            .ok_or_else(|| UnitError::UnknownConstant(name.to_string()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Constant> {
+        self.constants.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.constants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constants.is_empty()
+    }
+
+    // relative uncertainty contributions by adjusted constant
+    fn contributions<'a>(&'a self, constant: &'a Constant) -> Vec<(&'a str, f64)> {
+        if constant.exact {
+            return Vec::new();
+        }
+        let powers: Vec<(&str, f64)> = match DEPENDENCES.iter().find(|(n, _)| *n == constant.name) {
+            Some((_, powers)) => powers.to_vec(),
+            // an adjusted constant, or one assumed uncorrelated with the others
+            None => vec![(constant.name.as_str(), 1.0)],
+        };
+        powers
+            .into_iter()
+            .map(|(name, power)| {
+                let input = self.get(name).unwrap_or(constant);
+                (name, power * input.relative_uncertainty())
+            })
+            .collect()
+    }
+
+    /// The correlation coefficient of two adjusted constants, from the
+    /// listed quotient of the two (`QUOTIENTS`). Zero when the listing has no
+    /// quotient, or when its rounded uncertainties do not resolve the
+    /// coefficient to `CORRELATION_RESOLUTION`.
+    pub fn adjusted_correlation(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+        let Some((quotient, a, b)) = QUOTIENTS
+            .iter()
+            .find(|(_, x, y)| (*x, *y) == (a, b) || (*x, *y) == (b, a))
+        else {
+            return 0.0;
+        };
+        let (Some(quotient), Some(a), Some(b)) = (self.get(quotient), self.get(a), self.get(b))
+        else {
+            return 0.0;
+        };
+        if [quotient, a, b].iter().any(|c| c.uncertainty == 0.0) {
+            return 0.0;
+        }
+        let [uq, ua, ub] = [quotient, a, b].map(Constant::relative_uncertainty);
+        let error = [quotient, a, b]
+            .iter()
+            .zip([uq, ua, ub])
+            .map(|(c, u)| 2.0 * rounding(c.uncertainty) * u * u)
+            .sum::<f64>()
+            / (2.0 * ua * ub);
+        if error > CORRELATION_RESOLUTION {
+            return 0.0;
+        }
+        ((ua * ua + ub * ub - uq * uq) / (2.0 * ua * ub)).clamp(-1.0, 1.0)
+    }
+
+    /// The correlation coefficient of two constants, computed from their
+    /// dependence on the adjusted constants (`ADJUSTED`) and the correlations
+    /// of those (`adjusted_correlation`); zero if either is exact. The
+    /// fine-structure constant and the electron mass are almost fully
+    /// anticorrelated: `m_e = 2hR∞/(cα²)`.
+    pub fn correlation(&self, a: &Constant, b: &Constant) -> f64 {
+        if a.name == b.name {
+            return 1.0;
+        }
+        let (ca, cb) = (self.contributions(a), self.contributions(b));
+        let covariance = |ca: &[(&str, f64)], cb: &[(&str, f64)]| -> f64 {
+            ca.iter()
+                .flat_map(|(x, ux)| {
+                    cb.iter()
+                        .map(move |(y, uy)| ux * uy * self.adjusted_correlation(x, y))
+                })
+                .sum()
+        };
+        let (va, vb) = (covariance(&ca, &ca), covariance(&cb, &cb));
+        if va <= 0.0 || vb <= 0.0 {
+            return 0.0;
+        }
+        (covariance(&ca, &cb) / (va * vb).sqrt()).clamp(-1.0, 1.0)
+    }
+
+    /// The correlation matrix of the given constants
+    pub fn correlation_matrix(&self, constants: &[&Constant]) -> Matrix<f64> {
+        let mut matrix = Matrix::new(constants.len(), constants.len());
+        for (i, a) in constants.iter().enumerate() {
+            for (j, b) in constants.iter().enumerate() {
+                matrix[(i, j)] = self.correlation(a, b);
+            }
+        }
+        matrix
+    }
+
+    /// The product of powers of constants given by name or symbol, with the
+    /// uncertainty propagated through their correlations
+    /// (`quantity::power_product`)
+    pub fn product(&self, factors: &[(&str, i32)]) -> Result<Quantity, UnitError> {
+        let constants = factors
+            .iter()
+            .map(|(name, _)| self.require(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let quantities = constants
+            .iter()
+            .map(|c| c.quantity())
+            .collect::<Result<Vec<_>, _>>()?;
+        let factors: Vec<(&Quantity, i32)> = quantities
+            .iter()
+            .zip(factors)
+            .map(|(q, (_, p))| (q, *p))
+            .collect();
+        Ok(power_product(
+            &factors,
+            &self.correlation_matrix(&constants),
+        ))
+    }
+}
+
+impl Constant {
+    /// Relative standard uncertainty `u/|x|`
+    pub fn relative_uncertainty(&self) -> f64 {
+        self.uncertainty / self.value.abs()
+    }
+
+    /// The constant as a measured `Quantity`
+    pub fn quantity(&self) -> Result<Quantity, UnitError> {
+        let unit = self
+            .unit
+            .as_ref()
+            .ok_or_else(|| UnitError::UnknownUnit(self.unit_symbol.clone()))?;
+        Ok(Quantity::new(self.value, unit).with_uncertainty(self.uncertainty))
+    }
+}
+
+/// The bundled CODATA 2022 constants, parsed on first use
+pub fn codata() -> &'static Codata {
+    static CODATA: OnceLock<Codata> = OnceLock::new();
+    CODATA.get_or_init(|| Codata::parse(CODATA_2022).expect("bundled CODATA listing"))
+}
+
+/// Look up a bundled constant by NIST name or symbol
+pub fn constant(name: &str) -> Option<&'static Constant> {
+    codata().get(name)
+}
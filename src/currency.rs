@@ -0,0 +1,525 @@
+#![doc = include_str!("../doc/currency.md")]
+use crate::dimensional::{DIMENSIONLESS, Dimension, DimensionTrait};
+use crate::quantity::Quantity;
+use crate::units::{Unit, UnitError, catalog};
+use std::fmt;
+use std::ops;
+use std::str::FromStr;
+
+/// A QUDT `CurrencyUnit`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Currency {
+    /// Local name of the unit, e.g. `USDollar`
+    pub name: &'static str,
+    pub label: &'static str,
+    /// ISO 4217 alphabetic code, e.g. `USD`
+    pub code: Option<&'static str>,
+    /// ISO 4217 numeric code, e.g. `840`
+    pub numeric_code: Option<&'static str>,
+    /// `qudt:currencyExponent`, the number of decimal places of the minor
+    /// unit; `None` for funds and precious metals
+    pub exponent: Option<u32>,
+    /// Where the currency is used
+    pub description: Option<&'static str>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/currencies.rs"));
+
+/// Look up a currency by ISO code (`EUR`, `978`) or unit name (`Euro`)
+pub fn currency(name: &str) -> Option<&'static Currency> {
+    CURRENCIES
+        .iter()
+        .find(|c| c.code == Some(name))
+        .or_else(|| {
+            CURRENCIES
+                .iter()
+                .find(|c| c.name == name || c.numeric_code == Some(name))
+        })
+}
+
+fn require_currency(name: &str) -> Result<&'static Currency, UnitError> {
+    currency(name).ok_or_else(|| UnitError::UnknownUnit(name.to_string()))
+}
+
+impl Currency {
+    /// The unit of the currency in the catalog
+    pub fn unit(&self) -> &'static Unit {
+        catalog()
+            .get(self.name)
+            .expect("currency units are in the bundled catalog")
+    }
+
+    /// Round an amount half away from zero to the minor unit, unchanged
+    /// without `exponent`. The amount is rounded as written in decimal:
+    /// 1.005 USD is 1.01 USD although the nearest `f64` is 1.00499…
+    pub fn round(&self, amount: f64) -> f64 {
+        match self.exponent {
+            Some(exponent) => round_decimal(amount, exponent as i32),
+            None => amount,
+        }
+    }
+
+    /// The ISO code, or the unit name for currencies without one
+    pub fn code_or_name(&self) -> &'static str {
+        self.code.unwrap_or(self.name)
+    }
+}
+
+// round the shortest decimal representation of `amount` to `places` decimals
+fn round_decimal(amount: f64, places: i32) -> f64 {
+    if !amount.is_finite() {
+        return amount;
+    }
+    // `{:e}` writes the shortest digits that read back as `amount`
+    let text = format!("{:e}", amount.abs());
+    let (mantissa, exponent) = text.split_once('e').expect("exponent in `{:e}`");
+    let exponent: i32 = exponent.parse().expect("integer exponent");
+    let digits = mantissa.replace('.', "");
+    let keep = exponent + 1 + places;
+    if keep < 0 {
+        return 0.0;
+    }
+    let keep = keep as usize;
+    if keep >= digits.len() {
+        return amount;
+    }
+    let mut kept: u64 = digits[..keep].parse().unwrap_or(0);
+    if digits.as_bytes()[keep] >= b'5' {
+        kept += 1;
+    }
+    let rounded: f64 = format!("{}e{}", kept, exponent + 1 - keep as i32)
+        .parse()
+        .expect("decimal number");
+    rounded.copysign(amount)
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code_or_name())
+    }
+}
+
+/// A dimension with currency as an eighth base dimension ¤ next to the
+/// seven of `Dimension`: a price per litre is ¤L^-3
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonetaryDimension {
+    pub currency: i32,
+    pub physical: Dimension,
+}
+
+impl MonetaryDimension {
+    pub fn new(currency: i32, physical: Dimension) -> Self {
+        MonetaryDimension { currency, physical }
+    }
+}
+
+impl fmt::Display for MonetaryDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let physical = match self.physical == DIMENSIONLESS {
+            true => String::new(),
+            false => self.physical.to_string(),
+        };
+        match self.currency {
+            0 => write!(f, "{}", self.physical),
+            1 => write!(f, "¤{}", physical),
+            n => write!(f, "¤^{}{}", n, physical),
+        }
+    }
+}
+
+impl ops::Mul for MonetaryDimension {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        MonetaryDimension::new(
+            self.currency + rhs.currency,
+            (self.physical * rhs.physical).standard_form(),
+        )
+    }
+}
+
+impl ops::Div for MonetaryDimension {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        MonetaryDimension::new(
+            self.currency - rhs.currency,
+            (self.physical / rhs.physical).standard_form(),
+        )
+    }
+}
+
+/// An amount of money, or a price per unit of a physical quantity
+#[derive(Clone, Debug, PartialEq)]
+pub struct Money {
+    pub amount: f64,
+    pub currency: &'static Currency,
+    /// The unit the amount is charged per, e.g. `Liter`; `None` for a
+    /// plain amount
+    pub per: Option<Unit>,
+}
+
+impl Money {
+    /// A plain amount, rounded to the minor unit of the currency
+    pub fn new(amount: f64, currency: &'static Currency) -> Self {
+        Money {
+            amount: currency.round(amount),
+            currency,
+            per: None,
+        }
+    }
+
+    /// A plain amount in the currency with the given ISO code or name
+    pub fn of(amount: f64, currency: &str) -> Result<Self, UnitError> {
+        Ok(Money::new(amount, require_currency(currency)?))
+    }
+
+    /// A price per unit; prices are not rounded, a litre of fuel can cost
+    /// 1.799 EUR
+    pub fn price(amount: f64, currency: &'static Currency, per: &Unit) -> Self {
+        Money {
+            amount,
+            currency,
+            per: Some(per.clone()),
+        }
+    }
+
+    /// ¤ for an amount, ¤ divided by the dimension of `per` for a price
+    pub fn dimension(&self) -> MonetaryDimension {
+        let physical = match &self.per {
+            Some(unit) => {
+                (DIMENSIONLESS / unit.dimension.clone().unwrap_or(DIMENSIONLESS)).standard_form()
+            }
+            None => DIMENSIONLESS,
+        };
+        MonetaryDimension::new(1, physical)
+    }
+
+    /// The amount rounded to the minor unit of the currency
+    pub fn rounded(&self) -> Money {
+        Money {
+            amount: self.currency.round(self.amount),
+            ..self.clone()
+        }
+    }
+
+    fn mismatch(&self, other: &Money) -> UnitError {
+        UnitError::IncompatibleDimensions {
+            from: other.to_string(),
+            to: self.to_string(),
+        }
+    }
+
+    /// Sum of two amounts or two prices in the same currency; the price
+    /// of `other` is converted to the unit of `self`
+    pub fn checked_add(&self, other: &Money) -> Result<Money, UnitError> {
+        if self.currency != other.currency {
+            return Err(self.mismatch(other));
+        }
+        let amount = match (&self.per, &other.per) {
+            (None, None) => other.amount,
+            (Some(a), Some(b)) if a.name == b.name => other.amount,
+            // the price per unit scales inversely with the unit
+            (Some(a), Some(b)) => other.amount * a.convert(1.0, b)?,
+            _ => return Err(self.mismatch(other)),
+        };
+        let sum = Money {
+            amount: self.amount + amount,
+            ..self.clone()
+        };
+        Ok(match sum.per {
+            Some(_) => sum,
+            None => sum.rounded(),
+        })
+    }
+
+    /// The cost of `quantity` at this price, rounded to the minor unit
+    pub fn times(&self, quantity: &Quantity) -> Result<Money, UnitError> {
+        let per = self
+            .per
+            .as_ref()
+            .ok_or_else(|| UnitError::IncompatibleDimensions {
+                from: quantity.unit.name.clone(),
+                to: self.to_string(),
+            })?;
+        let value = match quantity.unit.name == per.name {
+            true => quantity.value,
+            false => quantity.convert_to(per)?.value,
+        };
+        Ok(Money::new(self.amount * value, self.currency))
+    }
+
+    /// The price per unit of `quantity`: the cost per measurement is the
+    /// total cost divided by the number of measurements
+    pub fn divide(&self, quantity: &Quantity) -> Result<Money, UnitError> {
+        if self.per.is_some() {
+            return Err(UnitError::IncompatibleDimensions {
+                from: quantity.unit.name.clone(),
+                to: self.to_string(),
+            });
+        }
+        Ok(Money::price(
+            self.amount / quantity.value,
+            self.currency,
+            &quantity.unit,
+        ))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.per, self.currency.exponent) {
+            (None, Some(exponent)) => {
+                write!(f, "{:.*} {}", exponent as usize, self.amount, self.currency)?
+            }
+            _ => write!(f, "{} {}", self.amount, self.currency)?,
+        }
+        match &self.per {
+            Some(unit) => write!(f, "/{}", unit.display_symbol()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A point in time in UTC, `2024-03-01` or `2024-03-01T16:00:00Z`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl Timestamp {
+    pub fn date(year: i32, month: u32, day: u32) -> Self {
+        Timestamp {
+            year,
+            month,
+            day,
+            ..Timestamp::default()
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = UnitError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = |position: usize, message: &str| UnitError::InvalidExpression {
+            expression: text.to_string(),
+            position,
+            message: message.to_string(),
+        };
+        let trimmed = text.trim().trim_end_matches('Z');
+        let (date, time) = trimmed.split_once(['T', ' ']).unwrap_or((trimmed, ""));
+        let date: Vec<&str> = date.split('-').collect();
+        let [year, month, day] = date[..] else {
+            return Err(invalid(0, "expected a date YYYY-MM-DD"));
+        };
+        let number = |field: &str, position: usize| {
+            field
+                .parse::<u32>()
+                .map_err(|_| invalid(position, "invalid number"))
+        };
+        let mut timestamp = Timestamp {
+            year: year.parse().map_err(|_| invalid(0, "invalid year"))?,
+            month: number(month, 5)?,
+            day: number(day, 8)?,
+            ..Timestamp::default()
+        };
+        if !(1..=12).contains(&timestamp.month) || !(1..=31).contains(&timestamp.day) {
+            return Err(invalid(5, "month or day out of range"));
+        }
+        if !time.is_empty() {
+            let time: Vec<&str> = time.split(':').collect();
+            let (hour, minute, second) = match time[..] {
+                [hour, minute] => (hour, minute, "0"),
+                [hour, minute, second] => (hour, minute, second),
+                _ => return Err(invalid(11, "expected a time HH:MM[:SS]")),
+            };
+            timestamp.hour = number(hour, 11)?;
+            timestamp.minute = number(minute, 14)?;
+            timestamp.second = number(second, 17)?;
+            if timestamp.hour > 23 || timestamp.minute > 59 || timestamp.second > 60 {
+                return Err(invalid(11, "time out of range"));
+            }
+        }
+        Ok(timestamp)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)?;
+        if (self.hour, self.minute, self.second) != (0, 0, 0) {
+            write!(
+                f,
+                "T{:02}:{:02}:{:02}Z",
+                self.hour, self.minute, self.second
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// One unit of `from` is worth `rate` units of `to` from `at` on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExchangeRate {
+    pub at: Timestamp,
+    pub from: &'static Currency,
+    pub to: &'static Currency,
+    pub rate: f64,
+}
+
+/// Exchange rates over time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExchangeRates {
+    /// Sorted by time
+    rates: Vec<ExchangeRate>,
+}
+
+const COLUMNS: [&[&str]; 4] = [
+    &["timestamp", "date", "time"],
+    &["from", "base"],
+    &["to", "quote"],
+    &["rate"],
+];
+
+impl ExchangeRates {
+    pub fn new() -> Self {
+        ExchangeRates::default()
+    }
+
+    /// Read rates from CSV with a header naming the columns `timestamp`
+    /// (or `date`), `from` (or `base`), `to` (or `quote`) and `rate`, e.g.
+    /// `2024-03-01,EUR,USD,1.0838`. Lines starting with `#` are comments.
+    pub fn from_csv(text: &str) -> Result<Self, UnitError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+        let invalid = |line: &str, number: usize, message: &str| UnitError::InvalidExpression {
+            expression: line.to_string(),
+            position: number + 1,
+            message: message.to_string(),
+        };
+        let Some((number, header)) = lines.next() else {
+            return Ok(ExchangeRates::new());
+        };
+        let header: Vec<String> = header.split(',').map(|h| h.trim().to_lowercase()).collect();
+        let mut columns = [0; 4];
+        for (column, names) in columns.iter_mut().zip(COLUMNS) {
+            *column = header
+                .iter()
+                .position(|h| names.contains(&h.as_str()))
+                .ok_or_else(|| {
+                    invalid(
+                        &header.join(","),
+                        number,
+                        &format!("missing column `{}`", names[0]),
+                    )
+                })?;
+        }
+        let mut rates = ExchangeRates::new();
+        for (number, line) in lines {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |i: usize| {
+                fields
+                    .get(columns[i])
+                    .copied()
+                    .ok_or_else(|| invalid(line, number, "missing field"))
+            };
+            let rate = ExchangeRate {
+                at: field(0)?.parse()?,
+                from: require_currency(field(1)?)?,
+                to: require_currency(field(2)?)?,
+                rate: field(3)?
+                    .parse()
+                    .map_err(|_| invalid(line, number, "invalid rate"))?,
+            };
+            if !(rate.rate.is_finite() && rate.rate > 0.0) {
+                return Err(invalid(line, number, "rates must be positive"));
+            }
+            rates.insert(rate);
+        }
+        Ok(rates)
+    }
+
+    /// Add a rate, keeping the table in time order
+    pub fn insert(&mut self, rate: ExchangeRate) {
+        let index = self.rates.partition_point(|r| r.at <= rate.at);
+        self.rates.insert(index, rate);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ExchangeRate> {
+        self.rates.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    // the latest quote of the pair in either direction
+    fn quote(&self, from: &Currency, to: &Currency, at: Timestamp) -> Option<f64> {
+        self.rates
+            .iter()
+            .rev()
+            .filter(|r| r.at <= at)
+            .find_map(|r| {
+                if (r.from, r.to) == (from, to) {
+                    Some(r.rate)
+                } else if (r.from, r.to) == (to, from) {
+                    Some(1.0 / r.rate)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// The rate in effect at `at`: the latest quote of the pair, inverted
+    /// if quoted the other way round, or else crossed through a currency
+    /// quoted against both
+    pub fn rate(&self, from: &Currency, to: &Currency, at: Timestamp) -> Result<f64, UnitError> {
+        if from == to {
+            return Ok(1.0);
+        }
+        if let Some(rate) = self.quote(from, to, at) {
+            return Ok(rate);
+        }
+        self.rates
+            .iter()
+            .flat_map(|r| [r.from, r.to])
+            .filter(|c| *c != from && *c != to)
+            .find_map(|via| Some(self.quote(from, via, at)? * self.quote(via, to, at)?))
+            .ok_or_else(|| UnitError::MissingExchangeRate {
+                from: from.to_string(),
+                to: to.to_string(),
+                at: at.to_string(),
+            })
+    }
+
+    /// Convert money at the rate in effect at `at`; amounts are rounded to
+    /// the minor unit of `to`, prices are not
+    pub fn convert(
+        &self,
+        money: &Money,
+        to: &'static Currency,
+        at: Timestamp,
+    ) -> Result<Money, UnitError> {
+        let converted = Money {
+            amount: money.amount * self.rate(money.currency, to, at)?,
+            currency: to,
+            per: money.per.clone(),
+        };
+        Ok(match converted.per {
+            Some(_) => converted,
+            None => converted.rounded(),
+        })
+    }
+}
@@ -1,8 +1,10 @@
 #![doc = include_str!("../doc/dimensional.md")]
+use crate::units::UnitError;
 use num::Rational32;
 use std::clone::Clone;
 use std::fmt;
 use std::ops;
+use std::str::FromStr;
 
 #[derive(Clone, Debug)]
 pub enum Dimension {
@@ -36,6 +38,11 @@ pub enum Dimension {
         original: Box<Dimension>,
         power: Rational32,
     },
+    // tagged with a QUDT quantity kind such as Torque, compared by exponents only
+    Kind {
+        original: Box<Dimension>,
+        kind: String,
+    },
 }
 
 pub const DIMENSIONAL_LABELS: [&str; 7] = ["L", "M", "T", "I", "Θ", "N", "J"];
@@ -74,7 +81,10 @@ pub const MAGNETIC_FLUX_DENSITY: Dimension = Dimension::PowerLaw(1, 1, -1, -1, 0
 
 impl fmt::Display for Dimension {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_dimension_string())
+        match self.kind() {
+            Some(kind) => write!(f, "{} ({})", self.to_dimension_string(), kind),
+            None => write!(f, "{}", self.to_dimension_string()),
+        }
     }
 }
 
@@ -129,7 +139,7 @@ impl Dimension {
             }
         }
         if result.is_empty() {
-            result.push_str("-"); // 如果所有维度都是0，显示为1
+            result.push('-'); // 如果所有维度都是0，显示为1
         }
         result
     }
@@ -190,7 +200,75 @@ impl Dimension {
                 let ae = original.get_exponents();
                 ae.map(|e| e * *power)
             }
+            Dimension::Kind { original, .. } => original.get_exponents(),
+        }
+    }
+
+    /// 解析量纲字符串，接受 `Display` 的格式（`L^2MT^-2`、`-`、`L^2MT^-2 (Torque)`），
+    /// 以及 `L·T^-2`、`L T-2`、`L/T^2`、`L^1/2`、`Theta` 等写法
+    pub fn parse(text: &str) -> Result<Dimension, UnitError> {
+        let error = |position: usize, message: &str| UnitError::InvalidExpression {
+            expression: text.to_string(),
+            position,
+            message: message.to_string(),
+        };
+        let (body, kind) = match text
+            .trim_end()
+            .strip_suffix(')')
+            .and_then(|t| t.rsplit_once(" ("))
+        {
+            Some((body, kind)) => (body, Some(kind.trim())),
+            None => (text, None),
+        };
+        let chars: Vec<(usize, char)> = body.char_indices().collect();
+        let mut exponents = [Rational32::from_integer(0); 7];
+        let mut i = 0;
+        let mut divide = false;
+        let mut factors = 0;
+        while i < chars.len() {
+            let (position, c) = chars[i];
+            match c {
+                ' ' | '·' | '*' | '.' => i += 1,
+                '/' if factors > 0 && !divide => {
+                    divide = true;
+                    i += 1;
+                }
+                '-' | '1' if body.trim() == c.to_string() => i += 1,
+                _ => {
+                    let rest = &body[position..];
+                    let (index, length) =
+                        match DIMENSIONAL_LABELS.iter().position(|l| rest.starts_with(l)) {
+                            _ if rest.starts_with("Theta") => (4, 5),
+                            Some(index) => (index, 1),
+                            None => return Err(error(position, "expected one of L M T I Θ N J")),
+                        };
+                    i += length;
+                    let (exponent, next) =
+                        parse_exponent(&chars, i).map_err(|at| error(at, "invalid exponent"))?;
+                    i = next;
+                    exponents[index] += if divide { -exponent } else { exponent };
+                    divide = false;
+                    factors += 1;
+                }
+            }
+        }
+        if divide {
+            return Err(error(body.len(), "missing dimension after `/`"));
+        }
+        if body.trim().is_empty() {
+            return Err(error(
+                0,
+                "empty dimension, write `-` for a dimensionless one",
+            ));
         }
+        let dimension = match exponents.iter().all(|e| e.is_integer()) {
+            true => Dimension::PowerLawIA(exponents.map(|e| *e.numer())),
+            false => Dimension::PowerLawRA(exponents),
+        };
+        Ok(match kind {
+            Some(kind) => dimension.with_kind(kind),
+            None => dimension,
+        })
     }
 
     /// 获取指定维度的指数
@@ -256,6 +334,47 @@ impl Dimension {
     pub fn sqrt(self) -> Self {
         self.pow(Rational32::new(1, 2))
     }
+
+    /// 标记量的种类（QUDT quantity kind 的名称，例如 `Torque`），替换原有标记
+    pub fn with_kind(self, kind: &str) -> Self {
+        Dimension::Kind {
+            original: Box::new(self.without_kind()),
+            kind: kind.to_string(),
+        }
+    }
+
+    /// 量的种类标记
+    pub fn kind(&self) -> Option<&str> {
+        match self {
+            Dimension::Kind { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// 去掉量的种类标记
+    pub fn without_kind(self) -> Self {
+        match self {
+            Dimension::Kind { original, .. } => *original,
+            other => other,
+        }
+    }
+
+    /// 检查量纲相等且种类标记一致（未标记的量纲与任何种类一致）
+    pub fn is_same_kind(&self, other: &Self) -> bool {
+        self == other
+            && match (self.kind(), other.kind()) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+
+    // 加减运算保留两侧共同的种类标记
+    fn keep_common_kind(&self, rhs: &Self, result: Self) -> Self {
+        match (self.kind(), rhs.kind()) {
+            (Some(a), Some(b)) if a == b => result.with_kind(a),
+            _ => result,
+        }
+    }
 }
 
 impl ops::Add for Dimension {
@@ -264,7 +383,7 @@ impl ops::Add for Dimension {
     fn add(self, rhs: Self) -> Self::Output {
         // check eq of self, rhs, panic if not eq
         if self == rhs {
-            self.standard_form()
+            self.keep_common_kind(&rhs, self.standard_form())
         } else {
             panic!("{} != {}, no add operation allowed.", self, rhs)
         }
@@ -277,7 +396,7 @@ impl ops::Sub for Dimension {
     fn sub(self, rhs: Self) -> Self::Output {
         // check eq of self, rhs, panic if not eq
         if self == rhs {
-            self.standard_form()
+            self.keep_common_kind(&rhs, self.standard_form())
         } else {
             panic!("{} != {}, no sub operation allowed.", self, rhs)
         }
@@ -306,6 +425,64 @@ impl ops::Div for Dimension {
     }
 }
 
+// the exponent after a label at `start`: `^-2`, `^(1/2)`, `^1/2`, `-2` or none;
+// returns the exponent and the index after it, or the position of an error
+fn parse_exponent(chars: &[(usize, char)], start: usize) -> Result<(Rational32, usize), usize> {
+    let mut i = start;
+    let caret = chars.get(i).is_some_and(|(_, c)| *c == '^');
+    if caret {
+        i += 1;
+    }
+    let parenthesized = caret && chars.get(i).is_some_and(|(_, c)| *c == '(');
+    if parenthesized {
+        i += 1;
+    }
+    let integer = |i: &mut usize| -> Option<i32> {
+        let begin = *i;
+        if chars.get(*i).is_some_and(|(_, c)| *c == '-' || *c == '+') {
+            *i += 1;
+        }
+        while chars.get(*i).is_some_and(|(_, c)| c.is_ascii_digit()) {
+            *i += 1;
+        }
+        let text: String = chars[begin..*i].iter().map(|(_, c)| c).collect();
+        text.parse().ok()
+    };
+    let end = chars.last().map_or(0, |(p, c)| p + c.len_utf8());
+    let position = |i: usize| chars.get(i).map_or(end, |(p, _)| *p);
+    let numer = match integer(&mut i) {
+        Some(numer) => numer,
+        None if caret => return Err(position(start)),
+        None => return Ok((Rational32::from_integer(1), start)),
+    };
+    let mut exponent = Rational32::from_integer(numer);
+    if (caret || parenthesized) && chars.get(i).is_some_and(|(_, c)| *c == '/') {
+        let slash = i;
+        i += 1;
+        match integer(&mut i) {
+            Some(denom) if denom != 0 => exponent = Rational32::new(numer, denom),
+            _ if parenthesized => return Err(position(slash)),
+            // `L^2/T`: the slash divides
+            _ => i = slash,
+        }
+    }
+    if parenthesized {
+        match chars.get(i) {
+            Some((_, ')')) => i += 1,
+            _ => return Err(position(i)),
+        }
+    }
+    Ok((exponent, i))
+}
+
+impl FromStr for Dimension {
+    type Err = UnitError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Dimension::parse(text)
+    }
+}
+
 impl ops::Index<usize> for Dimension {
     type Output = Rational32;
 
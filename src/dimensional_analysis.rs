@@ -1,16 +1,182 @@
 #![doc = include_str!("../doc/dimensional_analysis.md")]
+use crate::constants::codata;
 use crate::dimensional::*;
+use crate::expression::DerivedUnit;
 use crate::matrix::Matrix;
+use crate::quantity_kind::{QuantityKind, quantity_kinds};
+use crate::units::UnitError;
+use num::Integer;
 use num::rational::Rational32;
 use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DimensionalAnalysis {
     pub target: Dimension,
     pub dependencies: Vec<Dimension>,
 }
 
-#[derive(Debug)]
+/// A named variable of an analysis, e.g. `g` with the dimension LT^-2
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variable {
+    pub name: String,
+    pub dimension: Dimension,
+}
+
+impl Variable {
+    pub fn new(name: &str, dimension: Dimension) -> Self {
+        Variable {
+            name: name.to_string(),
+            dimension,
+        }
+    }
+
+    /// Declare a variable by a dimension string (`LT^-2`), a QUDT quantity
+    /// kind (`Acceleration`, case-insensitive) or a unit expression
+    /// (`m/s^2`), tried in this order. The prefixes `dim:`, `kind:` and
+    /// `unit:` select one reading, e.g. `unit:N` for the newton rather than
+    /// the amount of substance.
+    pub fn parse(name: &str, spec: &str) -> Result<Variable, UnitError> {
+        let spec = spec.trim();
+        let dimension = if let Some(text) = spec.strip_prefix("dim:") {
+            Dimension::parse(text.trim())?
+        } else if let Some(text) = spec.strip_prefix("kind:") {
+            kind_dimension(text.trim())?
+        } else if let Some(text) = spec.strip_prefix("unit:") {
+            unit_dimension(text.trim())?
+        } else {
+            Dimension::parse(spec)
+                .or_else(|_| kind_dimension(spec))
+                .or_else(|_| unit_dimension(spec))
+                .map_err(|_| UnitError::InvalidExpression {
+                    expression: spec.to_string(),
+                    position: 0,
+                    message: "neither a dimension, a quantity kind nor a unit".to_string(),
+                })?
+        };
+        Ok(Variable::new(name, dimension))
+    }
+}
+
+// the dimension of a quantity kind, tagged with the kind
+fn kind_dimension(name: &str) -> Result<Dimension, UnitError> {
+    let kinds = quantity_kinds();
+    let kind = kinds
+        .get(name)
+        .or_else(|| {
+            kinds
+                .iter()
+                .find(|k| k.name.eq_ignore_ascii_case(name) || k.label.eq_ignore_ascii_case(name))
+        })
+        .ok_or_else(|| UnitError::UnknownQuantityKind(name.to_string()))?;
+    let dimension = kind
+        .dimension
+        .clone()
+        .ok_or_else(|| UnitError::UnknownQuantityKind(name.to_string()))?;
+    Ok(dimension.with_kind(&kind.name))
+}
+
+fn unit_dimension(expression: &str) -> Result<Dimension, UnitError> {
+    DerivedUnit::parse(expression)?
+        .dimension()
+        .ok_or_else(|| UnitError::UnknownUnit(expression.to_string()))
+}
+
+/// A `DimensionalAnalysis` whose target and dependencies carry names
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamedAnalysis {
+    pub target: Variable,
+    pub dependencies: Vec<Variable>,
+}
+
+impl NamedAnalysis {
+    /// The analysis without the names
+    pub fn analysis(&self) -> DimensionalAnalysis {
+        DimensionalAnalysis {
+            target: self.target.dimension.clone(),
+            dependencies: self
+                .dependencies
+                .iter()
+                .map(|v| v.dimension.clone())
+                .collect(),
+        }
+    }
+
+    pub fn solve(&self) -> DimensionalAnalysisSolution {
+        self.analysis().solve()
+    }
+
+    /// The names of the target and the dependencies, in this order
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once(&self.target)
+            .chain(&self.dependencies)
+            .map(|v| v.name.as_str())
+            .collect()
+    }
+
+    pub fn pi_groups(&self) -> Vec<PiGroup> {
+        self.analysis().pi_groups()
+    }
+
+    pub fn diagnose(&self) -> Option<Diagnosis> {
+        self.analysis().diagnose()
+    }
+
+    pub fn redundancy(&self) -> Redundancy {
+        self.analysis().redundancy()
+    }
+}
+
+/// A dimensionless product `Π = y^k₀ · x₁^k₁ · … · xₙ^kₙ` of the variables
+/// of an analysis
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PiGroup {
+    /// The integer exponents of the target followed by the dependencies
+    pub exponents: Vec<i32>,
+}
+
+impl PiGroup {
+    /// Whether the variable at `index` (0 for the target) appears
+    pub fn contains(&self, index: usize) -> bool {
+        self.exponents.get(index).is_some_and(|&k| k != 0)
+    }
+
+    /// The product written with the variable names, e.g. `t^2·l^-1·g`
+    pub fn format(&self, names: &[&str]) -> String {
+        let factors: Vec<String> = self
+            .exponents
+            .iter()
+            .zip(names)
+            .filter(|(k, _)| **k != 0)
+            .map(|(k, name)| match k {
+                1 => name.to_string(),
+                k => format!("{}^{}", name, k),
+            })
+            .collect();
+        match factors.is_empty() {
+            true => "1".to_string(),
+            false => factors.join("·"),
+        }
+    }
+}
+
+impl From<&DimensionalAnalysis> for NamedAnalysis {
+    /// Name the target `y` and the dependencies `x1`, `x2`, ...
+    fn from(analysis: &DimensionalAnalysis) -> Self {
+        NamedAnalysis {
+            target: Variable::new("y", analysis.target.clone()),
+            dependencies: analysis
+                .dependencies
+                .iter()
+                .enumerate()
+                .map(|(i, d)| Variable::new(&format!("x{}", i + 1), d.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum DimensionalAnalysisSolution {
     NoSolution,
     UniqueSolution(Matrix<Rational32>),
@@ -27,7 +193,7 @@ impl fmt::Display for DimensionalAnalysisSolution {
         match self {
             DimensionalAnalysisSolution::NoSolution => write!(f, "No feasible solution."),
             DimensionalAnalysisSolution::UniqueSolution(v) => {
-                write!(f, "Unique solution:\n")?;
+                writeln!(f, "Unique solution:")?;
                 write!(f, "{}", v)
             }
             DimensionalAnalysisSolution::MultipleSolutions {
@@ -45,6 +211,136 @@ impl fmt::Display for DimensionalAnalysisSolution {
     }
 }
 
+/// A quantity whose addition to the dependencies makes an analysis solvable
+#[derive(Clone, Debug, PartialEq)]
+pub enum Candidate {
+    /// A QUDT quantity kind of the missing dimension, e.g. `ThermodynamicTemperature`
+    Kind(String),
+    /// A CODATA constant, e.g. `k_B` (Boltzmann constant)
+    Constant { symbol: String, name: String },
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Candidate::Kind(name) => write!(f, "{}", name),
+            Candidate::Constant { symbol, name } => write!(f, "{} ({})", symbol, name),
+        }
+    }
+}
+
+/// Why an analysis has no solution
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnosis {
+    /// Base dimensions, as indices into `DIMENSIONAL_LABELS`, whose
+    /// exponents the dependencies cannot match, see `diagnose`
+    pub inconsistent: Vec<usize>,
+    /// The dimension the dependencies lack: with one more variable of this
+    /// dimension the analysis has a solution
+    pub missing: Dimension,
+    /// Quantity kinds of the missing dimension, then CODATA constants that
+    /// make the analysis solvable
+    pub candidates: Vec<Candidate>,
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<&str> = self
+            .inconsistent
+            .iter()
+            .map(|&d| DIMENSIONAL_LABELS[d])
+            .collect();
+        match labels.len() {
+            1 => write!(f, "the exponent of {} cannot be matched", labels[0])?,
+            _ => write!(
+                f,
+                "the exponents of {} cannot be matched",
+                join_or(&labels, "and")
+            )?,
+        }
+        write!(f, "; add a quantity carrying {}", self.missing)?;
+        if !self.candidates.is_empty() {
+            let candidates: Vec<String> =
+                self.candidates.iter().map(Candidate::to_string).collect();
+            let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+            write!(f, ", e.g. {}", join_or(&candidates, "or"))?;
+        }
+        Ok(())
+    }
+}
+
+// `a, b and c`
+fn join_or(items: &[&str], conjunction: &str) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} {} {}", rest.join(", "), conjunction, last),
+        None => String::new(),
+    }
+}
+
+/// Dependencies that are not dimensionally independent
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Redundancy {
+    /// Indices into `dependencies` of the dimensionless dependencies
+    pub dimensionless: Vec<usize>,
+    /// Indices into `dependencies` of dependencies with the same dimension,
+    /// one group per dimension
+    pub duplicates: Vec<Vec<usize>>,
+    /// Dimensionless products of dependencies, one per dependency that is
+    /// not a pivot of the reduced row echelon form; the exponent of the
+    /// target is zero. The variables of each product are dimensionally
+    /// dependent, e.g. density and specific volume.
+    pub dependent: Vec<PiGroup>,
+}
+
+impl Redundancy {
+    /// Whether the dependencies are dimensionally independent
+    pub fn is_empty(&self) -> bool {
+        self.dimensionless.is_empty() && self.duplicates.is_empty() && self.dependent.is_empty()
+    }
+
+    /// The redundant dependencies written with the names of the target and
+    /// the dependencies: the dimensionless ones, duplicated dimensions and
+    /// pairs whose dimensions are powers of each other. Larger dependent
+    /// sets are left out, the Pi groups are made of them.
+    pub fn format(&self, names: &[&str]) -> Vec<String> {
+        let dependency = |i: &usize| names[i + 1];
+        let mut lines: Vec<String> = self
+            .dimensionless
+            .iter()
+            .map(|i| format!("{} is dimensionless", dependency(i)))
+            .collect();
+        for group in &self.duplicates {
+            let group: Vec<&str> = group.iter().map(dependency).collect();
+            lines.push(format!(
+                "{} have the same dimension",
+                join_or(&group, "and")
+            ));
+        }
+        for product in &self.dependent {
+            let variables: Vec<usize> = (0..names.len() - 1)
+                .filter(|&i| product.contains(i + 1))
+                .collect();
+            let duplicated = self
+                .duplicates
+                .iter()
+                .any(|g| variables.iter().all(|i| g.contains(i)));
+            if variables.len() == 2 && !duplicated {
+                lines.push(format!(
+                    "{} and {} are dimensionally dependent: {} is dimensionless",
+                    dependency(&variables[0]),
+                    dependency(&variables[1]),
+                    product.format(names)
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// The number of quantity kinds and of constants suggested by `diagnose`
+const CANDIDATES: usize = 3;
+
 impl fmt::Display for DimensionalAnalysis {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let (ab, _) = self.build_augmented_matrix();
@@ -105,29 +401,15 @@ impl DimensionalAnalysis {
             return DimensionalAnalysisSolution::UniqueSolution(Matrix::new(1, n));
         }
 
-        // println!("Augmented matrix [A|b]:");
-        // println!("{}", ab);
-
         // Convert to reduced row echelon form
-        let rank = ab.to_row_echelon_form();
-        // println!("Reduced row echelon form:");
-        // println!("{}", ab);
+        let rref = ab.to_row_echelon_form();
 
-        // Check for no solution
-        for i in 0..m {
-            let mut all_zeros = true;
-            for j in 0..n {
-                if ab[(i, j)] != Rational32::from_integer(0) {
-                    all_zeros = false;
-                    break;
-                }
-            }
-            if all_zeros && ab[(i, n)] != Rational32::from_integer(0) {
-                return DimensionalAnalysisSolution::NoSolution;
-            }
+        // No solution when b is a pivot column: a row 0 = b_i with b_i ≠ 0
+        if rref.pivot_row(n).is_some() {
+            return DimensionalAnalysisSolution::NoSolution;
         }
 
-        if rank < n {
+        if rref.rank < n {
             // Multiple solutions
             let mut reduced_a = Matrix::new(m, n);
             let mut reduced_b = Matrix::new(m, 1);
@@ -141,7 +423,7 @@ impl DimensionalAnalysis {
             }
 
             return DimensionalAnalysisSolution::MultipleSolutions {
-                rank,
+                rank: rref.rank,
                 n,
                 reduced_a,
                 reduced_b,
@@ -150,10 +432,273 @@ impl DimensionalAnalysis {
 
         // Unique solution - create a column matrix
         let mut solution = Matrix::new(n, 1);
-        for i in 0..n {
-            solution[(i, 0)] = ab[(i, n)];
+        for (i, &pivot) in rref.pivots.iter().enumerate() {
+            solution[(pivot, 0)] = ab[(i, n)];
         }
 
         DimensionalAnalysisSolution::UniqueSolution(solution)
     }
+
+    /// The exponents of the dependencies when the solution is unique
+    pub fn exponents(&self) -> Option<Vec<Rational32>> {
+        let n = self.dependencies.len();
+        match self.solve() {
+            DimensionalAnalysisSolution::UniqueSolution(solution)
+                if solution.dimensions() == (n, 1) =>
+            {
+                Some((0..n).map(|i| solution[(i, 0)]).collect())
+            }
+            DimensionalAnalysisSolution::UniqueSolution(_) => {
+                Some(vec![Rational32::from_integer(0); n])
+            }
+            _ => None,
+        }
+    }
+}
+
+impl DimensionalAnalysis {
+    // a solution of the equations of the base dimensions `rows`, with the
+    // free exponents zero; `None` if the equations contradict each other
+    fn particular_solution(&self, rows: &[usize]) -> Option<Vec<Rational32>> {
+        let n = self.dependencies.len();
+        let mut ab = Matrix::new(rows.len(), n + 1);
+        for (i, &d) in rows.iter().enumerate() {
+            for j in 0..n {
+                ab[(i, j)] = self.dependencies[j][d];
+            }
+            ab[(i, n)] = self.target[d];
+        }
+        let rref = ab.to_row_echelon_form();
+        if rref.pivot_row(n).is_some() {
+            return None;
+        }
+        let mut x = vec![Rational32::from_integer(0); n];
+        for (i, &pivot) in rref.pivots.iter().enumerate() {
+            x[pivot] = ab[(i, n)];
+        }
+        Some(x)
+    }
+
+    /// Why the analysis has no solution, `None` if it has one. The
+    /// inconsistent base dimensions are those of the target that no
+    /// dependency carries; if there are none, every equation is dropped
+    /// without which the others still contradict each other. The missing
+    /// dimension is what the target has beyond the product that matches the
+    /// equations kept in order while they are consistent.
+    pub fn diagnose(&self) -> Option<Diagnosis> {
+        let zero = Rational32::from_integer(0);
+        let rows: Vec<usize> = (0..7)
+            .filter(|&d| self.target[d] != zero || self.dependencies.iter().any(|v| v[d] != zero))
+            .collect();
+        if self.particular_solution(&rows).is_some() {
+            return None;
+        }
+
+        // the base dimensions of the target that no dependency carries, or
+        // else a minimal set of contradicting equations
+        let mut inconsistent: Vec<usize> = rows
+            .iter()
+            .copied()
+            .filter(|&d| self.dependencies.iter().all(|v| v[d] == zero))
+            .collect();
+        if inconsistent.is_empty() {
+            inconsistent = rows.clone();
+            for d in &rows {
+                let rest: Vec<usize> = inconsistent.iter().copied().filter(|r| r != d).collect();
+                if self.particular_solution(&rest).is_none() {
+                    inconsistent = rest;
+                }
+            }
+        }
+
+        let mut kept = Vec::new();
+        for &d in &rows {
+            kept.push(d);
+            if self.particular_solution(&kept).is_none() {
+                kept.pop();
+            }
+        }
+        let x = self.particular_solution(&kept)?;
+        let residual: Vec<Rational32> = (0..7)
+            .map(|d| {
+                self.target[d]
+                    - x.iter()
+                        .zip(&self.dependencies)
+                        .map(|(k, v)| k * v[d])
+                        .sum::<Rational32>()
+            })
+            .collect();
+        let mut exponents = integer_exponents(&residual);
+        if exponents.iter().find(|&&k| k != 0).is_some_and(|&k| k < 0) {
+            exponents.iter_mut().for_each(|k| *k = -*k);
+        }
+        let missing = Dimension::PowerLawRA(std::array::from_fn(|d| {
+            Rational32::from_integer(exponents[d])
+        }));
+
+        let candidates = self.candidates(&missing);
+        Some(Diagnosis {
+            inconsistent,
+            missing,
+            candidates,
+        })
+    }
+
+    // the quantity kinds of the missing dimension, then of its reciprocal,
+    // the most general first; then the constants with a symbol that make the
+    // analysis solvable, those of the missing dimension and the exact ones
+    // first
+    fn candidates(&self, missing: &Dimension) -> Vec<Candidate> {
+        let reciprocal = missing.clone().reciprocal();
+        let matches = |d: &Dimension| d == missing || d == &reciprocal;
+        let mut kinds: Vec<&QuantityKind> = quantity_kinds()
+            .iter()
+            .filter(|k| k.dimension.as_ref().is_some_and(matches))
+            .collect();
+        kinds.sort_by_key(|k| {
+            (
+                k.dimension.as_ref() != Some(missing),
+                k.generalizations.len(),
+                k.name.clone(),
+            )
+        });
+
+        let mut constants: Vec<(bool, bool, String, String)> = codata()
+            .iter()
+            .filter_map(|c| {
+                let symbol = c.symbol.clone()?;
+                let dimension = c.dimension.clone()?;
+                let mut extended = self.clone();
+                extended.dependencies.push(dimension.clone());
+                extended
+                    .particular_solution(&(0..7).collect::<Vec<_>>())
+                    .map(|_| (!matches(&dimension), !c.exact, symbol, c.name.clone()))
+            })
+            .collect();
+        constants.sort();
+
+        let kinds = kinds
+            .into_iter()
+            .take(CANDIDATES)
+            .map(|k| Candidate::Kind(k.name.clone()));
+        let constants = constants
+            .into_iter()
+            .take(CANDIDATES)
+            .map(|(_, _, symbol, name)| Candidate::Constant { symbol, name });
+        kinds.chain(constants).collect()
+    }
+
+    /// The dependencies that are dimensionless, share a dimension or are
+    /// dimensionally dependent, found from the pivots of the reduced row
+    /// echelon form of their exponent matrix; empty when the rank equals the
+    /// number of dependencies
+    pub fn redundancy(&self) -> Redundancy {
+        let n = self.dependencies.len();
+        if n == 0 {
+            return Redundancy::default();
+        }
+        let dimensionless = (0..n)
+            .filter(|&j| self.dependencies[j].is_dimensionless())
+            .collect();
+        let mut duplicates: Vec<Vec<usize>> = Vec::new();
+        for (j, dimension) in self.dependencies.iter().enumerate() {
+            if dimension.is_dimensionless() {
+                continue;
+            }
+            match duplicates
+                .iter_mut()
+                .find(|g| &self.dependencies[g[0]] == dimension)
+            {
+                Some(group) => group.push(j),
+                None => duplicates.push(vec![j]),
+            }
+        }
+        duplicates.retain(|g| g.len() > 1);
+        let dependent = Matrix::from_cols(
+            self.dependencies
+                .iter()
+                .map(|d| d.get_exponents().to_vec())
+                .collect(),
+        )
+        .null_space()
+        .into_iter()
+        .map(|x| {
+            let exponents: Vec<Rational32> = std::iter::once(Rational32::from_integer(0))
+                .chain(x)
+                .collect();
+            PiGroup {
+                exponents: integer_exponents(&exponents),
+            }
+        })
+        .collect();
+        Redundancy {
+            dimensionless,
+            duplicates,
+            dependent,
+        }
+    }
+
+    /// The dimensions of the target and the dependencies, in this order
+    pub fn variables(&self) -> Vec<Dimension> {
+        std::iter::once(&self.target)
+            .chain(&self.dependencies)
+            .cloned()
+            .collect()
+    }
+
+    /// A complete set of independent dimensionless groups (Buckingham Π
+    /// theorem), `n + 1 - rank` of them. The leading dependencies that are
+    /// dimensionally independent are the repeating variables; every other
+    /// variable appears in one group only, with a positive exponent. The
+    /// group of the target, if any, comes first.
+    pub fn pi_groups(&self) -> Vec<PiGroup> {
+        let n = self.dependencies.len();
+        // columns: the dependencies, then the target, so that pivots are
+        // taken among the dependencies first
+        let columns: Vec<Vec<Rational32>> = self
+            .dependencies
+            .iter()
+            .chain(std::iter::once(&self.target))
+            .map(|d| d.get_exponents().to_vec())
+            .collect();
+        let mut groups: Vec<PiGroup> = Matrix::from_cols(columns)
+            .null_space()
+            .into_iter()
+            .map(|x| {
+                let mut exponents = vec![x[n]];
+                exponents.extend_from_slice(&x[..n]);
+                PiGroup {
+                    exponents: integer_exponents(&exponents),
+                }
+            })
+            .collect();
+        groups.sort_by_key(|group| !group.contains(0));
+        groups
+    }
+}
+
+// the smallest integer multiple of rational exponents
+fn integer_exponents(exponents: &[Rational32]) -> Vec<i32> {
+    let denominator = exponents.iter().fold(1, |l, e| l.lcm(e.denom()));
+    let integers: Vec<i32> = exponents
+        .iter()
+        .map(|e| (e * denominator).to_integer())
+        .collect();
+    let divisor = integers.iter().fold(0, |g, k| g.gcd(k)).max(1);
+    integers.iter().map(|k| k / divisor).collect()
+}
+
+/// The number of independent dimensions among `dimensions`, the rank of
+/// their exponent matrix
+pub fn dimension_rank(dimensions: &[Dimension]) -> usize {
+    if dimensions.is_empty() {
+        return 0;
+    }
+    let mut matrix = Matrix::from_cols(
+        dimensions
+            .iter()
+            .map(|d| d.get_exponents().to_vec())
+            .collect(),
+    );
+    matrix.to_row_echelon_form().rank
 }
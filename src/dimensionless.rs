@@ -0,0 +1,429 @@
+#![doc = include_str!("../doc/dimensionless.md")]
+use crate::dimensional::*;
+use crate::dimensional_analysis::{NamedAnalysis, PiGroup, Variable};
+use num::{Rational32, Signed, Zero};
+use std::fmt;
+
+/// A variable in the definition of a dimensionless number
+#[derive(Clone, Debug, PartialEq)]
+pub struct Factor {
+    /// Usual symbols, ASCII first, e.g. `rho` and `ρ`
+    pub symbols: &'static [&'static str],
+    /// QUDT quantity kind, if QUDT has one
+    pub kind: Option<&'static str>,
+    pub dimension: Dimension,
+    pub exponent: Rational32,
+}
+
+/// A well-known dimensionless number with its definition, e.g. the
+/// Reynolds number `Re = ρ·V·L/μ`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DimensionlessNumber {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub definition: &'static [Factor],
+}
+
+const fn factor(
+    symbols: &'static [&'static str],
+    kind: Option<&'static str>,
+    dimension: Dimension,
+    numer: i32,
+    denom: i32,
+) -> Factor {
+    Factor {
+        symbols,
+        kind,
+        dimension,
+        exponent: Rational32::new_raw(numer, denom),
+    }
+}
+
+const RHO: [&str; 2] = ["rho", "ρ"];
+const VELOCITY_SYMBOLS: [&str; 3] = ["V", "v", "U"];
+const LENGTH_SYMBOLS: [&str; 4] = ["L", "D", "d", "l"];
+const MU: [&str; 2] = ["mu", "μ"];
+const NU: [&str; 2] = ["nu", "ν"];
+const CONDUCTIVITY: [&str; 2] = ["k", "lambda"];
+const DYNAMIC_VISCOSITY: Dimension = Dimension::PowerLaw(-1, 1, -1, 0, 0, 0, 0);
+const KINEMATIC_VISCOSITY: Dimension = Dimension::PowerLaw(2, 0, -1, 0, 0, 0, 0);
+const HEAT_TRANSFER_COEFFICIENT: Dimension = Dimension::PowerLaw(0, 1, -3, 0, -1, 0, 0);
+const THERMAL_CONDUCTIVITY: Dimension = Dimension::PowerLaw(1, 1, -3, 0, -1, 0, 0);
+
+/// Reynolds, Mach, Froude, Weber, Strouhal, Nusselt, Prandtl, Grashof,
+/// Biot, Péclet, Knudsen and Euler numbers. Some share a dimensional
+/// definition (Nusselt and Biot, Reynolds with `ν` and Péclet) and are told
+/// apart by the names and kinds of the variables only.
+pub const DIMENSIONLESS_NUMBERS: &[DimensionlessNumber] = &[
+    DimensionlessNumber {
+        symbol: "Re",
+        name: "Reynolds number",
+        definition: &[
+            factor(&RHO, Some("Density"), DENSITY, 1, 1),
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 1, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(&MU, Some("DynamicViscosity"), DYNAMIC_VISCOSITY, -1, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Re",
+        name: "Reynolds number",
+        definition: &[
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 1, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(&NU, Some("KinematicViscosity"), KINEMATIC_VISCOSITY, -1, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Ma",
+        name: "Mach number",
+        definition: &[
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 1, 1),
+            factor(&["c", "a"], Some("Speed"), VELOCITY, -1, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Fr",
+        name: "Froude number",
+        definition: &[
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 1, 1),
+            factor(&["g"], Some("Acceleration"), ACCELERATION, -1, 2),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, -1, 2),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "We",
+        name: "Weber number",
+        definition: &[
+            factor(&RHO, Some("Density"), DENSITY, 1, 1),
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 2, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(
+                &["sigma", "σ", "gamma"],
+                Some("ForcePerLength"),
+                Dimension::PowerLaw(0, 1, -2, 0, 0, 0, 0),
+                -1,
+                1,
+            ),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "St",
+        name: "Strouhal number",
+        definition: &[
+            factor(&["f", "omega", "ω"], Some("Frequency"), FREQUENCY, 1, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, -1, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Nu",
+        name: "Nusselt number",
+        definition: &[
+            factor(
+                &["h"],
+                Some("CoefficientOfHeatTransfer"),
+                HEAT_TRANSFER_COEFFICIENT,
+                1,
+                1,
+            ),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(
+                &["k", "k_f", "lambda"],
+                Some("ThermalConductivity"),
+                THERMAL_CONDUCTIVITY,
+                -1,
+                1,
+            ),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Pr",
+        name: "Prandtl number",
+        definition: &[
+            factor(
+                &["c_p", "cp", "c"],
+                Some("SpecificHeatCapacity"),
+                Dimension::PowerLaw(2, 0, -2, 0, -1, 0, 0),
+                1,
+                1,
+            ),
+            factor(&MU, Some("DynamicViscosity"), DYNAMIC_VISCOSITY, 1, 1),
+            factor(
+                &CONDUCTIVITY,
+                Some("ThermalConductivity"),
+                THERMAL_CONDUCTIVITY,
+                -1,
+                1,
+            ),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Gr",
+        name: "Grashof number",
+        definition: &[
+            factor(&["g"], Some("Acceleration"), ACCELERATION, 1, 1),
+            factor(
+                &["beta", "β"],
+                None,
+                Dimension::PowerLaw(0, 0, 0, 0, -1, 0, 0),
+                1,
+                1,
+            ),
+            factor(
+                &["dT", "ΔT", "T"],
+                Some("ThermodynamicTemperature"),
+                TEMPERATURE,
+                1,
+                1,
+            ),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 3, 1),
+            factor(&NU, Some("KinematicViscosity"), KINEMATIC_VISCOSITY, -2, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Bi",
+        name: "Biot number",
+        definition: &[
+            factor(
+                &["h"],
+                Some("CoefficientOfHeatTransfer"),
+                HEAT_TRANSFER_COEFFICIENT,
+                1,
+                1,
+            ),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(
+                &["k_s", "k_b", "ks"],
+                Some("ThermalConductivity"),
+                THERMAL_CONDUCTIVITY,
+                -1,
+                1,
+            ),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Pe",
+        name: "Péclet number",
+        definition: &[
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, 1, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, 1, 1),
+            factor(
+                &["alpha", "α"],
+                Some("ThermalDiffusivity"),
+                KINEMATIC_VISCOSITY,
+                -1,
+                1,
+            ),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Kn",
+        name: "Knudsen number",
+        definition: &[
+            factor(&["lambda", "λ", "mfp"], Some("Length"), LENGTH, 1, 1),
+            factor(&LENGTH_SYMBOLS, Some("Length"), LENGTH, -1, 1),
+        ],
+    },
+    DimensionlessNumber {
+        symbol: "Eu",
+        name: "Euler number",
+        definition: &[
+            factor(&["dp", "Δp", "p"], Some("Pressure"), PRESSURE, 1, 1),
+            factor(&RHO, Some("Density"), DENSITY, -1, 1),
+            factor(&VELOCITY_SYMBOLS, Some("Velocity"), VELOCITY, -2, 1),
+        ],
+    },
+];
+
+impl DimensionlessNumber {
+    /// The entries with a symbol, e.g. both definitions of `Re`
+    pub fn lookup(symbol: &str) -> Vec<&'static DimensionlessNumber> {
+        DIMENSIONLESS_NUMBERS
+            .iter()
+            .filter(|n| n.symbol == symbol)
+            .collect()
+    }
+
+    /// The definition written with the first symbols, e.g. `rho·V·L·mu^-1`
+    pub fn formula(&self) -> String {
+        let factors: Vec<String> = self
+            .definition
+            .iter()
+            .map(|f| match f.exponent == Rational32::from_integer(1) {
+                true => f.symbols[0].to_string(),
+                false => format!("{}^{}", f.symbols[0], f.exponent),
+            })
+            .collect();
+        factors.join("·")
+    }
+}
+
+/// A Pi group recognized as a power of a dimensionless number
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recognition {
+    pub number: &'static DimensionlessNumber,
+    /// `Π = number^power`
+    pub power: Rational32,
+    /// For each factor of the definition, the index of the variable of
+    /// the analysis (0 for the target) that plays its part
+    pub assignment: Vec<usize>,
+    /// How many variable names and quantity kinds agree with the
+    /// definition; a higher score is a more plausible reading
+    pub score: usize,
+}
+
+impl fmt::Display for Recognition {
+    /// `Re`, `Re⁻¹`, `Fr⁻²` or `Re^(1/2)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let power = self.power;
+        if power == Rational32::from_integer(1) {
+            return write!(f, "{}", self.number.symbol);
+        }
+        if !power.is_integer() {
+            return write!(f, "{}^({})", self.number.symbol, power);
+        }
+        let superscript: String = power
+            .to_integer()
+            .to_string()
+            .chars()
+            .map(|c| match c {
+                '-' => '⁻',
+                '1' => '¹',
+                '2' => '²',
+                '3' => '³',
+                d => char::from_u32(0x2070 + d.to_digit(10).expect("a digit"))
+                    .expect("a superscript digit"),
+            })
+            .collect();
+        write!(f, "{}{}", self.number.symbol, superscript)
+    }
+}
+
+/// The dimensionless numbers of which a Pi group of the analysis is a
+/// power, the most plausible first. A number matches when the variables of
+/// the group can be paired with the factors of its definition, with equal
+/// dimensions and exponents in a fixed ratio; among several pairings, the
+/// one with the most agreeing names and quantity kinds is kept.
+pub fn recognize(analysis: &NamedAnalysis, group: &PiGroup) -> Vec<Recognition> {
+    let variables: Vec<&Variable> = std::iter::once(&analysis.target)
+        .chain(&analysis.dependencies)
+        .collect();
+    let factors: Vec<(usize, Rational32)> = group
+        .exponents
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| **k != 0)
+        .map(|(i, k)| (i, Rational32::from_integer(*k)))
+        .collect();
+    let mut found: Vec<Recognition> = DIMENSIONLESS_NUMBERS
+        .iter()
+        .filter(|number| number.definition.len() == factors.len())
+        .filter_map(|number| {
+            let mut best: Option<Recognition> = None;
+            let mut assignment = Vec::new();
+            pair(
+                number,
+                &variables,
+                &factors,
+                &mut assignment,
+                None,
+                &mut best,
+            );
+            best
+        })
+        .collect();
+    // the best reading first, then the simplest power
+    found.sort_by_key(|r| {
+        (
+            std::cmp::Reverse(r.score),
+            r.power.abs() != Rational32::from_integer(1),
+        )
+    });
+    found
+}
+
+// assign the factors of the definition one by one to unused variables of
+// the group, keeping the highest-scoring complete assignment
+fn pair(
+    number: &'static DimensionlessNumber,
+    variables: &[&Variable],
+    factors: &[(usize, Rational32)],
+    assignment: &mut Vec<usize>,
+    power: Option<Rational32>,
+    best: &mut Option<Recognition>,
+) {
+    let position = assignment.len();
+    let Some(definition) = number.definition.get(position) else {
+        let power = power.expect("a definition has factors");
+        let score = assignment
+            .iter()
+            .zip(number.definition)
+            .map(|(&i, factor)| agreement(variables[i], factor))
+            .sum();
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            *best = Some(Recognition {
+                number,
+                power,
+                assignment: assignment.clone(),
+                score,
+            });
+        }
+        return;
+    };
+    for &(index, exponent) in factors {
+        if assignment.contains(&index) || variables[index].dimension != definition.dimension {
+            continue;
+        }
+        let ratio = exponent / definition.exponent;
+        if power.is_some_and(|p| p != ratio) || ratio.is_zero() {
+            continue;
+        }
+        assignment.push(index);
+        pair(number, variables, factors, assignment, Some(ratio), best);
+        assignment.pop();
+    }
+}
+
+fn agreement(variable: &Variable, factor: &Factor) -> usize {
+    let named = factor.symbols.iter().any(|s| *s == variable.name) as usize;
+    let kind = match (variable.dimension.kind(), factor.kind) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b) as usize,
+        _ => 0,
+    };
+    named + 2 * kind
+}
+
+/// The most plausible readings of a Pi group: the recognitions with the
+/// best score, one per symbol; several when the names and kinds of the
+/// variables do not tell the numbers apart (Nusselt and Biot)
+pub fn readings(analysis: &NamedAnalysis, group: &PiGroup) -> Vec<Recognition> {
+    let found = recognize(analysis, group);
+    let Some(best) = found.first().cloned() else {
+        return found;
+    };
+    let mut readings: Vec<Recognition> = Vec::new();
+    for recognition in found {
+        if recognition.score == best.score
+            && recognition.power.abs() == best.power.abs()
+            && !readings
+                .iter()
+                .any(|r| r.to_string() == recognition.to_string())
+        {
+            readings.push(recognition);
+        }
+    }
+    readings
+}
+
+/// The readings of a Pi group as text, e.g. `Re⁻¹` or `Nu or Bi`
+pub fn describe(analysis: &NamedAnalysis, group: &PiGroup) -> Option<String> {
+    let readings: Vec<String> = readings(analysis, group)
+        .iter()
+        .map(Recognition::to_string)
+        .collect();
+    match readings.is_empty() {
+        true => None,
+        false => Some(readings.join(" or ")),
+    }
+}
@@ -0,0 +1,484 @@
+#![doc = include_str!("../doc/expression.md")]
+use crate::dimensional::{DIMENSIONLESS, Dimension, DimensionTrait};
+use crate::prefix::{Prefix, prefixes};
+use crate::units::{UNIT_NS, Unit, UnitError, catalog};
+use std::fmt;
+use std::ops;
+
+/// Symbols whose catalog lookup is missing or ambiguous in QUDT 1.1,
+/// resolved to the unit the SI Brochure means by them
+const SYMBOL_ALIASES: [(&str, &str); 7] = [
+    ("h", "Hour"),
+    ("d", "Day"),
+    ("L", "Liter"),
+    ("l", "Liter"),
+    ("t", "MetricTon"),
+    ("°C", "DegreeCelsius"),
+    ("Ω", "Ohm"),
+];
+
+/// A product of powers of units times a numerical scale, e.g. `kg·m/s^2`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivedUnit {
+    /// Pure number in front of the units, `1` for most expressions
+    pub scale: f64,
+    /// Units with their exponents; each unit appears once
+    pub factors: Vec<(Unit, i32)>,
+}
+
+impl From<Unit> for DerivedUnit {
+    fn from(unit: Unit) -> Self {
+        DerivedUnit {
+            scale: 1.0,
+            factors: vec![(unit, 1)],
+        }
+    }
+}
+
+impl fmt::Display for DerivedUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl ops::Mul for DerivedUnit {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self.scale *= rhs.scale;
+        for (unit, exponent) in rhs.factors {
+            self.push(unit, exponent);
+        }
+        self
+    }
+}
+
+impl ops::Div for DerivedUnit {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.powi(-1)
+    }
+}
+
+impl DerivedUnit {
+    /// The dimensionless unit `1`
+    pub fn one() -> Self {
+        DerivedUnit {
+            scale: 1.0,
+            factors: Vec::new(),
+        }
+    }
+
+    /// Parse a unit expression such as `kg·m/s^2`, `N m` or `W/(m2.K)`
+    pub fn parse(text: &str) -> Result<DerivedUnit, UnitError> {
+        parse_unit_expression(text)
+    }
+
+    /// Multiply by `unit^exponent`, merging with an existing factor of the same unit
+    pub fn push(&mut self, unit: Unit, exponent: i32) {
+        match self.factors.iter().position(|(u, _)| u.iri == unit.iri) {
+            Some(i) => {
+                self.factors[i].1 += exponent;
+                if self.factors[i].1 == 0 {
+                    self.factors.remove(i);
+                }
+            }
+            None if exponent != 0 => self.factors.push((unit, exponent)),
+            None => {}
+        }
+    }
+
+    pub fn powi(mut self, exponent: i32) -> DerivedUnit {
+        self.scale = self.scale.powi(exponent);
+        for factor in self.factors.iter_mut() {
+            factor.1 *= exponent;
+        }
+        self.factors.retain(|(_, e)| *e != 0);
+        self
+    }
+
+    /// Factor to the coherent SI unit, `None` if any unit lacks a multiplier
+    pub fn conversion_multiplier(&self) -> Option<f64> {
+        self.factors
+            .iter()
+            .try_fold(self.scale, |acc, (unit, exponent)| {
+                unit.conversion_multiplier.map(|m| acc * m.powi(*exponent))
+            })
+    }
+
+    /// The dimension, `None` if any unit has no known dimension
+    pub fn dimension(&self) -> Option<Dimension> {
+        let dimension = self
+            .factors
+            .iter()
+            .try_fold(DIMENSIONLESS, |acc, (unit, exponent)| {
+                let d = unit.dimension.clone()?;
+                Some(if *exponent > 0 {
+                    acc * d.powi(*exponent)
+                } else {
+                    acc / d.powi(-exponent)
+                })
+            })?;
+        Some(dimension.standard_form())
+    }
+
+    /// The symbol in SI style, e.g. `kg·m/s^2` or `W/(m^2·K)`
+    pub fn symbol(&self) -> String {
+        let power = |unit: &Unit, exponent: i32| {
+            if exponent == 1 {
+                unit.display_symbol().to_string()
+            } else {
+                format!("{}^{}", unit.display_symbol(), exponent)
+            }
+        };
+        let mut numerator: Vec<String> = Vec::new();
+        if self.scale != 1.0 {
+            numerator.push(format!("{}", self.scale));
+        }
+        numerator.extend(
+            self.factors
+                .iter()
+                .filter(|(_, e)| *e > 0)
+                .map(|(u, e)| power(u, *e)),
+        );
+        let denominator: Vec<String> = self
+            .factors
+            .iter()
+            .filter(|(_, e)| *e < 0)
+            .map(|(u, e)| power(u, -e))
+            .collect();
+        match (numerator.is_empty(), denominator.len()) {
+            (true, 0) => "1".to_string(),
+            (_, 0) => numerator.join("·"),
+            (true, _) => self
+                .factors
+                .iter()
+                .map(|(u, e)| power(u, *e))
+                .collect::<Vec<_>>()
+                .join("·"),
+            (false, 1) => format!("{}/{}", numerator.join("·"), denominator[0]),
+            (false, _) => format!("{}/({})", numerator.join("·"), denominator.join("·")),
+        }
+    }
+
+    /// QUDT-style local name, e.g. `WattPerSquareMeterKelvin`
+    pub fn name(&self) -> String {
+        let power = |unit: &Unit, exponent: i32| match exponent {
+            1 => unit.name.clone(),
+            2 => format!("Square{}", unit.name),
+            3 => format!("Cubic{}", unit.name),
+            4 => format!("Quartic{}", unit.name),
+            _ => format!("{}{}", unit.name, exponent),
+        };
+        let mut name: String = self
+            .factors
+            .iter()
+            .filter(|(_, e)| *e > 0)
+            .map(|(u, e)| power(u, *e))
+            .collect();
+        let denominator: String = self
+            .factors
+            .iter()
+            .filter(|(_, e)| *e < 0)
+            .map(|(u, e)| power(u, -e))
+            .collect();
+        if !denominator.is_empty() {
+            name.push_str("Per");
+            name.push_str(&denominator);
+        }
+        name
+    }
+
+    /// A single `Unit` with the combined multiplier and dimension.
+    ///
+    /// A lone unit is returned unchanged, a catalog unit with the same
+    /// QUDT name (`KilometerPerHour` for `km/h`) is preferred, otherwise a
+    /// new unit is synthesized.
+    pub fn to_unit(&self) -> Unit {
+        if let [(unit, 1)] = self.factors.as_slice()
+            && self.scale == 1.0
+        {
+            return unit.clone();
+        }
+        let name = self.name();
+        if self.scale == 1.0
+            && let Some(existing) = catalog().iter().find(|u| {
+                u.name == name
+                    && u.conversion_multiplier == self.conversion_multiplier()
+                    && u.dimension == self.dimension()
+            })
+        {
+            return existing.clone();
+        }
+
+        let multiplier = self.conversion_multiplier();
+        let mut unit_types = vec!["DerivedUnit".to_string()];
+        if self.factors.iter().all(|(u, _)| u.is_si()) {
+            unit_types.push(
+                if multiplier == Some(1.0) {
+                    "SIDerivedUnit"
+                } else {
+                    "SIUnit"
+                }
+                .to_string(),
+            );
+        }
+        Unit {
+            iri: format!("{}{}", UNIT_NS, name),
+            label: name.clone(),
+            name,
+            symbol: Some(self.symbol()),
+            abbreviation: None,
+            conversion_multiplier: multiplier,
+            // an offset only makes sense for a lone unit; in products the
+            // units are intervals (K/s, °C/s)
+            conversion_offset: 0.0,
+            quantity_kinds: Vec::new(),
+            unit_types,
+            dimension: self.dimension(),
+            unece_common_code: None,
+        }
+    }
+
+    /// Convert a value from this unit to another unit of the same dimension
+    pub fn convert(&self, value: f64, to: &DerivedUnit) -> Result<f64, UnitError> {
+        self.to_unit().convert(value, &to.to_unit())
+    }
+}
+
+/// Parse a unit expression into a `DerivedUnit`.
+///
+/// Atoms are resolved against QUDT symbols and abbreviations, with SI and
+/// binary prefixes; `*`, `·`, `.` and spaces multiply, `/` divides (left
+/// associative), exponents are written `^2`, `^-1`, `2` or `²`.
+pub fn parse_unit_expression(text: &str) -> Result<DerivedUnit, UnitError> {
+    let text = text.trim();
+    // the whole text may be a catalog symbol such as `km/hr`
+    if !text.is_empty()
+        && let Ok(Some(unit)) = resolve_symbol(text)
+    {
+        return Ok(DerivedUnit::from(unit));
+    }
+    let mut parser = ExpressionParser {
+        text,
+        chars: text.char_indices().collect(),
+        pos: 0,
+    };
+    let unit = parser.expression()?;
+    parser.skip_spaces();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected character"));
+    }
+    Ok(unit)
+}
+
+/// Resolve a single unit symbol, possibly prefixed (`kN`, `µm`).
+///
+/// Returns `Ok(None)` when nothing matches and `UnitError::AmbiguousUnit`
+/// when the symbol names several different units.
+pub fn resolve_symbol(symbol: &str) -> Result<Option<Unit>, UnitError> {
+    if let Some(unit) = exact_symbol(symbol)? {
+        return Ok(Some(unit));
+    }
+    for prefix in prefixes() {
+        if let Some(rest) = symbol.strip_prefix(prefix.symbol.as_str())
+            && !rest.is_empty()
+            && let Some(unit) = exact_symbol(rest)?
+        {
+            return prefix.apply(&unit).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn exact_symbol(symbol: &str) -> Result<Option<Unit>, UnitError> {
+    let units = catalog();
+    if let Some((_, name)) = SYMBOL_ALIASES.iter().find(|(s, _)| *s == symbol) {
+        return Ok(units.get(name).cloned());
+    }
+    let mut candidates: Vec<&Unit> = units
+        .find_by_symbol(symbol)
+        .into_iter()
+        .filter(|u| Prefix::from_unit(u).is_none())
+        .collect();
+    // a symbol wins over another unit's abbreviation: QUDT 1.1 abbreviates
+    // the millimetre as `m^-3`
+    if candidates
+        .iter()
+        .any(|u| u.symbol.as_deref() == Some(symbol))
+    {
+        candidates.retain(|u| u.symbol.as_deref() == Some(symbol));
+    }
+    // the SI unit wins over a non-SI unit with the same symbol (C, F, cd, rad)
+    if candidates.iter().any(|u| u.is_si()) {
+        candidates.retain(|u| u.is_si());
+    }
+    match candidates.as_slice() {
+        [] => Ok(None),
+        [unit] => Ok(Some((*unit).clone())),
+        _ => Err(UnitError::AmbiguousUnit {
+            symbol: symbol.to_string(),
+            candidates: candidates.iter().map(|u| u.name.clone()).collect(),
+        }),
+    }
+}
+
+const OPERATORS: &str = "*·./^() ";
+const SUPERSCRIPTS: [(char, char); 12] = [
+    ('⁰', '0'),
+    ('¹', '1'),
+    ('²', '2'),
+    ('³', '3'),
+    ('⁴', '4'),
+    ('⁵', '5'),
+    ('⁶', '6'),
+    ('⁷', '7'),
+    ('⁸', '8'),
+    ('⁹', '9'),
+    ('⁻', '-'),
+    ('⁺', '+'),
+];
+
+struct ExpressionParser<'a> {
+    text: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+}
+
+impl ExpressionParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(_, c)| *c)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map_or(self.text.len(), |(i, _)| *i)
+    }
+
+    fn error(&self, message: &str) -> UnitError {
+        UnitError::InvalidExpression {
+            expression: self.text.to_string(),
+            position: self.offset(),
+            message: message.to_string(),
+        }
+    }
+
+    // expression := term ((`*` | `·` | `.` | `/` | juxtaposition) term)*
+    fn expression(&mut self) -> Result<DerivedUnit, UnitError> {
+        let mut result = self.term()?;
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('*' | '·' | '.') => {
+                    self.pos += 1;
+                    result = result * self.term()?;
+                }
+                Some('/') => {
+                    self.pos += 1;
+                    result = result / self.term()?;
+                }
+                Some(')') | None => return Ok(result),
+                Some(_) => result = result * self.term()?,
+            }
+        }
+    }
+
+    // term := factor exponent?
+    fn term(&mut self) -> Result<DerivedUnit, UnitError> {
+        self.skip_spaces();
+        let factor = match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.expression()?;
+                self.skip_spaces();
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected `)`"));
+                }
+                self.pos += 1;
+                inner
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits: String = self.chars[start..self.pos].iter().map(|(_, c)| c).collect();
+                let mut unit = DerivedUnit::one();
+                unit.scale = digits
+                    .parse::<f64>()
+                    .map_err(|_| self.error("invalid number"))?;
+                unit
+            }
+            Some(c) if !OPERATORS.contains(c) => self.atom()?,
+            _ => return Err(self.error("expected a unit")),
+        };
+        match self.exponent()? {
+            Some(exponent) => Ok(factor.powi(exponent)),
+            None => Ok(factor),
+        }
+    }
+
+    fn atom(&mut self) -> Result<DerivedUnit, UnitError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let signed_digit = (c == '-' || c == '+')
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|(_, d)| d.is_ascii_digit());
+            if OPERATORS.contains(c)
+                || c.is_ascii_digit()
+                || signed_digit
+                || superscript(c).is_some()
+            {
+                break;
+            }
+            self.pos += 1;
+        }
+        let symbol: String = self.chars[start..self.pos].iter().map(|(_, c)| c).collect();
+        match resolve_symbol(&symbol)? {
+            Some(unit) => Ok(DerivedUnit::from(unit)),
+            None => Err(UnitError::UnknownUnit(symbol)),
+        }
+    }
+
+    // exponent := `^` [+-]? digits | [+-]? digits | superscripts
+    fn exponent(&mut self) -> Result<Option<i32>, UnitError> {
+        let start = self.pos;
+        if self.peek() == Some('^') {
+            self.pos += 1;
+        }
+        let mut digits = String::new();
+        while let Some(c) = self.peek() {
+            let c = superscript(c).unwrap_or(c);
+            let sign = (c == '-' || c == '+') && digits.is_empty();
+            if !(c.is_ascii_digit() || sign) {
+                break;
+            }
+            digits.push(c);
+            self.pos += 1;
+        }
+        if digits.is_empty() {
+            if self.pos > start {
+                return Err(self.error("expected an exponent"));
+            }
+            return Ok(None);
+        }
+        digits
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| self.error("invalid exponent"))
+    }
+}
+
+fn superscript(c: char) -> Option<char> {
+    SUPERSCRIPTS.iter().find(|(s, _)| *s == c).map(|(_, d)| *d)
+}
@@ -0,0 +1,398 @@
+#![doc = include_str!("../doc/fit.md")]
+use crate::dimensional_analysis::{NamedAnalysis, PiGroup};
+use crate::expression::DerivedUnit;
+use crate::format::{FormatOptions, format_measurement};
+use crate::units::{UnitError, catalog};
+use std::fmt;
+
+/// The measured values of one variable, in `unit`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub unit: DerivedUnit,
+    pub values: Vec<f64>,
+}
+
+impl Column {
+    /// A column in a unit expression; an empty expression or `1` for pure
+    /// numbers
+    pub fn new(name: &str, unit: &str, values: Vec<f64>) -> Result<Column, UnitError> {
+        let unit = match unit.trim() {
+            "" | "1" => DerivedUnit::one(),
+            unit => DerivedUnit::parse(unit)?,
+        };
+        Ok(Column {
+            name: name.to_string(),
+            unit,
+            values,
+        })
+    }
+
+    /// The values in the coherent SI unit; °C and °F are converted as
+    /// temperatures, not as differences
+    pub fn si_values(&self) -> Result<Vec<f64>, UnitError> {
+        let unit = self.unit.to_unit();
+        self.values.iter().map(|v| unit.to_si(*v)).collect()
+    }
+}
+
+/// A table of measurements, one column per variable and one row per run
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataTable {
+    pub columns: Vec<Column>,
+}
+
+impl DataTable {
+    /// Check that the columns have the same length
+    pub fn new(columns: Vec<Column>) -> Result<DataTable, FitError> {
+        if let Some(column) = columns
+            .iter()
+            .find(|c| c.values.len() != columns[0].values.len())
+        {
+            return Err(FitError::RaggedColumn {
+                name: column.name.clone(),
+                rows: column.values.len(),
+                expected: columns[0].values.len(),
+            });
+        }
+        Ok(DataTable { columns })
+    }
+
+    /// Read comma-separated values whose header cells are `name [unit]`,
+    /// e.g. `D [mm], V [m/s], F [N]`; a cell without unit is a pure number.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<DataTable, FitError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        let (_, header) = lines.next().ok_or(FitError::InvalidData {
+            line: 0,
+            message: "missing header".to_string(),
+        })?;
+        let mut columns = header
+            .split(',')
+            .map(|cell| {
+                let cell = cell.trim();
+                let (name, unit) = match cell.split_once('[') {
+                    Some((name, unit)) => (name, unit.trim_end().trim_end_matches(']')),
+                    None => (cell, ""),
+                };
+                Column::new(name.trim(), unit, Vec::new())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for (number, line) in lines {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            if cells.len() != columns.len() {
+                return Err(FitError::InvalidData {
+                    line: number,
+                    message: format!("{} values for {} columns", cells.len(), columns.len()),
+                });
+            }
+            for (column, cell) in columns.iter_mut().zip(cells) {
+                let value = cell.parse().map_err(|_| FitError::InvalidData {
+                    line: number,
+                    message: format!("`{}` is not a number", cell),
+                })?;
+                column.values.push(value);
+            }
+        }
+        Ok(DataTable { columns })
+    }
+
+    /// The number of rows
+    pub fn rows(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.values.len())
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+/// Errors of fitting a power law to measurements
+#[derive(Clone, Debug, PartialEq)]
+pub enum FitError {
+    Unit(UnitError),
+    /// A variable of the analysis without a column
+    MissingVariable(String),
+    /// A column whose unit does not have the dimension of the variable
+    WrongDimension {
+        name: String,
+        unit: String,
+        dimension: String,
+    },
+    RaggedColumn {
+        name: String,
+        rows: usize,
+        expected: usize,
+    },
+    /// A line of a data table that cannot be read
+    InvalidData {
+        line: usize,
+        message: String,
+    },
+    /// No Pi group contains the target: the analysis has no solution
+    NoTargetGroup,
+    /// A Pi group whose value is not positive in a row, so that its
+    /// logarithm is undefined
+    NonPositive {
+        group: usize,
+        row: usize,
+    },
+    /// Not more rows than coefficients, the standard errors are undefined
+    TooFewRows {
+        rows: usize,
+        parameters: usize,
+    },
+    /// The logarithms of the groups are linearly dependent over the data
+    Singular,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::Unit(error) => write!(f, "{}", error),
+            FitError::MissingVariable(name) => write!(f, "no column for variable `{}`", name),
+            FitError::WrongDimension {
+                name,
+                unit,
+                dimension,
+            } => {
+                write!(
+                    f,
+                    "column `{}` in `{}` does not have the dimension {}",
+                    name, unit, dimension
+                )
+            }
+            FitError::RaggedColumn {
+                name,
+                rows,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "column `{}` has {} values, expected {}",
+                    name, rows, expected
+                )
+            }
+            FitError::InvalidData { line, message } => write!(f, "line {}: {}", line, message),
+            FitError::NoTargetGroup => write!(f, "no Pi group contains the target"),
+            FitError::NonPositive { group, row } => {
+                write!(f, "Π{} is not positive in row {}", group + 1, row + 1)
+            }
+            FitError::TooFewRows { rows, parameters } => {
+                write!(
+                    f,
+                    "{} rows are too few to fit {} coefficients",
+                    rows, parameters
+                )
+            }
+            FitError::Singular => write!(f, "the Pi groups are linearly dependent over the data"),
+        }
+    }
+}
+
+impl std::error::Error for FitError {}
+
+impl From<UnitError> for FitError {
+    fn from(error: UnitError) -> Self {
+        FitError::Unit(error)
+    }
+}
+
+/// A least-squares estimate with its standard error
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub value: f64,
+    pub standard_error: f64,
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bare = catalog().require("Unitless").expect("Unitless").clone();
+        bare.symbol = Some(String::new());
+        let text = format_measurement(
+            self.value,
+            self.standard_error,
+            &bare,
+            &FormatOptions::concise(),
+        );
+        write!(f, "{}", text)
+    }
+}
+
+/// The correlation `Π1 = C·Π2^a₂·…·Πk^aₖ` fitted to measurements
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerLawFit {
+    /// The Pi groups, the group of the target first
+    pub groups: Vec<PiGroup>,
+    /// The coefficient `C`; its standard error follows from that of `ln C`
+    pub coefficient: Estimate,
+    /// The exponents `a₂ … aₖ` of the other groups
+    pub exponents: Vec<Estimate>,
+    /// `ln Π1 − ln(C·Π2^a₂·…)` in each row
+    pub residuals: Vec<f64>,
+    /// The standard deviation of the residuals
+    pub residual_std: f64,
+    /// The coefficient of determination of `ln Π1`
+    pub r_squared: f64,
+    pub degrees_of_freedom: usize,
+}
+
+impl PowerLawFit {
+    /// `Π1` predicted from the values of `Π2 … Πk`
+    pub fn predict(&self, groups: &[f64]) -> f64 {
+        self.exponents
+            .iter()
+            .zip(groups)
+            .fold(self.coefficient.value, |product, (a, g)| {
+                product * g.powf(a.value)
+            })
+    }
+
+    /// The fitted correlation, e.g. `Π1 = 1.002(12)·Π2^-0.2498(31)`
+    pub fn relation(&self) -> String {
+        let factors: Vec<String> = self
+            .exponents
+            .iter()
+            .enumerate()
+            .map(|(i, a)| format!("·Π{}^{}", i + 2, a))
+            .collect();
+        format!("Π1 = {}{}", self.coefficient, factors.concat())
+    }
+}
+
+/// The fraction of its norm below which the part of a regressor independent
+/// of the ones before it counts as rounding: the logarithms of the groups are
+/// then collinear to working precision, with a condition number above 1e8
+const SINGULAR_TOLERANCE: f64 = 1e-8;
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Fit `Π1 = C·Π2^a₂·…·Πk^aₖ` to the measurements by least squares on the
+/// logarithms. The groups are those of `NamedAnalysis::pi_groups`, the
+/// columns are matched to the variables by name and converted to SI; a
+/// unique solution is the case of a single group, where only `C` is fitted.
+pub fn fit_power_law(analysis: &NamedAnalysis, data: &DataTable) -> Result<PowerLawFit, FitError> {
+    let variables = std::iter::once(&analysis.target).chain(&analysis.dependencies);
+    let mut values = Vec::new();
+    for variable in variables {
+        let column = data
+            .column(&variable.name)
+            .ok_or_else(|| FitError::MissingVariable(variable.name.clone()))?;
+        if column.unit.dimension().as_ref() != Some(&variable.dimension) {
+            return Err(FitError::WrongDimension {
+                name: variable.name.clone(),
+                unit: column.unit.symbol(),
+                dimension: variable.dimension.to_string(),
+            });
+        }
+        values.push(column.si_values()?);
+    }
+
+    let groups = analysis.pi_groups();
+    if !groups.first().is_some_and(|g| g.contains(0)) {
+        return Err(FitError::NoTargetGroup);
+    }
+    let rows = data.rows();
+    let parameters = groups.len();
+    if rows <= parameters {
+        return Err(FitError::TooFewRows { rows, parameters });
+    }
+    // ln Πₖ in each row
+    let mut logs = vec![vec![0.0; rows]; parameters];
+    for (k, group) in groups.iter().enumerate() {
+        for (row, log) in logs[k].iter_mut().enumerate() {
+            let product: f64 = group
+                .exponents
+                .iter()
+                .zip(&values)
+                .map(|(e, column)| column[row].powi(*e))
+                .product();
+            if !(product > 0.0 && product.is_finite()) {
+                return Err(FitError::NonPositive { group: k, row });
+            }
+            *log = product.ln();
+        }
+    }
+
+    // least squares of y = ln Π1 on X = [1, ln Π2, …] by Householder QR,
+    // which unlike the normal equations does not square the condition of X;
+    // columns[j] holds the j-th column of X and is reduced in place to R
+    let regressor = |j: usize, row: usize| if j == 0 { 1.0 } else { logs[j][row] };
+    let y = &logs[0];
+    let mut columns: Vec<Vec<f64>> = (0..parameters)
+        .map(|j| (0..rows).map(|r| regressor(j, r)).collect())
+        .collect();
+    let mut qty = y.clone();
+    let sizes: Vec<f64> = columns.iter().map(|c| dot(c, c).sqrt()).collect();
+    for j in 0..parameters {
+        // the norm of the part of column j independent of the columns before
+        let alpha = dot(&columns[j][j..], &columns[j][j..]).sqrt();
+        if alpha <= SINGULAR_TOLERANCE * sizes[j] {
+            return Err(FitError::Singular);
+        }
+        let alpha = if columns[j][j] > 0.0 { -alpha } else { alpha };
+        let mut v = columns[j][j..].to_vec();
+        v[0] -= alpha;
+        let vv = dot(&v, &v);
+        for column in columns[j..].iter_mut().chain(std::iter::once(&mut qty)) {
+            let s = 2.0 * dot(&v, &column[j..]) / vv;
+            for (c, v) in column[j..].iter_mut().zip(&v) {
+                *c -= s * v;
+            }
+        }
+    }
+    let r = |i: usize, j: usize| columns[j][i];
+    // R·β = Qᵀy and R⁻¹, whose rows give the diagonal of (XᵀX)⁻¹ = R⁻¹R⁻ᵀ
+    let mut beta = vec![0.0; parameters];
+    let mut inverse = vec![vec![0.0; parameters]; parameters];
+    for i in (0..parameters).rev() {
+        let sum: f64 = (i + 1..parameters).map(|k| r(i, k) * beta[k]).sum();
+        beta[i] = (qty[i] - sum) / r(i, i);
+        inverse[i][i] = 1.0 / r(i, i);
+        let row: Vec<f64> = (i + 1..parameters)
+            .map(|c| -(i + 1..=c).map(|k| r(i, k) * inverse[k][c]).sum::<f64>() / r(i, i))
+            .collect();
+        inverse[i][i + 1..].copy_from_slice(&row);
+    }
+
+    let residuals: Vec<f64> = (0..rows)
+        .map(|r| {
+            y[r] - (0..parameters)
+                .map(|j| beta[j] * regressor(j, r))
+                .sum::<f64>()
+        })
+        .collect();
+    let degrees_of_freedom = rows - parameters;
+    let rss: f64 = residuals.iter().map(|r| r * r).sum();
+    let variance = rss / degrees_of_freedom as f64;
+    let mean = y.iter().sum::<f64>() / rows as f64;
+    let tss: f64 = y.iter().map(|v| (v - mean).powi(2)).sum();
+    let errors: Vec<f64> = (0..parameters)
+        .map(|j| (variance * dot(&inverse[j], &inverse[j])).sqrt())
+        .collect();
+
+    let coefficient = beta[0].exp();
+    Ok(PowerLawFit {
+        groups,
+        coefficient: Estimate {
+            value: coefficient,
+            standard_error: coefficient * errors[0],
+        },
+        exponents: (1..parameters)
+            .map(|j| Estimate {
+                value: beta[j],
+                standard_error: errors[j],
+            })
+            .collect(),
+        residuals,
+        residual_std: variance.sqrt(),
+        r_squared: if tss > 0.0 { 1.0 - rss / tss } else { 1.0 },
+        degrees_of_freedom,
+    })
+}
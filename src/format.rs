@@ -0,0 +1,308 @@
+#![doc = include_str!("../doc/format.md")]
+use crate::prefix::decimal_prefix;
+use crate::units::Unit;
+
+/// How the uncertainty is written next to the value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UncertaintyStyle {
+    /// `12.345(12) mm`
+    Concise,
+    /// `(12.345 ± 0.024) mm`
+    PlusMinus,
+}
+
+/// How the power of ten of the value is written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// The value as is, in the given unit
+    Plain,
+    /// Exponent restricted to multiples of three, `12.345(12)×10^-3 m`
+    Engineering,
+}
+
+/// Options controlling how a measurement result is printed
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOptions {
+    pub style: UncertaintyStyle,
+    /// Significant digits kept in the uncertainty, 1 or 2
+    pub uncertainty_digits: u32,
+    /// Coverage factor `k`; the expanded uncertainty `k·u` is printed when set
+    pub coverage_factor: Option<f64>,
+    pub notation: Notation,
+    /// Replace the engineering exponent by an SI prefix on the unit symbol
+    pub auto_prefix: bool,
+    /// Group digits in threes with a thin space (SI Brochure 5.4.4)
+    pub digit_grouping: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            style: UncertaintyStyle::Concise,
+            uncertainty_digits: 2,
+            coverage_factor: None,
+            notation: Notation::Plain,
+            auto_prefix: false,
+            digit_grouping: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// `12.345(12) mm`
+    pub fn concise() -> Self {
+        FormatOptions::default()
+    }
+
+    /// `(12.345 ± 0.024) mm, k=2`
+    pub fn plus_minus(coverage_factor: f64) -> Self {
+        FormatOptions {
+            style: UncertaintyStyle::PlusMinus,
+            coverage_factor: Some(coverage_factor),
+            ..FormatOptions::default()
+        }
+    }
+
+    /// Concise notation with thin-space digit grouping, `1.674 927 471(21) kg`
+    pub fn si_brochure() -> Self {
+        FormatOptions {
+            digit_grouping: true,
+            ..FormatOptions::default()
+        }
+    }
+
+    pub fn with_uncertainty_digits(mut self, digits: u32) -> Self {
+        self.uncertainty_digits = digits.clamp(1, 2);
+        self
+    }
+
+    pub fn with_engineering_notation(mut self) -> Self {
+        self.notation = Notation::Engineering;
+        self
+    }
+
+    /// Engineering notation written as an SI prefix where the unit allows it
+    pub fn with_auto_prefix(mut self) -> Self {
+        self.notation = Notation::Engineering;
+        self.auto_prefix = true;
+        self
+    }
+}
+
+const THIN_SPACE: char = '\u{2009}';
+
+/// A value rounded to the last significant digit of its uncertainty.
+///
+/// The numbers are kept as integers scaled by `10^exponent` so that
+/// printing does not reintroduce binary rounding noise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundedValue {
+    pub value: i128,
+    pub uncertainty: i128,
+    pub exponent: i32,
+}
+
+impl RoundedValue {
+    pub fn value_f64(&self) -> f64 {
+        self.value as f64 * 10f64.powi(self.exponent)
+    }
+
+    pub fn uncertainty_f64(&self) -> f64 {
+        self.uncertainty as f64 * 10f64.powi(self.exponent)
+    }
+}
+
+/// Round the uncertainty to `digits` significant digits (GUM 7.2.6) and
+/// the value to the same decimal place.
+///
+/// Returns `None` when the uncertainty is zero or not finite, in which
+/// case no rounding rule applies, and when the rounded value would need
+/// more significant digits than an `f64` carries.
+pub fn round_to_uncertainty(value: f64, uncertainty: f64, digits: u32) -> Option<RoundedValue> {
+    let uncertainty = uncertainty.abs();
+    if uncertainty == 0.0 || !uncertainty.is_finite() || !value.is_finite() {
+        return None;
+    }
+    let digits = digits.clamp(1, 2) as i32;
+    let mut exponent = uncertainty.log10().floor() as i32 - (digits - 1);
+    let mut u = scale(uncertainty, exponent).round() as i128;
+    // 0.096 with one digit rounds up to 0.1, one decade higher
+    if u >= 10i128.pow(digits as u32) {
+        exponent += 1;
+        u = scale(uncertainty, exponent).round() as i128;
+    }
+    let scaled = scale(value, exponent).round();
+    // beyond 17 digits the mantissa is binary noise, and far beyond it the cast saturates
+    if scaled.abs() >= MAX_MANTISSA {
+        return None;
+    }
+    Some(RoundedValue {
+        value: scaled as i128,
+        uncertainty: u,
+        exponent,
+    })
+}
+
+/// Round to `digits` significant digits, e.g. to hide the binary noise of
+/// a computed value before printing it
+pub fn round_significant(value: f64, digits: usize) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    format!("{:.*e}", digits.max(1) - 1, value)
+        .parse()
+        .unwrap_or(value)
+}
+
+const MAX_MANTISSA: f64 = 1e17;
+
+fn scale(x: f64, exponent: i32) -> f64 {
+    // dividing by an exact power of ten is more accurate than multiplying by its inverse
+    if exponent < 0 {
+        x * 10f64.powi(-exponent)
+    } else {
+        x / 10f64.powi(exponent)
+    }
+}
+
+/// Write `mantissa × 10^exponent` as a decimal number
+pub fn format_scaled(mantissa: i128, exponent: i32, digit_grouping: bool) -> String {
+    let mut digits = mantissa.unsigned_abs().to_string();
+    let (integer, fraction) = if exponent >= 0 {
+        digits.push_str(&"0".repeat(exponent as usize));
+        (digits, String::new())
+    } else {
+        let decimals = (-exponent) as usize;
+        if digits.len() <= decimals {
+            digits = format!("{}{}", "0".repeat(decimals - digits.len() + 1), digits);
+        }
+        let split = digits.len() - decimals;
+        (digits[..split].to_string(), digits[split..].to_string())
+    };
+    let mut result = String::new();
+    if mantissa < 0 {
+        result.push('-');
+    }
+    if digit_grouping {
+        result.push_str(&group_integer(&integer));
+    } else {
+        result.push_str(&integer);
+    }
+    if !fraction.is_empty() {
+        result.push('.');
+        if digit_grouping {
+            result.push_str(&group_fraction(&fraction));
+        } else {
+            result.push_str(&fraction);
+        }
+    }
+    result
+}
+
+// ISO 80000-1 leaves four-digit groups unseparated
+fn group_integer(digits: &str) -> String {
+    if digits.len() <= 4 {
+        return digits.to_string();
+    }
+    let mut result = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(THIN_SPACE);
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn group_fraction(digits: &str) -> String {
+    if digits.len() <= 4 {
+        return digits.to_string();
+    }
+    let mut result = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && i.is_multiple_of(3) {
+            result.push(THIN_SPACE);
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn engineering_exponent(value: f64, uncertainty: f64) -> i32 {
+    let reference = if value != 0.0 { value } else { uncertainty };
+    if reference == 0.0 || !reference.is_finite() {
+        return 0;
+    }
+    let decade = reference.abs().log10().floor() as i32;
+    3 * decade.div_euclid(3)
+}
+
+/// Format a value with standard uncertainty `uncertainty` in `unit`
+pub fn format_measurement(
+    value: f64,
+    uncertainty: f64,
+    unit: &Unit,
+    options: &FormatOptions,
+) -> String {
+    let k = options.coverage_factor.unwrap_or(1.0);
+    let mut value = value;
+    let mut uncertainty = uncertainty * k;
+    let mut symbol = unit.display_symbol().to_string();
+    let mut power = String::new();
+
+    if options.notation == Notation::Engineering {
+        let mut exponent = engineering_exponent(value, uncertainty);
+        let prefixed = match decimal_prefix(exponent.clamp(-24, 24)) {
+            Some(prefix) if options.auto_prefix => prefix.apply(unit).ok(),
+            _ => None,
+        };
+        if let Some(prefixed) = prefixed {
+            exponent = exponent.clamp(-24, 24);
+            symbol = prefixed.display_symbol().to_string();
+        } else if exponent != 0 {
+            power = format!("×10^{}", exponent);
+        }
+        value = scale(value, exponent);
+        uncertainty = scale(uncertainty, exponent);
+    }
+
+    let rounded = round_to_uncertainty(value, uncertainty, options.uncertainty_digits);
+    let with_uncertainty = uncertainty != 0.0 && uncertainty.is_finite() && value.is_finite();
+    let number = match (rounded, options.style) {
+        // the uncertainty is below the resolution of the value: no rounding rule applies
+        (None, _) if with_uncertainty => {
+            format!("({:e} ± {:e}){}", value, uncertainty, power)
+        }
+        (None, _) => {
+            let v = format!("{}", value);
+            if power.is_empty() {
+                v
+            } else {
+                format!("{}{}", v, power)
+            }
+        }
+        (Some(r), UncertaintyStyle::Concise) => format!(
+            "{}({}){}",
+            format_scaled(r.value, r.exponent, options.digit_grouping),
+            // the uncertainty is written in units of the last digit shown
+            format_scaled(r.uncertainty, r.exponent.max(0), false),
+            power
+        ),
+        (Some(r), UncertaintyStyle::PlusMinus) => format!(
+            "({} ± {}){}",
+            format_scaled(r.value, r.exponent, options.digit_grouping),
+            format_scaled(r.uncertainty, r.exponent, options.digit_grouping),
+            power
+        ),
+    };
+
+    let mut result = if symbol.is_empty() {
+        number
+    } else {
+        format!("{} {}", number, symbol)
+    };
+    if let (Some(k), true) = (options.coverage_factor, with_uncertainty) {
+        result.push_str(&format!(", k={}", k));
+    }
+    result
+}
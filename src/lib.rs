@@ -5,3 +5,28 @@ pub mod matrix;
 pub mod dimensional;
 pub mod dimensional_analysis;
 pub mod units;
+pub mod rdf;
+mod qudt_records;
+pub mod turtle;
+pub mod prefix;
+pub mod expression;
+pub mod ucum;
+pub mod unece;
+pub mod quantity;
+pub mod quantity_kind;
+pub mod unit_system;
+pub mod natural_units;
+pub mod constants;
+pub mod currency;
+pub mod logarithmic;
+pub mod repl;
+pub mod fit;
+pub mod similitude;
+pub mod dimensionless;
+pub mod format;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "problem")]
+pub mod problem;
+#[cfg(feature = "cli")]
+pub mod cli;
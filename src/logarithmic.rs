@@ -0,0 +1,488 @@
+#![doc = include_str!("../doc/logarithmic.md")]
+use crate::expression::DerivedUnit;
+use crate::quantity::Quantity;
+use crate::units::{Unit, UnitError, catalog};
+use std::f64::consts::E;
+use std::fmt;
+
+/// What the ratio of a level compares
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelKind {
+    /// Powers and energies: 10 dB is a ratio of 10
+    Power,
+    /// Field quantities whose square is a power (voltage, sound pressure):
+    /// 20 dB is a ratio of 10
+    RootPower,
+    /// Plain ratios such as frequency intervals and concentrations
+    Ratio,
+}
+
+impl fmt::Display for LevelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelKind::Power => write!(f, "power"),
+            LevelKind::RootPower => write!(f, "root-power"),
+            LevelKind::Ratio => write!(f, "ratio"),
+        }
+    }
+}
+
+impl LevelKind {
+    // the exponent turning the ratio into a power ratio
+    fn exponent(&self) -> f64 {
+        match self {
+            LevelKind::RootPower => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// A logarithmic unit: `level = factor · log_base(power ratio)` for power
+/// and root-power levels, `factor · log_base(ratio)` for plain ratios
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogarithmicUnit {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub base: f64,
+    pub factor: f64,
+    /// Measures plain ratios rather than power and root-power levels
+    pub ratio: bool,
+}
+
+pub const BEL: LogarithmicUnit = LogarithmicUnit {
+    name: "Bel",
+    symbol: "B",
+    base: 10.0,
+    factor: 1.0,
+    ratio: false,
+};
+
+pub const DECIBEL: LogarithmicUnit = LogarithmicUnit {
+    name: "Decibel",
+    symbol: "dB",
+    base: 10.0,
+    factor: 10.0,
+    ratio: false,
+};
+
+/// 1 Np is a field ratio of e, 20/ln 10 ≈ 8.686 dB
+pub const NEPER: LogarithmicUnit = LogarithmicUnit {
+    name: "Neper",
+    symbol: "Np",
+    base: E,
+    factor: 0.5,
+    ratio: false,
+};
+
+/// A frequency ratio of 2
+pub const OCTAVE: LogarithmicUnit = LogarithmicUnit {
+    name: "Octave",
+    symbol: "oct",
+    base: 2.0,
+    factor: 1.0,
+    ratio: true,
+};
+
+/// A frequency ratio of 10
+pub const DECADE: LogarithmicUnit = LogarithmicUnit {
+    name: "Decade",
+    symbol: "dec",
+    base: 10.0,
+    factor: 1.0,
+    ratio: true,
+};
+
+/// `pH = -log₁₀(c / 1 mol/L)`, the activity approximated by the concentration
+pub const PH: LogarithmicUnit = LogarithmicUnit {
+    name: "pH",
+    symbol: "pH",
+    base: 10.0,
+    factor: -1.0,
+    ratio: true,
+};
+
+pub const LOGARITHMIC_UNITS: [LogarithmicUnit; 6] = [BEL, DECIBEL, NEPER, OCTAVE, DECADE, PH];
+
+impl LogarithmicUnit {
+    /// Look up a unit by name or symbol
+    pub fn lookup(name: &str) -> Option<LogarithmicUnit> {
+        LOGARITHMIC_UNITS
+            .into_iter()
+            .find(|u| u.symbol == name || u.name == name)
+    }
+
+    /// Whether levels of `kind` can be given in this unit
+    pub fn applies_to(&self, kind: LevelKind) -> bool {
+        self.ratio == (kind == LevelKind::Ratio)
+    }
+
+    // level per natural logarithm of the power ratio
+    fn scale(&self) -> f64 {
+        self.factor / self.base.ln()
+    }
+}
+
+/// A named reference level such as dBm (re 1 mW)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reference {
+    pub symbol: &'static str,
+    pub unit: LogarithmicUnit,
+    pub kind: LevelKind,
+    pub value: f64,
+    /// Unit expression of `value`, e.g. `mW`
+    pub quantity_unit: &'static str,
+}
+
+pub const REFERENCES: &[Reference] = &[
+    Reference {
+        symbol: "dBm",
+        unit: DECIBEL,
+        kind: LevelKind::Power,
+        value: 1.0,
+        quantity_unit: "mW",
+    },
+    Reference {
+        symbol: "dBW",
+        unit: DECIBEL,
+        kind: LevelKind::Power,
+        value: 1.0,
+        quantity_unit: "W",
+    },
+    Reference {
+        symbol: "dBV",
+        unit: DECIBEL,
+        kind: LevelKind::RootPower,
+        value: 1.0,
+        quantity_unit: "V",
+    },
+    Reference {
+        symbol: "dBµV",
+        unit: DECIBEL,
+        kind: LevelKind::RootPower,
+        value: 1.0,
+        quantity_unit: "µV",
+    },
+    // sound pressure level in air, ISO 1683
+    Reference {
+        symbol: "dB SPL",
+        unit: DECIBEL,
+        kind: LevelKind::RootPower,
+        value: 20.0,
+        quantity_unit: "µPa",
+    },
+    Reference {
+        symbol: "dB SWL",
+        unit: DECIBEL,
+        kind: LevelKind::Power,
+        value: 1.0,
+        quantity_unit: "pW",
+    },
+    Reference {
+        symbol: "pH",
+        unit: PH,
+        kind: LevelKind::Ratio,
+        value: 1.0,
+        quantity_unit: "mol/L",
+    },
+];
+
+/// QUDT units of levels and the references they stand for; `Decibel` and
+/// `DecibelCarrier` (relative to the carrier power) are relative levels
+const QUDT_UNITS: &[(&str, &str)] = &[
+    ("Decibel", "dB"),
+    ("DecibelReferredToOneMilliwatt", "dBm"),
+    ("DecibelCarrier", "dB"),
+];
+
+impl Reference {
+    /// Look up a reference by symbol, `dBuV` for `dBµV`
+    pub fn lookup(symbol: &str) -> Option<&'static Reference> {
+        let symbol = symbol.replace('u', "µ");
+        REFERENCES
+            .iter()
+            .find(|r| r.symbol.replace('u', "µ") == symbol)
+    }
+
+    /// The reference quantity, e.g. 1 mW
+    pub fn quantity(&self) -> Quantity {
+        let unit =
+            DerivedUnit::parse(self.quantity_unit).expect("reference units in the bundled catalog");
+        Quantity::new(self.value, &unit.to_unit())
+    }
+}
+
+/// A level `L = factor · log_base(x / x₀)`: a power, root-power or plain
+/// ratio in a logarithmic unit, relative to a reference quantity `x₀`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogarithmicQuantity {
+    pub level: f64,
+    /// Standard uncertainty of the level
+    pub uncertainty: f64,
+    pub unit: LogarithmicUnit,
+    pub kind: LevelKind,
+    /// `None` for relative levels: gains, attenuations, dBc
+    pub reference: Option<Quantity>,
+}
+
+fn kind_mismatch(from: impl fmt::Display, to: impl fmt::Display) -> UnitError {
+    UnitError::KindMismatch {
+        from: from.to_string(),
+        to: to.to_string(),
+    }
+}
+
+fn same_quantity(a: &Quantity, b: &Quantity) -> bool {
+    a.unit.name == b.unit.name && a.value == b.value
+}
+
+impl LogarithmicQuantity {
+    /// A relative level, e.g. a gain of 20 dB
+    pub fn new(level: f64, unit: LogarithmicUnit, kind: LevelKind) -> Result<Self, UnitError> {
+        if !unit.applies_to(kind) {
+            return Err(kind_mismatch(kind, unit.name));
+        }
+        Ok(LogarithmicQuantity {
+            level,
+            uncertainty: 0.0,
+            unit,
+            kind,
+            reference: None,
+        })
+    }
+
+    /// A level given by a reference symbol (`dBm`, `dBµV`, `dB SPL`, `pH`)
+    /// or, relative, by a unit symbol (`dB`, `Np`, `oct`)
+    pub fn parse_level(level: f64, symbol: &str) -> Result<Self, UnitError> {
+        if let Some(reference) = Reference::lookup(symbol) {
+            return Ok(LogarithmicQuantity {
+                reference: Some(reference.quantity()),
+                ..LogarithmicQuantity::new(level, reference.unit, reference.kind)?
+            });
+        }
+        let unit = LogarithmicUnit::lookup(symbol)
+            .ok_or_else(|| UnitError::UnknownUnit(symbol.to_string()))?;
+        let kind = if unit.ratio {
+            LevelKind::Ratio
+        } else {
+            LevelKind::Power
+        };
+        LogarithmicQuantity::new(level, unit, kind)
+    }
+
+    /// A quantity in a QUDT level unit: `Decibel`, `DecibelReferredToOneMilliwatt`
+    /// or `DecibelCarrier`
+    pub fn from_quantity(quantity: &Quantity) -> Result<Self, UnitError> {
+        let (_, symbol) = QUDT_UNITS
+            .iter()
+            .find(|(name, _)| *name == quantity.unit.name)
+            .ok_or_else(|| UnitError::IncompatibleDimensions {
+                from: quantity.unit.name.clone(),
+                to: DECIBEL.name.to_string(),
+            })?;
+        Ok(LogarithmicQuantity::parse_level(quantity.value, symbol)?
+            .with_uncertainty(quantity.uncertainty))
+    }
+
+    /// Set the standard uncertainty of the level
+    pub fn with_uncertainty(mut self, uncertainty: f64) -> Self {
+        self.uncertainty = uncertainty.abs();
+        self
+    }
+
+    /// The level of `quantity` relative to `reference`
+    pub fn from_linear(
+        quantity: &Quantity,
+        reference: &Quantity,
+        kind: LevelKind,
+        unit: LogarithmicUnit,
+    ) -> Result<Self, UnitError> {
+        let quantity = match quantity.unit.name == reference.unit.name {
+            true => quantity.clone(),
+            false => quantity.convert_to(&reference.unit)?,
+        };
+        let ratio = quantity.value / reference.value;
+        if !(ratio.is_finite() && ratio > 0.0) {
+            return Err(UnitError::InvalidExpression {
+                expression: quantity.to_string(),
+                position: 0,
+                message: "a level needs a positive ratio to the reference".to_string(),
+            });
+        }
+        let slope = unit.scale() * kind.exponent();
+        Ok(LogarithmicQuantity {
+            reference: Some(reference.clone()),
+            ..LogarithmicQuantity::new(slope * ratio.ln(), unit, kind)?
+        }
+        .with_uncertainty(slope * quantity.uncertainty / quantity.value))
+    }
+
+    // level per natural logarithm of the ratio
+    fn slope(&self) -> f64 {
+        self.unit.scale() * self.kind.exponent()
+    }
+
+    /// The ratio `x / x₀`
+    pub fn ratio(&self) -> f64 {
+        (self.level / self.slope()).exp()
+    }
+
+    /// The linear quantity `x`, in the unit of the reference
+    pub fn to_linear(&self) -> Result<Quantity, UnitError> {
+        let reference =
+            self.reference
+                .as_ref()
+                .ok_or_else(|| UnitError::IncompatibleDimensions {
+                    from: self.to_string(),
+                    to: "a linear quantity".to_string(),
+                })?;
+        let value = self.ratio() * reference.value;
+        Ok(Quantity::new(value, &reference.unit)
+            .with_uncertainty(value * self.uncertainty / self.slope().abs()))
+    }
+
+    /// The same level in another logarithmic unit, e.g. dB in Np
+    pub fn convert_to(&self, unit: LogarithmicUnit) -> Result<Self, UnitError> {
+        if !unit.applies_to(self.kind) {
+            return Err(kind_mismatch(self.kind, unit.name));
+        }
+        let factor = unit.scale() / self.unit.scale();
+        Ok(LogarithmicQuantity {
+            level: self.level * factor,
+            uncertainty: self.uncertainty * factor.abs(),
+            unit,
+            ..self.clone()
+        })
+    }
+
+    /// The level of `self` in the unit and relative to the reference of `to`
+    fn expressed_like(&self, to: &LogarithmicQuantity) -> Result<Self, UnitError> {
+        if self.kind != to.kind {
+            return Err(kind_mismatch(self.kind, to.kind));
+        }
+        let mut level = self.convert_to(to.unit)?;
+        match (&self.reference, &to.reference) {
+            (None, None) => {}
+            (Some(a), Some(b)) if same_quantity(a, b) => {}
+            (Some(a), Some(b)) => {
+                let shift = LogarithmicQuantity::from_linear(a, b, to.kind, to.unit)?;
+                level.level += shift.level;
+                level.reference = Some(b.clone());
+            }
+            _ => {
+                return Err(UnitError::IncompatibleDimensions {
+                    from: self.to_string(),
+                    to: to.to_string(),
+                });
+            }
+        }
+        Ok(level)
+    }
+
+    /// The level of the summed powers, for uncorrelated sources: 60 dB SPL
+    /// plus 60 dB SPL is 63 dB SPL. Root-power levels add their squares.
+    /// The result has the unit and reference of `self`.
+    pub fn checked_add(&self, other: &LogarithmicQuantity) -> Result<Self, UnitError> {
+        if self.kind == LevelKind::Ratio {
+            return Err(kind_mismatch(self.kind, LevelKind::Power));
+        }
+        let other = other.expressed_like(self)?;
+        let scale = self.unit.scale();
+        let powers: Vec<(f64, f64)> = [self, &other]
+            .iter()
+            .map(|l| {
+                let power = (l.level / scale).exp();
+                (power, power * l.uncertainty / scale.abs())
+            })
+            .collect();
+        let power: f64 = powers.iter().map(|(p, _)| p).sum();
+        let uncertainty = powers.iter().map(|(_, u)| u * u).sum::<f64>().sqrt();
+        Ok(LogarithmicQuantity {
+            level: scale * power.ln(),
+            uncertainty: scale.abs() * uncertainty / power,
+            ..self.clone()
+        })
+    }
+
+    /// The power sum of several levels, see `checked_add`
+    pub fn sum(levels: &[LogarithmicQuantity]) -> Result<Self, UnitError> {
+        let (first, rest) = levels
+            .split_first()
+            .ok_or_else(|| UnitError::InvalidExpression {
+                expression: String::new(),
+                position: 0,
+                message: "no levels to sum".to_string(),
+            })?;
+        rest.iter()
+            .try_fold(first.clone(), |sum, level| sum.checked_add(level))
+    }
+
+    /// Apply a relative level: an amplifier with 20 dB gain turns -30 dBm
+    /// into -10 dBm. Power and root-power levels in the same logarithmic
+    /// unit take the same gain.
+    pub fn amplify(&self, gain: &LogarithmicQuantity) -> Result<Self, UnitError> {
+        if gain.reference.is_some() {
+            return Err(UnitError::IncompatibleDimensions {
+                from: gain.to_string(),
+                to: "a relative level".to_string(),
+            });
+        }
+        if (gain.kind == LevelKind::Ratio) != (self.kind == LevelKind::Ratio) {
+            return Err(kind_mismatch(gain.kind, self.kind));
+        }
+        let gain = gain.convert_to(self.unit)?;
+        Ok(LogarithmicQuantity {
+            level: self.level + gain.level,
+            uncertainty: self.uncertainty.hypot(gain.uncertainty),
+            ..self.clone()
+        })
+    }
+
+    /// The relative level `self - other`, e.g. a signal-to-noise ratio
+    pub fn difference(&self, other: &LogarithmicQuantity) -> Result<Self, UnitError> {
+        let other = other.expressed_like(self)?;
+        Ok(LogarithmicQuantity {
+            level: self.level - other.level,
+            uncertainty: self.uncertainty.hypot(other.uncertainty),
+            reference: None,
+            ..self.clone()
+        })
+    }
+
+    /// The named reference of the level, if any
+    pub fn named_reference(&self) -> Option<&'static Reference> {
+        let reference = self.reference.as_ref()?;
+        REFERENCES.iter().find(|r| {
+            r.unit == self.unit && r.kind == self.kind && same_quantity(&r.quantity(), reference)
+        })
+    }
+
+    /// The QUDT unit of the level: `Decibel` for relative levels in dB,
+    /// `DecibelReferredToOneMilliwatt` for dBm
+    pub fn qudt_unit(&self) -> Option<&'static Unit> {
+        let symbol = match (self.named_reference(), &self.reference) {
+            (Some(reference), _) => reference.symbol,
+            (None, None) => self.unit.symbol,
+            (None, Some(_)) => return None,
+        };
+        let (name, _) = QUDT_UNITS.iter().find(|(_, s)| *s == symbol)?;
+        catalog().get(name)
+    }
+
+    /// `-30 dBm`, `3 dB`, `94 dB SPL`, `1.2 Np re 1 mW`
+    pub fn symbol(&self) -> String {
+        match (self.named_reference(), &self.reference) {
+            (Some(reference), _) => reference.symbol.to_string(),
+            (None, Some(reference)) => format!("{} re {}", self.unit.symbol, reference),
+            (None, None) => self.unit.symbol.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LogarithmicQuantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unit == PH {
+            true => write!(f, "pH {}", self.level),
+            false => write!(f, "{} {}", self.level, self.symbol()),
+        }
+    }
+}
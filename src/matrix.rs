@@ -52,6 +52,15 @@ where
         Matrix { data, rows, cols }
     }
 
+    /// Create the `n`×`n` identity matrix
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Matrix::new(n, n);
+        for i in 0..n {
+            matrix.data[i][i] = T::one();
+        }
+        matrix
+    }
+
     /// Create a matrix from a 1D vector column
     pub fn from_col(col: Vec<T>) -> Self {
         if col.is_empty() {
@@ -216,18 +225,29 @@ where
         (max_row, max_val)
     }
 
-    /// Reduce the matrix to reduced row echelon form using Gaussian-Jordan elimination
-    /// Returns the rank of the matrix
-    pub fn to_row_echelon_form(&mut self) -> usize {
+    /// Reduce the matrix to reduced row echelon form using Gaussian-Jordan
+    /// elimination, recording the pivots and the row operations
+    pub fn to_row_echelon_form(&mut self) -> RrefResult<T> {
+        let mut result = RrefResult {
+            rank: 0,
+            pivots: Vec::new(),
+            free: Vec::new(),
+            operations: Vec::new(),
+            transform: Matrix::identity(self.rows),
+        };
         if self.rows == 0 || self.cols == 0 {
-            return 0;
+            result.free = (0..self.cols).collect();
+            return result;
         }
 
-        let mut rank = 0;
-        let mut pivot_col = 0;
-
         // Forward elimination (convert to row echelon form)
-        for i in 0..self.rows {
+        for pivot_col in 0..self.cols {
+            let i = result.rank;
+            if i == self.rows {
+                result.free.push(pivot_col);
+                continue;
+            }
+
             // Find the pivot row (row with largest absolute value in current column)
             let mut max_row = i;
             let mut max_val = self.data[pivot_col][i].abs();
@@ -240,63 +260,172 @@ where
                 }
             }
 
-            // If the pivot is zero, try next column
+            // If the pivot is zero, try next column with the same row
             if max_val == T::zero() {
-                pivot_col += 1;
-                if pivot_col >= self.cols {
-                    break;
-                }
+                result.free.push(pivot_col);
                 continue;
             }
 
             // Swap rows if necessary
             if max_row != i {
-                self.swap_rows(i, max_row);
+                self.apply(RowOperation::Swap(i, max_row), &mut result);
             }
 
             // Make the pivot 1
             let pivot = self.data[pivot_col][i].clone();
             if pivot != T::one() {
-                self.mul_row(i, T::one() / pivot.clone());
+                let factor = T::one() / pivot;
+                self.apply(RowOperation::Scale { row: i, factor }, &mut result);
             }
 
             // Eliminate entries below the pivot
             for k in i + 1..self.rows {
                 let factor = self.data[pivot_col][k].clone();
                 if factor != T::zero() {
-                    self.add_mul_row(k, i, -factor);
+                    let factor = -factor;
+                    self.apply(
+                        RowOperation::AddMultiple {
+                            row: k,
+                            source: i,
+                            factor,
+                        },
+                        &mut result,
+                    );
                 }
             }
 
-            rank += 1;
-            pivot_col += 1;
-            if pivot_col >= self.cols {
-                break;
-            }
+            result.pivots.push(pivot_col);
+            result.rank += 1;
         }
 
         // Backward elimination (convert to reduced row echelon form)
-        let mut pivot_col = 0;
-        for i in 0..rank {
-            // Find the pivot column for this row
-            while pivot_col < self.cols && self.data[pivot_col][i] == T::zero() {
-                pivot_col += 1;
-            }
-            if pivot_col >= self.cols {
-                break;
-            }
-
+        for (i, pivot_col) in result.pivots.clone().into_iter().enumerate() {
             // Eliminate entries above the pivot
             for k in 0..i {
                 let factor = self.data[pivot_col][k].clone();
                 if factor != T::zero() {
-                    self.add_mul_row(k, i, -factor);
+                    let factor = -factor;
+                    self.apply(
+                        RowOperation::AddMultiple {
+                            row: k,
+                            source: i,
+                            factor,
+                        },
+                        &mut result,
+                    );
                 }
             }
-            pivot_col += 1;
         }
 
-        rank
+        result
+    }
+
+    // apply a row operation to the matrix and to the transformation, and
+    // record it
+    fn apply(&mut self, operation: RowOperation<T>, result: &mut RrefResult<T>) {
+        operation.apply(self);
+        operation.apply(&mut result.transform);
+        result.operations.push(operation);
+    }
+
+    /// A basis of the null space, the vectors `x` with `A·x = 0`: one vector
+    /// per free column of the reduced row echelon form, with 1 at that column
+    pub fn null_space(&self) -> Vec<Vec<T>> {
+        let mut reduced = self.clone();
+        let rref = reduced.to_row_echelon_form();
+        rref.free
+            .iter()
+            .map(|&free| {
+                let mut x = vec![T::zero(); self.cols];
+                x[free] = T::one();
+                for (i, &pivot) in rref.pivots.iter().enumerate() {
+                    x[pivot] = -reduced.data[free][i].clone();
+                }
+                x
+            })
+            .collect()
+    }
+}
+
+/// An elementary row operation of Gauss-Jordan elimination; rows are
+/// counted from 0
+#[derive(Clone, Debug, PartialEq)]
+pub enum RowOperation<T> {
+    /// Swap two rows
+    Swap(usize, usize),
+    /// Multiply a row by a scalar
+    Scale { row: usize, factor: T },
+    /// Add a multiple of the row `source` to the row `row`
+    AddMultiple {
+        row: usize,
+        source: usize,
+        factor: T,
+    },
+}
+
+impl<T> RowOperation<T>
+where
+    T: Clone + Zero + One + Signed + NumAssign + Debug + PartialOrd,
+{
+    /// Apply the operation to a matrix, e.g. to replay an elimination
+    pub fn apply(&self, matrix: &mut Matrix<T>) {
+        match self {
+            RowOperation::Swap(i1, i2) => matrix.swap_rows(*i1, *i2),
+            RowOperation::Scale { row, factor } => matrix.mul_row(*row, factor.clone()),
+            RowOperation::AddMultiple {
+                row,
+                source,
+                factor,
+            } => matrix.add_mul_row(*row, *source, factor.clone()),
+        }
+    }
+}
+
+impl<T: Display> Display for RowOperation<T> {
+    /// Textbook notation with rows counted from 1, e.g. `R2 ← R2 + -3·R1`
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            RowOperation::Swap(i1, i2) => write!(f, "R{} ↔ R{}", i1 + 1, i2 + 1),
+            RowOperation::Scale { row, factor } => {
+                write!(f, "R{} ← {}·R{}", row + 1, factor, row + 1)
+            }
+            RowOperation::AddMultiple {
+                row,
+                source,
+                factor,
+            } => {
+                write!(
+                    f,
+                    "R{} ← R{} + {}·R{}",
+                    row + 1,
+                    row + 1,
+                    factor,
+                    source + 1
+                )
+            }
+        }
+    }
+}
+
+/// The outcome of `Matrix::to_row_echelon_form`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RrefResult<T> {
+    pub rank: usize,
+    /// The pivot column of each nonzero row, in row order
+    pub pivots: Vec<usize>,
+    /// The columns without pivot, the free variables of `A·x = 0`
+    pub free: Vec<usize>,
+    /// The row operations, in the order they were applied
+    pub operations: Vec<RowOperation<T>>,
+    /// The invertible matrix `E` with `E·A` the reduced row echelon form of
+    /// `A`: the operations applied to the identity
+    pub transform: Matrix<T>,
+}
+
+impl<T> RrefResult<T> {
+    /// The row whose pivot is in column `j`, `None` for a free column
+    pub fn pivot_row(&self, j: usize) -> Option<usize> {
+        self.pivots.iter().position(|&p| p == j)
     }
 }
 
@@ -0,0 +1,348 @@
+#![doc = include_str!("../doc/natural_units.md")]
+use crate::constants::codata;
+use crate::dimensional::{DIMENSIONLESS, Dimension, DimensionTrait};
+use crate::dimensional_analysis::{DimensionalAnalysis, dimension_rank};
+use crate::expression::DerivedUnit;
+use crate::quantity::Quantity;
+use crate::units::{Unit, UnitError, catalog};
+use num::rational::Rational32;
+use std::fmt;
+
+/// A physical constant that a natural system of units sets to one
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefiningConstant {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    /// Value in coherent SI units
+    pub value: f64,
+    pub dimension: Dimension,
+}
+
+impl DefiningConstant {
+    /// A constant of the bundled CODATA listing (`constants::codata`),
+    /// given by NIST name or symbol
+    pub fn codata(name: &str) -> Result<Self, UnitError> {
+        let constant = codata().require(name)?;
+        Ok(DefiningConstant {
+            name: &constant.name,
+            symbol: constant.symbol.as_deref().unwrap_or(&constant.name),
+            value: constant.value,
+            dimension: constant
+                .dimension
+                .clone()
+                .ok_or_else(|| UnitError::UnknownUnit(constant.unit_symbol.clone()))?,
+        })
+    }
+}
+
+fn bundled(name: &str) -> DefiningConstant {
+    DefiningConstant::codata(name).expect("constant in the bundled CODATA listing")
+}
+
+/// c, exact since 1983
+pub fn speed_of_light() -> DefiningConstant {
+    bundled("c")
+}
+
+/// ħ = h/2π, exact since 2019
+pub fn reduced_planck_constant() -> DefiningConstant {
+    bundled("ħ")
+}
+
+/// G
+pub fn gravitational_constant() -> DefiningConstant {
+    bundled("G")
+}
+
+/// k_B, exact since 2019
+pub fn boltzmann_constant() -> DefiningConstant {
+    bundled("k_B")
+}
+
+/// e, exact since 2019
+pub fn elementary_charge() -> DefiningConstant {
+    bundled("e")
+}
+
+/// m_e
+pub fn electron_mass() -> DefiningConstant {
+    bundled("m_e")
+}
+
+/// k_e = 1/4πε₀
+pub fn coulomb_constant() -> DefiningConstant {
+    let permittivity = bundled("ε_0");
+    DefiningConstant {
+        name: "Coulomb constant",
+        symbol: "k_e",
+        value: 1.0 / (4.0 * std::f64::consts::PI * permittivity.value),
+        dimension: (DIMENSIONLESS / permittivity.dimension).standard_form(),
+    }
+}
+
+/// A system of units in which the chosen constants equal one, completed by
+/// base units for the dimensions the constants leave over
+#[derive(Clone, Debug, PartialEq)]
+pub struct NaturalUnitSystem {
+    pub name: String,
+    pub constants: Vec<DefiningConstant>,
+    /// Units of the remaining dimensions, e.g. `GigaElectronVolt`
+    pub base_units: Vec<Unit>,
+}
+
+/// A dimension written as powers of the constants and the base units of a
+/// natural system: a mass is GeV·c⁻²
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reduction {
+    /// Powers of the constants, in the order of the system's constants
+    pub constants: Vec<(&'static str, Rational32)>,
+    /// Powers of the base units, by unit name
+    pub base_units: Vec<(String, Rational32)>,
+}
+
+impl Reduction {
+    /// The power of a constant given by its symbol, zero if absent
+    pub fn power(&self, symbol: &str) -> Rational32 {
+        self.constants
+            .iter()
+            .find(|(s, _)| *s == symbol)
+            .map_or(Rational32::from_integer(0), |(_, p)| *p)
+    }
+}
+
+impl fmt::Display for Reduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let factors: Vec<String> = self
+            .base_units
+            .iter()
+            .map(|(name, power)| (name.as_str(), *power))
+            .chain(
+                self.constants
+                    .iter()
+                    .map(|(symbol, power)| (*symbol, *power)),
+            )
+            .filter(|(_, power)| *power != Rational32::from_integer(0))
+            .map(
+                |(factor, power)| match power == Rational32::from_integer(1) {
+                    true => factor.to_string(),
+                    false => format!("{}^{}", factor, power),
+                },
+            )
+            .collect();
+        match factors.is_empty() {
+            true => write!(f, "1"),
+            false => write!(f, "{}", factors.join("·")),
+        }
+    }
+}
+
+fn to_f64(exponent: Rational32) -> f64 {
+    *exponent.numer() as f64 / *exponent.denom() as f64
+}
+
+impl NaturalUnitSystem {
+    /// Set `constants` to one. The constants must be dimensionally
+    /// independent, and so must the base units together with them.
+    pub fn new(
+        name: &str,
+        constants: Vec<DefiningConstant>,
+        base_units: Vec<Unit>,
+    ) -> Result<Self, UnitError> {
+        let error = |reason: &str| UnitError::NotInSystem {
+            quantity: constants
+                .iter()
+                .map(|c| c.symbol)
+                .collect::<Vec<_>>()
+                .join(", "),
+            system: name.to_string(),
+            reason: reason.to_string(),
+        };
+        let mut dimensions: Vec<Dimension> =
+            constants.iter().map(|c| c.dimension.clone()).collect();
+        if dimension_rank(&dimensions) < constants.len() {
+            return Err(error("the constants are not dimensionally independent"));
+        }
+        for unit in &base_units {
+            match (&unit.dimension, unit.conversion_multiplier) {
+                (Some(dimension), Some(_)) => dimensions.push(dimension.clone()),
+                _ => return Err(UnitError::MissingConversion(unit.name.clone())),
+            }
+        }
+        if dimension_rank(&dimensions) < dimensions.len() {
+            return Err(error("the base units depend on the constants"));
+        }
+        Ok(NaturalUnitSystem {
+            name: name.to_string(),
+            constants,
+            base_units,
+        })
+    }
+
+    /// High-energy physics: c = ħ = k_B = 1, everything in powers of GeV
+    pub fn particle_physics() -> Self {
+        let gev = catalog()
+            .get("GigaElectronVolt")
+            .expect("GigaElectronVolt in the bundled catalog");
+        NaturalUnitSystem::new(
+            "particle physics",
+            vec![
+                speed_of_light(),
+                reduced_planck_constant(),
+                boltzmann_constant(),
+            ],
+            vec![gev.clone()],
+        )
+        .expect("independent constants")
+    }
+
+    /// Planck units (Gaussian): c = ħ = G = k_B = k_e = 1
+    pub fn planck() -> Self {
+        NaturalUnitSystem::new(
+            "Planck",
+            vec![
+                speed_of_light(),
+                reduced_planck_constant(),
+                gravitational_constant(),
+                boltzmann_constant(),
+                coulomb_constant(),
+            ],
+            Vec::new(),
+        )
+        .expect("independent constants")
+    }
+
+    /// Hartree atomic units: ħ = e = m_e = k_e = 1
+    pub fn atomic() -> Self {
+        NaturalUnitSystem::new(
+            "atomic",
+            vec![
+                reduced_planck_constant(),
+                elementary_charge(),
+                electron_mass(),
+                coulomb_constant(),
+            ],
+            Vec::new(),
+        )
+        .expect("independent constants")
+    }
+
+    /// How many of the seven base dimensions remain independent once the
+    /// constants are set to one
+    pub fn effective_dimensions(&self) -> usize {
+        let dimensions: Vec<Dimension> =
+            self.constants.iter().map(|c| c.dimension.clone()).collect();
+        7 - dimension_rank(&dimensions)
+    }
+
+    fn error(&self, dimension: &Dimension, reason: &str) -> UnitError {
+        UnitError::NotInSystem {
+            quantity: dimension.to_string(),
+            system: self.name.clone(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Solve `dimension = Π constantᵃ · Π base unitᵇ` with `DimensionalAnalysis`
+    pub fn reduce(&self, dimension: &Dimension) -> Result<Reduction, UnitError> {
+        let analysis = DimensionalAnalysis {
+            target: dimension.clone(),
+            dependencies: self
+                .constants
+                .iter()
+                .map(|c| c.dimension.clone())
+                .chain(self.base_units.iter().filter_map(|u| u.dimension.clone()))
+                .collect(),
+        };
+        let exponents = analysis
+            .exponents()
+            .ok_or_else(|| self.error(dimension, "not spanned by the constants and base units"))?;
+        let (constants, base_units) = exponents.split_at(self.constants.len());
+        Ok(Reduction {
+            constants: self
+                .constants
+                .iter()
+                .map(|c| c.symbol)
+                .zip(constants.iter().copied())
+                .collect(),
+            base_units: self
+                .base_units
+                .iter()
+                .map(|u| u.name.clone())
+                .zip(base_units.iter().copied())
+                .collect(),
+        })
+    }
+
+    /// The unit of a dimension in the system: the product of base units,
+    /// `Unitless` when the constants alone make up the dimension
+    pub fn natural_unit(&self, dimension: &Dimension) -> Result<Unit, UnitError> {
+        let reduction = self.reduce(dimension)?;
+        let mut product = DerivedUnit::one();
+        for (unit, (_, exponent)) in self.base_units.iter().zip(&reduction.base_units) {
+            if !exponent.is_integer() {
+                return Err(self.error(dimension, "fractional power of a base unit"));
+            }
+            product.push(unit.clone(), exponent.to_integer());
+        }
+        match product.factors.is_empty() {
+            true => catalog().require("Unitless").cloned(),
+            false => Ok(product.to_unit()),
+        }
+    }
+
+    // value of Π constantᵃ in SI units
+    fn constants_factor(&self, reduction: &Reduction) -> f64 {
+        self.constants
+            .iter()
+            .zip(&reduction.constants)
+            .map(|(c, (_, exponent))| c.value.powf(to_f64(*exponent)))
+            .product()
+    }
+
+    /// Express an SI quantity in the system, e.g. a mass in GeV (c = 1)
+    pub fn to_natural(&self, quantity: &Quantity) -> Result<Quantity, UnitError> {
+        let dimension = quantity
+            .unit
+            .dimension
+            .clone()
+            .ok_or_else(|| UnitError::MissingConversion(quantity.unit.name.clone()))?;
+        let reduction = self.reduce(&dimension)?;
+        let unit = self.natural_unit(&dimension)?;
+        let scale = self.constants_factor(&reduction) * unit.to_si(1.0)?;
+        let factor = quantity.unit.to_si(1.0)? - quantity.unit.to_si(0.0)?;
+        Ok(
+            Quantity::new(quantity.unit.to_si(quantity.value)? / scale, &unit)
+                .with_uncertainty(quantity.uncertainty * factor / scale),
+        )
+    }
+
+    /// Restore the powers of the constants: express a quantity of the
+    /// system in an SI `unit`, e.g. 1/GeV as a length in femtometres
+    pub fn from_natural(&self, quantity: &Quantity, unit: &Unit) -> Result<Quantity, UnitError> {
+        let incompatible = || UnitError::IncompatibleDimensions {
+            from: quantity.unit.name.clone(),
+            to: unit.name.clone(),
+        };
+        let (Some(natural), Some(target)) = (&quantity.unit.dimension, &unit.dimension) else {
+            return Err(incompatible());
+        };
+        let analysis = DimensionalAnalysis {
+            target: target.clone() / natural.clone(),
+            dependencies: self.constants.iter().map(|c| c.dimension.clone()).collect(),
+        };
+        let exponents = analysis.exponents().ok_or_else(incompatible)?;
+        let reduction = Reduction {
+            constants: self
+                .constants
+                .iter()
+                .map(|c| c.symbol)
+                .zip(exponents)
+                .collect(),
+            base_units: Vec::new(),
+        };
+        let scale = self.constants_factor(&reduction) * quantity.unit.to_si(1.0)?;
+        let factor = unit.to_si(1.0)? - unit.to_si(0.0)?;
+        Ok(Quantity::new(unit.from_si(quantity.value * scale)?, unit)
+            .with_uncertainty(quantity.uncertainty * scale / factor))
+    }
+}
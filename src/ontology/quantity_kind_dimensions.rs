@@ -0,0 +1,249 @@
+// Exponents (L, M, T, I, Θ, N, J) of the QUDT 1.1 quantity kinds, by local
+// name. Shared with `build.rs`, which needs them to generate the unit constants.
+pub(crate) const QUANTITY_KIND_DIMENSIONS: &[(&str, [i32; 7])] = &[
+    ("AbsoluteHumidity", [-3, 1, 0, 0, 0, 0, 0]),
+    ("AbsorbedDose", [2, 0, -2, 0, 0, 0, 0]),
+    ("AbsorbedDoseRate", [2, 0, -3, 0, 0, 0, 0]),
+    ("Acceleration", [1, 0, -2, 0, 0, 0, 0]),
+    ("Action", [2, 1, -1, 0, 0, 0, 0]),
+    ("Activity", [0, 0, -1, 0, 0, 0, 0]),
+    ("AmountOfSubstance", [0, 0, 0, 0, 0, 1, 0]),
+    ("AmountOfSubstancePerUnitMass", [0, -1, 0, 0, 0, 1, 0]),
+    ("AmountOfSubstancePerUnitVolume", [-3, 0, 0, 0, 0, 1, 0]),
+    ("Angle", [0, 0, 0, 0, 0, 0, 0]),
+    ("AngularAcceleration", [0, 0, -2, 0, 0, 0, 0]),
+    ("AngularFrequency", [0, 0, -1, 0, 0, 0, 0]),
+    ("AngularMomentum", [2, 1, -1, 0, 0, 0, 0]),
+    ("AngularVelocity", [0, 0, -1, 0, 0, 0, 0]),
+    ("Area", [2, 0, 0, 0, 0, 0, 0]),
+    ("AreaAngle", [2, 0, 0, 0, 0, 0, 0]),
+    ("AreaPerTime", [2, 0, -1, 0, 0, 0, 0]),
+    ("AreaTemperature", [2, 0, 0, 0, 1, 0, 0]),
+    ("AreaThermalExpansion", [2, 0, 0, 0, -1, 0, 0]),
+    ("AreaTime", [2, 0, 1, 0, 0, 0, 0]),
+    ("AreaTimeTemperature", [2, 0, 1, 0, 1, 0, 0]),
+    ("AtmosphericPressure", [-1, 1, -2, 0, 0, 0, 0]),
+    ("AtomicCharge", [0, 0, 1, 1, 0, 0, 0]),
+    ("AuxillaryMagneticField", [-1, 0, 0, 1, 0, 0, 0]),
+    ("Capacitance", [-2, -1, 4, 2, 0, 0, 0]),
+    ("CatalyticActivity", [0, 0, -1, 0, 0, 1, 0]),
+    ("Circulation", [2, 0, -1, 0, 0, 0, 0]),
+    ("CoefficientOfHeatTransfer", [0, 1, -3, 0, -1, 0, 0]),
+    ("CompressibilityFactor", [0, 0, 0, 0, 0, 0, 0]),
+    ("Concentration", [-3, 0, 0, 0, 0, 1, 0]),
+    (
+        "CubicElectricDipoleMomentPerSquareEnergy",
+        [-1, -2, 7, 3, 0, 0, 0],
+    ),
+    ("Curvature", [-1, 0, 0, 0, 0, 0, 0]),
+    ("DataRate", [0, 0, -1, 0, 0, 0, 0]),
+    ("Density", [-3, 1, 0, 0, 0, 0, 0]),
+    ("Dimensionless", [0, 0, 0, 0, 0, 0, 0]),
+    ("DimensionlessRatio", [0, 0, 0, 0, 0, 0, 0]),
+    ("DoseEquivalent", [2, 0, -2, 0, 0, 0, 0]),
+    ("DryVolume", [3, 0, 0, 0, 0, 0, 0]),
+    ("DynamicPressure", [-1, 1, -2, 0, 0, 0, 0]),
+    ("DynamicViscosity", [-1, 1, -1, 0, 0, 0, 0]),
+    ("ElectricCharge", [0, 0, 1, 1, 0, 0, 0]),
+    ("ElectricChargeLineDensity", [-1, 0, 1, 1, 0, 0, 0]),
+    ("ElectricChargePerAmountOfSubstance", [0, 0, 1, 1, 0, -1, 0]),
+    ("ElectricChargePerArea", [-2, 0, 1, 1, 0, 0, 0]),
+    ("ElectricChargePerMass", [0, -1, 1, 1, 0, 0, 0]),
+    ("ElectricChargeVolumeDensity", [-3, 0, 1, 1, 0, 0, 0]),
+    // QUDT 1.1 attaches siemens and mho to this kind, i.e. it is used as conductance
+    ("ElectricConductivity", [-2, -1, 3, 2, 0, 0, 0]),
+    ("ElectricCurrent", [0, 0, 0, 1, 0, 0, 0]),
+    ("ElectricCurrentDensity", [-2, 0, 0, 1, 0, 0, 0]),
+    ("ElectricCurrentPerAngle", [0, 0, 0, 1, 0, 0, 0]),
+    ("ElectricCurrentPerUnitEnergy", [-2, -1, 2, 1, 0, 0, 0]),
+    ("ElectricCurrentPerUnitLength", [-1, 0, 0, 1, 0, 0, 0]),
+    ("ElectricDipoleMoment", [1, 0, 1, 1, 0, 0, 0]),
+    ("ElectricDisplacementField", [-2, 0, 1, 1, 0, 0, 0]),
+    ("ElectricField", [1, 1, -3, -1, 0, 0, 0]),
+    ("ElectricFlux", [3, 1, -3, -1, 0, 0, 0]),
+    ("ElectricPotential", [2, 1, -3, -1, 0, 0, 0]),
+    ("ElectricPower", [2, 1, -3, 0, 0, 0, 0]),
+    ("ElectricQuadrupoleMoment", [2, 0, 1, 1, 0, 0, 0]),
+    ("ElectromotiveForce", [2, 1, -3, -1, 0, 0, 0]),
+    ("EnergyAndWork", [2, 1, -2, 0, 0, 0, 0]),
+    (
+        "EnergyAndWorkPerMassAmountOfSubstance",
+        [2, 0, -2, 0, 0, -1, 0],
+    ),
+    ("EnergyDensity", [-1, 1, -2, 0, 0, 0, 0]),
+    ("EnergyInternal", [2, 1, -2, 0, 0, 0, 0]),
+    ("EnergyKinetic", [2, 1, -2, 0, 0, 0, 0]),
+    ("EnergyPerArea", [0, 1, -2, 0, 0, 0, 0]),
+    ("EnergyPerAreaElectricCharge", [0, 1, -3, -1, 0, 0, 0]),
+    ("EnergyPerElectricCharge", [2, 1, -3, -1, 0, 0, 0]),
+    ("EnergyPerSquareMagneticFluxDensity", [2, -1, 2, 2, 0, 0, 0]),
+    ("EnergyPerTemperature", [2, 1, -2, 0, -1, 0, 0]),
+    ("Enthalpy", [2, 1, -2, 0, 0, 0, 0]),
+    ("Exposure", [0, -1, 1, 1, 0, 0, 0]),
+    ("FirstMomentOfArea", [3, 0, 0, 0, 0, 0, 0]),
+    ("Force", [1, 1, -2, 0, 0, 0, 0]),
+    ("ForceMagnitude", [1, 1, -2, 0, 0, 0, 0]),
+    ("ForcePerArea", [-1, 1, -2, 0, 0, 0, 0]),
+    ("ForcePerAreaTime", [-1, 1, -3, 0, 0, 0, 0]),
+    ("ForcePerElectricCharge", [1, 1, -3, -1, 0, 0, 0]),
+    ("ForcePerLength", [0, 1, -2, 0, 0, 0, 0]),
+    ("Frequency", [0, 0, -1, 0, 0, 0, 0]),
+    ("Friction", [1, 1, -2, 0, 0, 0, 0]),
+    ("Gain", [0, 0, 0, 0, 0, 0, 0]),
+    ("GravitationalAttraction", [3, -1, -2, 0, 0, 0, 0]),
+    ("GyromagneticRatio", [0, -1, 1, 1, 0, 0, 0]),
+    ("HeartRate", [0, 0, -1, 0, 0, 0, 0]),
+    ("Heat", [2, 1, -2, 0, 0, 0, 0]),
+    ("HeatCapacity", [2, 1, -2, 0, -1, 0, 0]),
+    ("HeatCapacityRatio", [0, 0, 0, 0, 0, 0, 0]),
+    ("HeatFlowRate", [2, 1, -3, 0, 0, 0, 0]),
+    ("HeatFlowRatePerUnitArea", [0, 1, -3, 0, 0, 0, 0]),
+    ("Illuminance", [-2, 0, 0, 0, 0, 0, 1]),
+    ("Inductance", [2, 1, -2, -2, 0, 0, 0]),
+    ("InformationEntropy", [0, 0, 0, 0, 0, 0, 0]),
+    ("InverseAmountOfSubstance", [0, 0, 0, 0, 0, -1, 0]),
+    ("InverseEnergy", [-2, -1, 2, 0, 0, 0, 0]),
+    ("InverseLength", [-1, 0, 0, 0, 0, 0, 0]),
+    ("InverseLengthTemperature", [-1, 0, 0, 0, -1, 0, 0]),
+    ("InverseMagneticFlux", [-2, -1, 2, 1, 0, 0, 0]),
+    ("InversePermittivity", [3, 1, -4, -2, 0, 0, 0]),
+    ("InverseSquareEnergy", [-4, -2, 4, 0, 0, 0, 0]),
+    ("InverseTimeTemperature", [0, 0, -1, 0, -1, 0, 0]),
+    ("InverseVolume", [-3, 0, 0, 0, 0, 0, 0]),
+    ("Irradiance", [0, 1, -3, 0, 0, 0, 0]),
+    ("KinematicViscosity", [2, 0, -1, 0, 0, 0, 0]),
+    ("KineticEnergy", [2, 1, -2, 0, 0, 0, 0]),
+    ("Length", [1, 0, 0, 0, 0, 0, 0]),
+    ("LengthByForce", [2, 1, -2, 0, 0, 0, 0]),
+    ("LengthEnergy", [3, 1, -2, 0, 0, 0, 0]),
+    ("LengthMass", [1, 1, 0, 0, 0, 0, 0]),
+    ("LengthMolarEnergy", [3, 1, -2, 0, 0, -1, 0]),
+    ("LengthPerUnitElectricCurrent", [1, 0, 0, -1, 0, 0, 0]),
+    ("LengthPerUnitMagneticFlux", [-1, -1, 2, 1, 0, 0, 0]),
+    ("LengthTemperature", [1, 0, 0, 0, 1, 0, 0]),
+    ("LengthTemperatureTime", [1, 0, 1, 0, 1, 0, 0]),
+    ("LinearAcceleration", [1, 0, -2, 0, 0, 0, 0]),
+    ("LinearEnergyTransfer", [1, 1, -2, 0, 0, 0, 0]),
+    ("LinearMomentum", [1, 1, -1, 0, 0, 0, 0]),
+    ("LinearThermalExpansion", [1, 0, 0, 0, -1, 0, 0]),
+    ("LinearVelocity", [1, 0, -1, 0, 0, 0, 0]),
+    ("LiquidVolume", [3, 0, 0, 0, 0, 0, 0]),
+    ("Luminance", [-2, 0, 0, 0, 0, 0, 1]),
+    ("LuminousEfficacy", [-2, -1, 3, 0, 0, 0, 1]),
+    ("LuminousEmmitance", [-2, 0, 0, 0, 0, 0, 1]),
+    ("LuminousEnergy", [0, 0, 1, 0, 0, 0, 1]),
+    ("LuminousFlux", [0, 0, 0, 0, 0, 0, 1]),
+    ("LuminousFluxPerArea", [-2, 0, 0, 0, 0, 0, 1]),
+    ("LuminousIntensity", [0, 0, 0, 0, 0, 0, 1]),
+    ("MachNumber", [0, 0, 0, 0, 0, 0, 0]),
+    ("MagneticDipoleMoment", [2, 0, 0, 1, 0, 0, 0]),
+    ("MagneticField", [0, 1, -2, -1, 0, 0, 0]),
+    ("MagneticFlux", [2, 1, -2, -1, 0, 0, 0]),
+    ("MagneticFluxPerUnitLength", [1, 1, -2, -1, 0, 0, 0]),
+    ("MagnetizationField", [-1, 0, 0, 1, 0, 0, 0]),
+    ("MagnetomotiveForce", [0, 0, 0, 1, 0, 0, 0]),
+    ("Mass", [0, 1, 0, 0, 0, 0, 0]),
+    ("MassAmountOfSubstance", [0, 1, 0, 0, 0, 1, 0]),
+    ("MassAmountOfSubstanceTemperature", [0, 1, 0, 0, 1, 1, 0]),
+    ("MassPerArea", [-2, 1, 0, 0, 0, 0, 0]),
+    ("MassPerAreaTime", [-2, 1, -1, 0, 0, 0, 0]),
+    ("MassPerElectricCharge", [0, 1, -1, -1, 0, 0, 0]),
+    ("MassPerLength", [-1, 1, 0, 0, 0, 0, 0]),
+    ("MassPerTime", [0, 1, -1, 0, 0, 0, 0]),
+    ("MassPerVolume", [-3, 1, 0, 0, 0, 0, 0]),
+    ("MassTemperature", [0, 1, 0, 0, 1, 0, 0]),
+    ("MicrobialFormation", [0, 0, 0, 0, 0, 0, 0]),
+    ("MolarAngularMomentum", [2, 1, -1, 0, 0, -1, 0]),
+    ("MolarConcentration", [-3, 0, 0, 0, 0, 1, 0]),
+    ("MolarEnergy", [2, 1, -2, 0, 0, -1, 0]),
+    ("MolarHeatCapacity", [2, 1, -2, 0, -1, -1, 0]),
+    ("MolarMass", [0, 1, 0, 0, 0, -1, 0]),
+    ("MolarVolume", [3, 0, 0, 0, 0, -1, 0]),
+    ("MoleFraction", [0, 0, 0, 0, 0, 0, 0]),
+    ("MolecularMass", [0, 1, 0, 0, 0, 0, 0]),
+    ("MolecularViscosity", [-1, 1, -1, 0, 0, 0, 0]),
+    ("MomentOfInertia", [2, 1, 0, 0, 0, 0, 0]),
+    ("Momentum", [1, 1, -1, 0, 0, 0, 0]),
+    ("NumberDensity", [-3, 0, 0, 0, 0, 0, 0]),
+    ("Permeability", [1, 1, -2, -2, 0, 0, 0]),
+    ("Permittivity", [-3, -1, 4, 2, 0, 0, 0]),
+    ("PlaneAngle", [0, 0, 0, 0, 0, 0, 0]),
+    ("PolarMomentOfInertia", [2, 1, 0, 0, 0, 0, 0]),
+    ("Polarizability", [0, -1, 4, 2, 0, 0, 0]),
+    ("PolarizationField", [-2, 0, 1, 1, 0, 0, 0]),
+    ("PotentialEnergy", [2, 1, -2, 0, 0, 0, 0]),
+    ("Power", [2, 1, -3, 0, 0, 0, 0]),
+    ("PowerArea", [4, 1, -3, 0, 0, 0, 0]),
+    ("PowerAreaPerSolidAngle", [4, 1, -3, 0, 0, 0, 0]),
+    ("PowerPerArea", [0, 1, -3, 0, 0, 0, 0]),
+    ("PowerPerAreaAngle", [0, 1, -3, 0, 0, 0, 0]),
+    ("PowerPerAreaQuarticTemperature", [0, 1, -3, 0, -4, 0, 0]),
+    ("PowerPerElectricCharge", [2, 1, -4, -1, 0, 0, 0]),
+    ("Pressure", [-1, 1, -2, 0, 0, 0, 0]),
+    (
+        "QuarticElectricDipoleMomentPerCubicEnergy",
+        [-2, -3, 10, 4, 0, 0, 0],
+    ),
+    // decibel levels are ratios
+    ("RF-Power", [0, 0, 0, 0, 0, 0, 0]),
+    ("Radiance", [0, 1, -3, 0, 0, 0, 0]),
+    ("RadiantEmmitance", [0, 1, -3, 0, 0, 0, 0]),
+    ("RadiantEnergy", [2, 1, -2, 0, 0, 0, 0]),
+    ("RadiantFlux", [2, 1, -3, 0, 0, 0, 0]),
+    ("RadiantIntensity", [2, 1, -3, 0, 0, 0, 0]),
+    ("Radiosity", [0, 1, -3, 0, 0, 0, 0]),
+    ("Resistance", [2, 1, -3, -2, 0, 0, 0]),
+    ("RespiratoryRate", [0, 0, -1, 0, 0, 0, 0]),
+    ("ReynoldsNumber", [0, 0, 0, 0, 0, 0, 0]),
+    ("SecondMomentOfArea", [4, 0, 0, 0, 0, 0, 0]),
+    ("SignalDetectionThreshold", [0, 0, 0, 0, 0, 0, 0]),
+    ("SignalStrength", [0, 0, 0, 0, 0, 0, 0]),
+    ("SolidAngle", [0, 0, 0, 0, 0, 0, 0]),
+    ("SpecificEnergy", [2, 0, -2, 0, 0, 0, 0]),
+    ("SpecificHeatCapacity", [2, 0, -2, 0, -1, 0, 0]),
+    ("SpecificHeatPressure", [3, -1, 0, 0, -1, 0, 0]),
+    ("SpecificHeatVolume", [-1, 0, -2, 0, -1, 0, 0]),
+    ("SpecificImpulseByMass", [1, 0, -1, 0, 0, 0, 0]),
+    ("SpecificImpulseByWeight", [0, 0, 1, 0, 0, 0, 0]),
+    ("SpecificVolume", [3, -1, 0, 0, 0, 0, 0]),
+    ("Speed", [1, 0, -1, 0, 0, 0, 0]),
+    ("SquareEnergy", [4, 2, -4, 0, 0, 0, 0]),
+    ("StandardGravitationalParameter", [3, 0, -2, 0, 0, 0, 0]),
+    ("StaticPressure", [-1, 1, -2, 0, 0, 0, 0]),
+    ("StochasticProcess", [0, 0, -1, 0, 0, 0, 0]),
+    ("Strain", [0, 0, 0, 0, 0, 0, 0]),
+    ("StrainEnergyDensity", [-1, 1, -2, 0, 0, 0, 0]),
+    ("Stress", [-1, 1, -2, 0, 0, 0, 0]),
+    ("TemperatureAmountOfSubstance", [0, 0, 0, 0, 1, 1, 0]),
+    ("TemperaturePerMagneticFluxDensity", [0, -1, 2, 1, 1, 0, 0]),
+    ("TemperaturePerTime", [0, 0, -1, 0, 1, 0, 0]),
+    ("Tension", [1, 1, -2, 0, 0, 0, 0]),
+    ("ThermalConductivity", [1, 1, -3, 0, -1, 0, 0]),
+    ("ThermalDiffusivity", [2, 0, -1, 0, 0, 0, 0]),
+    ("ThermalEfficiency", [0, 0, 0, 0, 0, 0, 0]),
+    ("ThermalEnergy", [2, 1, -2, 0, 0, 0, 0]),
+    ("ThermalEnergyLength", [3, 1, -2, 0, 0, 0, 0]),
+    ("ThermalInsulance", [0, -1, 3, 0, 1, 0, 0]),
+    ("ThermalResistance", [-2, -1, 3, 0, 1, 0, 0]),
+    ("ThermalResistivity", [-1, -1, 3, 0, 1, 0, 0]),
+    ("ThermodynamicEntropy", [2, 1, -2, 0, -1, 0, 0]),
+    ("ThermodynamicTemperature", [0, 0, 0, 0, 1, 0, 0]),
+    ("Thrust", [1, 1, -2, 0, 0, 0, 0]),
+    ("ThrustToMassRatio", [1, 0, -2, 0, 0, 0, 0]),
+    ("Time", [0, 0, 1, 0, 0, 0, 0]),
+    ("TimeSquared", [0, 0, 2, 0, 0, 0, 0]),
+    ("TimeTemperature", [0, 0, 1, 0, 1, 0, 0]),
+    ("Torque", [2, 1, -2, 0, 0, 0, 0]),
+    ("TotalPressure", [-1, 1, -2, 0, 0, 0, 0]),
+    ("Velocity", [1, 0, -1, 0, 0, 0, 0]),
+    ("VideoFrameRate", [0, 0, -1, 0, 0, 0, 0]),
+    ("Viscosity", [-1, 1, -1, 0, 0, 0, 0]),
+    ("Volume", [3, 0, 0, 0, 0, 0, 0]),
+    ("VolumePerMass", [3, -1, 0, 0, 0, 0, 0]),
+    ("VolumePerTime", [3, 0, -1, 0, 0, 0, 0]),
+    ("VolumePerUnitTime", [3, 0, -1, 0, 0, 0, 0]),
+    ("VolumeThermalExpansion", [3, 0, 0, 0, -1, 0, 0]),
+    ("VolumetricHeatCapacity", [-1, 1, -2, 0, -1, 0, 0]),
+    ("Vorticity", [0, 0, -1, 0, 0, 0, 0]),
+    ("Weight", [1, 1, -2, 0, 0, 0, 0]),
+];
@@ -0,0 +1,224 @@
+#![doc = include_str!("../doc/prefix.md")]
+use crate::units::{UNIT_NS, Unit, UnitCatalog, UnitError, catalog};
+use std::sync::OnceLock;
+
+/// Decimal (SI) or binary (IEC 80000-13) prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefixKind {
+    /// `10^exponent`, from `qudt:DecimalPrefixUnit`
+    Decimal,
+    /// `2^exponent`, from `qudt:BinaryPrefixUnit`
+    Binary,
+}
+
+/// A unit prefix such as `kilo` (k) or `gibi` (Gi)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prefix {
+    /// QUDT local name, e.g. `Kilo`
+    pub name: String,
+    pub symbol: String,
+    pub kind: PrefixKind,
+    /// Power of ten (decimal) or of two (binary)
+    pub exponent: i32,
+}
+
+/// Units outside the SI that the SI Brochure (table 8) allows to take prefixes
+const ACCEPTED_NON_SI: [&str; 4] = ["Liter", "MetricTon", "ElectronVolt", "Dalton"];
+
+impl Prefix {
+    /// The factor the prefix stands for
+    pub fn factor(&self) -> f64 {
+        match self.kind {
+            PrefixKind::Decimal => 10f64.powi(self.exponent),
+            PrefixKind::Binary => 2f64.powi(self.exponent),
+        }
+    }
+
+    /// Build a prefix from a `DecimalPrefixUnit` or `BinaryPrefixUnit` entry
+    pub fn from_unit(unit: &Unit) -> Option<Prefix> {
+        let kind = if unit.has_type("DecimalPrefixUnit") {
+            PrefixKind::Decimal
+        } else if unit.has_type("BinaryPrefixUnit") {
+            PrefixKind::Binary
+        } else {
+            return None;
+        };
+        let multiplier = unit.conversion_multiplier?;
+        let exponent = match kind {
+            PrefixKind::Decimal => multiplier.log10().round() as i32,
+            // binary prefixes are powers of 1024; this also repairs the
+            // multiplier of Pebi, which QUDT 1.1 lists as 125899906842624
+            PrefixKind::Binary => 10 * (multiplier.log2() / 10.0).round() as i32,
+        };
+        Some(Prefix {
+            name: unit.name.clone(),
+            symbol: unit.symbol.clone()?,
+            kind,
+            exponent,
+        })
+    }
+
+    /// Check whether the SI (or IEC 80000-13 for binary prefixes) allows
+    /// this prefix on `unit`
+    pub fn check(&self, unit: &Unit) -> Result<(), UnitError> {
+        let reject = |reason: &str| {
+            Err(UnitError::PrefixNotAllowed {
+                prefix: self.name.clone(),
+                unit: unit.name.clone(),
+                reason: reason.to_string(),
+            })
+        };
+        if Prefix::from_unit(unit).is_some() {
+            return reject("a prefix cannot be applied to another prefix");
+        }
+        if unit.name == "Kilogram" {
+            return reject("prefixes for mass are applied to the gram");
+        }
+        if split_prefixed_name(catalog(), &unit.name).is_some() {
+            return reject("compound prefixes are not allowed");
+        }
+        if unit.conversion_offset != 0.0 {
+            return reject("the unit has a conversion offset");
+        }
+        let information = unit.has_type("InformationEntropyUnit");
+        match self.kind {
+            PrefixKind::Binary if !information => {
+                reject("binary prefixes apply to units of information only")
+            }
+            PrefixKind::Decimal
+                if !(unit.is_si()
+                    || information
+                    || ACCEPTED_NON_SI.contains(&unit.name.as_str())) =>
+            {
+                reject("the unit does not accept SI prefixes")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The prefixed unit, e.g. `Micro` applied to `Newton`.
+    ///
+    /// Prefixed units that already exist in the catalog (`Kilometer`,
+    /// `Millibar`) are returned as they are, the others are synthesized
+    /// from the base unit after checking the SI rules.
+    pub fn apply(&self, unit: &Unit) -> Result<Unit, UnitError> {
+        let name = format!("{}{}", self.name, unit.name);
+        if let Some(existing) = catalog()
+            .iter()
+            .find(|u| u.name.eq_ignore_ascii_case(&name))
+        {
+            return Ok(existing.clone());
+        }
+        self.check(unit)?;
+
+        let mut unit_types: Vec<String> = unit
+            .unit_types
+            .iter()
+            .filter(|t| *t != "SIBaseUnit" && *t != "SIDerivedUnit")
+            .cloned()
+            .collect();
+        if unit.is_si() && !unit_types.iter().any(|t| t == "SIUnit") {
+            unit_types.push("SIUnit".to_string());
+        }
+        let with_prefix =
+            |text: &Option<String>| text.as_ref().map(|s| format!("{}{}", self.symbol, s));
+        Ok(Unit {
+            iri: format!("{}{}", UNIT_NS, name),
+            label: format!("{}{}", self.name, unit.label.to_lowercase()),
+            name,
+            symbol: with_prefix(&unit.symbol),
+            abbreviation: with_prefix(&unit.abbreviation),
+            conversion_multiplier: unit.conversion_multiplier.map(|m| m * self.factor()),
+            conversion_offset: 0.0,
+            quantity_kinds: unit.quantity_kinds.clone(),
+            unit_types,
+            dimension: unit.dimension.clone(),
+            unece_common_code: None,
+        })
+    }
+}
+
+/// The prefixes defined by the bundled QUDT unit vocabulary
+pub fn prefixes() -> &'static [Prefix] {
+    static PREFIXES: OnceLock<Vec<Prefix>> = OnceLock::new();
+    PREFIXES.get_or_init(|| {
+        let mut prefixes: Vec<Prefix> = catalog().iter().filter_map(Prefix::from_unit).collect();
+        // longest symbols first, so that `da` wins over `d` when parsing
+        prefixes.sort_by(|a, b| {
+            b.symbol
+                .len()
+                .cmp(&a.symbol.len())
+                .then(a.exponent.cmp(&b.exponent))
+        });
+        prefixes
+    })
+}
+
+/// Find a prefix by its QUDT name (`Kilo`, case-insensitive) or its symbol (`k`)
+pub fn find_prefix(text: &str) -> Option<&'static Prefix> {
+    let text = normalize_micro(text);
+    prefixes().iter().find(|p| p.symbol == text).or_else(|| {
+        prefixes()
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(&text))
+    })
+}
+
+/// The decimal prefix for `10^exponent`
+pub fn decimal_prefix(exponent: i32) -> Option<&'static Prefix> {
+    prefixes()
+        .iter()
+        .find(|p| p.kind == PrefixKind::Decimal && p.exponent == exponent)
+}
+
+/// Resolve a unit symbol that may carry a prefix, e.g. `GiB` or `µN·m`.
+///
+/// Symbols of catalog units take precedence; otherwise the longest prefix
+/// symbol followed by a known unit symbol is used. In QUDT 1.1 products
+/// are written with `-` (`N-m`), so `·` and spaces are accepted for it.
+pub fn parse_prefixed(symbol: &str) -> Result<Unit, UnitError> {
+    let units = catalog();
+    let symbol = normalize_micro(symbol);
+    if let Some(unit) = find_symbol(units, &symbol) {
+        return Ok(unit.clone());
+    }
+    for prefix in prefixes() {
+        if let Some(rest) = symbol.strip_prefix(prefix.symbol.as_str())
+            && let Some(unit) = find_symbol(units, rest)
+        {
+            return prefix.apply(unit);
+        }
+    }
+    Err(UnitError::UnknownUnit(symbol))
+}
+
+/// Split a QUDT unit name into prefix and base unit, e.g. `Kilogram` into
+/// `Kilo` and `Gram`, when both are known
+pub fn split_prefixed_name<'a>(
+    units: &'a UnitCatalog,
+    name: &str,
+) -> Option<(&'static Prefix, &'a Unit)> {
+    prefixes().iter().find_map(|prefix| {
+        let rest = name.strip_prefix(prefix.name.as_str())?;
+        let mut chars = rest.chars();
+        let first = chars.next()?;
+        let base: String = first.to_uppercase().chain(chars).collect();
+        units.get(&base).map(|unit| (prefix, unit))
+    })
+}
+
+fn find_symbol<'a>(units: &'a UnitCatalog, symbol: &str) -> Option<&'a Unit> {
+    // the prefix entries themselves (`m` for Milli) are not units
+    let find = |symbol: &str| {
+        units
+            .find_by_symbol(symbol)
+            .into_iter()
+            .find(|u| Prefix::from_unit(u).is_none())
+    };
+    find(symbol).or_else(|| find(&symbol.replace(['·', ' '], "-")))
+}
+
+// U+03BC GREEK SMALL LETTER MU is often typed for the micro sign U+00B5
+fn normalize_micro(text: &str) -> String {
+    text.replace('\u{3bc}', "\u{b5}")
+}
@@ -0,0 +1,684 @@
+#![doc = include_str!("../doc/problem.md")]
+use crate::dimensional::{DIMENSIONAL_LABELS, Dimension};
+use crate::dimensional_analysis::{
+    Diagnosis, DimensionalAnalysisSolution, NamedAnalysis, PiGroup, Redundancy, Variable,
+};
+use crate::dimensionless::{Recognition, readings};
+use crate::units::UnitError;
+use num::{Rational32, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A dimensional-analysis problem as written in a problem file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Problem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub target: VariableSpec,
+    pub dependencies: Vec<VariableSpec>,
+}
+
+/// A variable declared in a problem file, either as `"name = spec"` (see
+/// `Variable::parse`) or as a table with a name and exactly one of
+/// `dimension`, `kind` and `unit`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    untagged,
+    expecting = "a variable such as \"g = LT^-2\" or a table with a name and one of dimension, kind and unit"
+)]
+pub enum VariableSpec {
+    Short(String),
+    Full {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dimension: Option<Dimension>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        kind: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
+/// Errors of reading and solving problem files
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProblemError {
+    /// The file cannot be read
+    Io(String),
+    /// The text is not a valid problem
+    Parse(String),
+    /// A variable whose declaration cannot be resolved to a dimension
+    Variable { name: String, error: UnitError },
+    /// An error in a problem file
+    InFile {
+        path: String,
+        error: Box<ProblemError>,
+    },
+}
+
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProblemError::Io(message) | ProblemError::Parse(message) => write!(f, "{}", message),
+            ProblemError::Variable { name, error } => write!(f, "variable `{}`: {}", name, error),
+            ProblemError::InFile { path, error } => write!(f, "{}: {}", path, error),
+        }
+    }
+}
+
+impl std::error::Error for ProblemError {}
+
+impl VariableSpec {
+    pub fn name(&self) -> &str {
+        match self {
+            VariableSpec::Short(text) => text
+                .split_once('=')
+                .map_or(text.as_str(), |(name, _)| name)
+                .trim(),
+            VariableSpec::Full { name, .. } => name,
+        }
+    }
+
+    /// The declaration as written, e.g. `LT^-2`, `kind: Acceleration` or
+    /// `unit: m/s^2`
+    pub fn declaration(&self) -> String {
+        match self {
+            VariableSpec::Short(text) => text
+                .split_once('=')
+                .map_or(text.as_str(), |(_, spec)| spec)
+                .trim()
+                .to_string(),
+            VariableSpec::Full {
+                dimension: Some(d), ..
+            } => d.to_string(),
+            VariableSpec::Full {
+                kind: Some(kind), ..
+            } => format!("kind: {}", kind),
+            VariableSpec::Full {
+                unit: Some(unit), ..
+            } => format!("unit: {}", unit),
+            VariableSpec::Full { .. } => String::new(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            VariableSpec::Full { description, .. } => description.as_deref(),
+            VariableSpec::Short(_) => None,
+        }
+    }
+
+    /// The named dimension of the variable
+    pub fn resolve(&self) -> Result<Variable, ProblemError> {
+        let error = |error: UnitError| ProblemError::Variable {
+            name: self.name().to_string(),
+            error,
+        };
+        match self {
+            VariableSpec::Short(text) => {
+                let (name, spec) = text.split_once('=').unwrap_or((text, text));
+                Variable::parse(name.trim(), spec).map_err(error)
+            }
+            VariableSpec::Full {
+                name,
+                dimension,
+                kind,
+                unit,
+                ..
+            } => match (dimension, kind, unit) {
+                (Some(dimension), None, None) => Ok(Variable::new(name, dimension.clone())),
+                (None, Some(kind), None) => {
+                    Variable::parse(name, &format!("kind:{}", kind)).map_err(error)
+                }
+                (None, None, Some(unit)) => {
+                    Variable::parse(name, &format!("unit:{}", unit)).map_err(error)
+                }
+                _ => Err(error(UnitError::InvalidExpression {
+                    expression: name.clone(),
+                    position: 0,
+                    message: "declare exactly one of dimension, kind and unit".to_string(),
+                })),
+            },
+        }
+    }
+}
+
+impl From<&Variable> for VariableSpec {
+    fn from(variable: &Variable) -> Self {
+        VariableSpec::Full {
+            name: variable.name.clone(),
+            dimension: Some(variable.dimension.clone()),
+            kind: None,
+            unit: None,
+            description: None,
+        }
+    }
+}
+
+impl Problem {
+    pub fn from_toml(text: &str) -> Result<Problem, ProblemError> {
+        toml::from_str(text).map_err(|e| ProblemError::Parse(e.to_string()))
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Problem, ProblemError> {
+        serde_norway::from_str(text).map_err(|e| ProblemError::Parse(e.to_string()))
+    }
+
+    pub fn from_json(text: &str) -> Result<Problem, ProblemError> {
+        serde_json::from_str(text).map_err(|e| ProblemError::Parse(e.to_string()))
+    }
+
+    /// Read a `.yaml`/`.yml` or `.json` file, or TOML otherwise
+    pub fn read(path: &Path) -> Result<Problem, ProblemError> {
+        let in_file = |error: ProblemError| ProblemError::InFile {
+            path: path.display().to_string(),
+            error: Box::new(error),
+        };
+        let text =
+            std::fs::read_to_string(path).map_err(|e| in_file(ProblemError::Io(e.to_string())))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => Problem::from_yaml(&text),
+            Some("json") => Problem::from_json(&text),
+            _ => Problem::from_toml(&text),
+        }
+        .map_err(in_file)
+    }
+
+    /// The analysis with every variable resolved to its dimension
+    pub fn analysis(&self) -> Result<NamedAnalysis, ProblemError> {
+        Ok(NamedAnalysis {
+            target: self.target.resolve()?,
+            dependencies: self
+                .dependencies
+                .iter()
+                .map(VariableSpec::resolve)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn solve(&self) -> Result<Report, ProblemError> {
+        let mut report = Report::new(self.analysis()?);
+        report.title = self.title.clone();
+        report.description = self.description.clone();
+        report.specs = std::iter::once(&self.target)
+            .chain(&self.dependencies)
+            .cloned()
+            .collect();
+        Ok(report)
+    }
+}
+
+/// The solution of a problem: the unique power law, the absence of a
+/// solution, or the Pi groups
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// The file the problem was read from
+    pub source: Option<String>,
+    /// The declarations of the target and the dependencies, in this order
+    pub specs: Vec<VariableSpec>,
+    pub analysis: NamedAnalysis,
+    pub solution: DimensionalAnalysisSolution,
+    /// The exponents of the dependencies when the solution is unique
+    pub exponents: Option<Vec<Rational32>>,
+    /// Why there is no solution, and what to add
+    pub diagnosis: Option<Diagnosis>,
+    /// Dependencies that are dimensionless, duplicated or dimensionally
+    /// dependent
+    pub redundancy: Redundancy,
+    pub pi_groups: Vec<PiGroup>,
+    /// The dimensionless numbers each Pi group is a power of, see
+    /// `dimensionless::readings`
+    pub readings: Vec<Vec<Recognition>>,
+}
+
+impl Report {
+    /// Solve an analysis; the variables are declared by their dimensions
+    pub fn new(analysis: NamedAnalysis) -> Report {
+        Report {
+            title: None,
+            description: None,
+            source: None,
+            specs: std::iter::once(&analysis.target)
+                .chain(&analysis.dependencies)
+                .map(VariableSpec::from)
+                .collect(),
+            solution: analysis.solve(),
+            exponents: analysis.analysis().exponents(),
+            diagnosis: analysis.diagnose(),
+            redundancy: analysis.redundancy(),
+            readings: analysis
+                .pi_groups()
+                .iter()
+                .map(|g| readings(&analysis, g))
+                .collect(),
+            pi_groups: analysis.pi_groups(),
+            analysis,
+        }
+    }
+
+    /// The readings of the Pi group at `index`, e.g. `Re⁻¹` or `Nu or Bi`
+    pub fn reading(&self, index: usize) -> Option<String> {
+        let readings: Vec<String> = self
+            .readings
+            .get(index)?
+            .iter()
+            .map(Recognition::to_string)
+            .collect();
+        match readings.is_empty() {
+            true => None,
+            false => Some(readings.join(" or ")),
+        }
+    }
+
+    /// The title, or the name of the source file
+    pub fn name(&self) -> String {
+        match (&self.title, &self.source) {
+            (Some(title), _) => title.clone(),
+            (None, Some(source)) => Path::new(source)
+                .file_stem()
+                .map_or(source.clone(), |s| s.to_string_lossy().to_string()),
+            (None, None) => "Dimensional analysis".to_string(),
+        }
+    }
+
+    /// One line stating the solution, e.g. `unique solution: t = C·l^1/2·g^-1/2`
+    pub fn relation(&self) -> String {
+        let names = self.analysis.names();
+        match (&self.solution, &self.exponents) {
+            (DimensionalAnalysisSolution::NoSolution, _) => {
+                format!(
+                    "no solution: {} is not a power law of the dependencies",
+                    names[0]
+                )
+            }
+            (DimensionalAnalysisSolution::UniqueSolution(_), Some(exponents)) => {
+                let factors: Vec<String> = names[1..]
+                    .iter()
+                    .zip(exponents)
+                    .filter(|(_, k)| !k.is_zero())
+                    .map(|(name, k)| match k == &Rational32::from_integer(1) {
+                        true => name.to_string(),
+                        false => format!("{}^{}", name, k),
+                    })
+                    .collect();
+                let product = if factors.is_empty() {
+                    "1".to_string()
+                } else {
+                    factors.join("·")
+                };
+                format!("unique solution: {} = C·{}", names[0], product)
+            }
+            (DimensionalAnalysisSolution::MultipleSolutions { rank, n, .. }, _) => format!(
+                "multiple solutions: rank {}, {} dependencies, {} Pi groups",
+                rank,
+                n,
+                self.pi_groups.len()
+            ),
+            _ => String::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let names = self.analysis.names();
+        let variables: Vec<&Variable> = std::iter::once(&self.analysis.target)
+            .chain(&self.analysis.dependencies)
+            .collect();
+        let mut report = json!({
+            "variables": variables,
+            "solution": self.solution,
+            "exponents": self.exponents.as_ref().map(|e| names[1..]
+                .iter()
+                .zip(e)
+                .map(|(name, k)| (name.to_string(), json!(k.to_string())))
+                .collect::<serde_json::Map<_, _>>()),
+            "relation": self.relation(),
+            "diagnosis": self.diagnosis.as_ref().map(|d| json!({
+                "inconsistent": d.inconsistent.iter().map(|&i| DIMENSIONAL_LABELS[i]).collect::<Vec<_>>(),
+                "missing": d.missing.to_string(),
+                "candidates": d.candidates.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+                "text": d.to_string(),
+            })),
+            "redundancy": {
+                "dimensionless": self.redundancy.dimensionless.iter().map(|&i| names[i + 1]).collect::<Vec<_>>(),
+                "duplicates": self.redundancy.duplicates.iter()
+                    .map(|g| g.iter().map(|&i| names[i + 1]).collect::<Vec<_>>())
+                    .collect::<Vec<_>>(),
+                "dependent": self.redundancy.dependent.iter().map(|g| g.format(&names)).collect::<Vec<_>>(),
+                "text": self.redundancy.format(&names),
+            },
+            "pi_groups": self
+                .pi_groups
+                .iter()
+                .enumerate()
+                .map(|(i, g)| {
+                    let mut group = group_json(i, g, &names);
+                    group["reading"] = json!(self.reading(i));
+                    group
+                })
+                .collect::<Vec<_>>(),
+        });
+        for (key, value) in [("title", &self.title), ("source", &self.source)] {
+            if let Some(value) = value {
+                report[key] = json!(value);
+            }
+        }
+        report
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let names = self.analysis.names();
+        let mut text = format!("## {}\n\n", self.name());
+        if let Some(description) = &self.description {
+            text.push_str(&format!("{}\n\n", description.trim()));
+        }
+        let described = self.specs.iter().any(|s| s.description().is_some());
+        text.push_str("| variable | declared as | dimension |");
+        text.push_str(if described { " description |\n" } else { "\n" });
+        text.push_str("|---|---|---|");
+        text.push_str(if described { "---|\n" } else { "\n" });
+        let variables = std::iter::once(&self.analysis.target).chain(&self.analysis.dependencies);
+        for (spec, variable) in self.specs.iter().zip(variables) {
+            text.push_str(&format!(
+                "| {} | {} | {} |",
+                variable.name,
+                spec.declaration(),
+                variable.dimension
+            ));
+            match described {
+                true => text.push_str(&format!(" {} |\n", spec.description().unwrap_or(""))),
+                false => text.push('\n'),
+            }
+        }
+        let relation = self.relation();
+        let (kind, rest) = relation.split_once(": ").unwrap_or((&relation, ""));
+        text.push_str(&format!("\n**{}**: {}\n", capitalize(kind), rest));
+        if let Some(diagnosis) = &self.diagnosis {
+            text.push_str(&format!("\n{}.\n", capitalize(&diagnosis.to_string())));
+        }
+        let redundant = self.redundancy.format(&names);
+        if !redundant.is_empty() {
+            text.push_str("\nRedundant dependencies:\n\n");
+            for line in redundant {
+                text.push_str(&format!("- {}\n", line));
+            }
+        }
+        if !self.pi_groups.is_empty() {
+            text.push_str("\n| group | product | reading |\n|---|---|---|\n");
+            for (i, group) in self.pi_groups.iter().enumerate() {
+                let reading = self.reading(i).map_or(String::new(), |r| format!("{} ", r));
+                text.push_str(&format!(
+                    "| Π{} | {} | {}|\n",
+                    i + 1,
+                    group.format(&names),
+                    reading
+                ));
+            }
+        }
+        text
+    }
+
+    pub fn to_latex(&self) -> String {
+        let names = self.analysis.names();
+        let mut text = format!("\\section*{{{}}}\n\n", latex_text(&self.name()));
+        if let Some(description) = &self.description {
+            text.push_str(&format!("{}\n\n", latex_text(description.trim())));
+        }
+        text.push_str(
+            "\\begin{tabular}{lll}\n\\hline\nvariable & declared as & dimension \\\\\n\\hline\n",
+        );
+        let variables = std::iter::once(&self.analysis.target).chain(&self.analysis.dependencies);
+        for (spec, variable) in self.specs.iter().zip(variables) {
+            text.push_str(&format!(
+                "${}$ & {} & ${}$ \\\\\n",
+                latex_name(&variable.name),
+                latex_text(&spec.declaration()),
+                latex_dimension(&variable.dimension)
+            ));
+        }
+        text.push_str("\\hline\n\\end{tabular}\n\n");
+        let latex_names: Vec<String> = names.iter().map(|n| latex_name(n)).collect();
+        match (&self.solution, &self.exponents) {
+            (DimensionalAnalysisSolution::UniqueSolution(_), Some(exponents)) => {
+                let exponents: Vec<Rational32> = std::iter::once(Rational32::zero())
+                    .chain(exponents.iter().copied())
+                    .collect();
+                text.push_str(&format!(
+                    "Unique solution:\n\\[ {} = C\\,{} \\]\n",
+                    latex_names[0],
+                    latex_product(&latex_names, &exponents)
+                ));
+            }
+            (DimensionalAnalysisSolution::NoSolution, _) => text.push_str(&format!(
+                "No solution: ${}$ is not a power law of the dependencies.\n",
+                latex_names[0]
+            )),
+            _ => text.push_str(&format!("{}.\n", capitalize(&latex_text(&self.relation())))),
+        }
+        if let Some(diagnosis) = &self.diagnosis {
+            text.push_str(&format!(
+                "{}.\n",
+                capitalize(&latex_text(&diagnosis.to_string()))
+            ));
+        }
+        let redundant = self.redundancy.format(&names);
+        if !redundant.is_empty() {
+            text.push_str("Redundant dependencies:\n\\begin{itemize}\n");
+            for line in redundant {
+                text.push_str(&format!("\\item {}\n", latex_text(&line)));
+            }
+            text.push_str("\\end{itemize}\n");
+        }
+        if !self.pi_groups.is_empty() {
+            text.push_str("\\begin{align*}\n");
+            let lines: Vec<String> = self
+                .pi_groups
+                .iter()
+                .enumerate()
+                .map(|(i, g)| {
+                    let exponents: Vec<Rational32> = g
+                        .exponents
+                        .iter()
+                        .map(|k| Rational32::from_integer(*k))
+                        .collect();
+                    let readings: Vec<String> =
+                        self.readings[i].iter().map(latex_reading).collect();
+                    let reading = match readings.is_empty() {
+                        true => String::new(),
+                        false => format!(" = {}", readings.join(" = ")),
+                    };
+                    format!(
+                        "\\Pi_{{{}}} &= {}{}",
+                        i + 1,
+                        latex_product(&latex_names, &exponents),
+                        reading
+                    )
+                })
+                .collect();
+            text.push_str(&lines.join(" \\\\\n"));
+            text.push_str("\n\\end{align*}\n");
+        }
+        text
+    }
+}
+
+/// The output formats of `write_reports`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+    Latex,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "latex" | "tex" => Ok(ReportFormat::Latex),
+            _ => Err(format!(
+                "unknown format `{}`, expected json, markdown or latex",
+                text
+            )),
+        }
+    }
+}
+
+/// Read and solve problem files; the first error stops the batch
+pub fn solve_files<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Report>, ProblemError> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+            let mut report =
+                Problem::read(path)?
+                    .solve()
+                    .map_err(|error| ProblemError::InFile {
+                        path: path.display().to_string(),
+                        error: Box::new(error),
+                    })?;
+            report.source = Some(path.display().to_string());
+            Ok(report)
+        })
+        .collect()
+}
+
+/// The reports as a JSON array, or as Markdown or LaTeX sections
+pub fn write_reports(reports: &[Report], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Json => {
+            let reports: Vec<Value> = reports.iter().map(Report::to_json).collect();
+            serde_json::to_string_pretty(&reports).expect("JSON values serialize")
+        }
+        ReportFormat::Markdown => reports
+            .iter()
+            .map(Report::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ReportFormat::Latex => reports
+            .iter()
+            .map(Report::to_latex)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+fn group_json(i: usize, group: &PiGroup, names: &[&str]) -> Value {
+    let exponents: serde_json::Map<String, Value> = names
+        .iter()
+        .zip(&group.exponents)
+        .filter(|(_, k)| **k != 0)
+        .map(|(name, k)| (name.to_string(), json!(k)))
+        .collect();
+    json!({
+        "name": format!("Π{}", i + 1),
+        "product": group.format(names),
+        "exponents": exponents,
+    })
+}
+
+// `\mathrm{Re}^{-1}`
+fn latex_reading(recognition: &Recognition) -> String {
+    let symbol = format!("\\mathrm{{{}}}", recognition.number.symbol);
+    match recognition.power == Rational32::from_integer(1) {
+        true => symbol,
+        false => format!("{}^{{{}}}", symbol, recognition.power),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map_or(String::new(), |c| c.to_uppercase().chain(chars).collect())
+}
+
+const GREEK: [&str; 34] = [
+    "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa",
+    "lambda", "mu", "nu", "xi", "pi", "rho", "sigma", "tau", "upsilon", "phi", "chi", "psi",
+    "omega", "Gamma", "Delta", "Theta", "Lambda", "Xi", "Pi", "Sigma", "Upsilon", "Phi", "Psi",
+    "Omega",
+];
+
+// `rho` as `\rho`, `x_1` as `x_{1}`, longer names upright
+fn latex_name(name: &str) -> String {
+    let (base, subscript) = name.split_once('_').unwrap_or((name, ""));
+    let base = match base {
+        b if GREEK.contains(&b) => format!("\\{}", b),
+        b if b.chars().count() > 1 => format!("\\mathrm{{{}}}", latex_text(b)),
+        b => latex_text(b),
+    };
+    match subscript.is_empty() {
+        true => base,
+        false => format!("{}_{{\\mathrm{{{}}}}}", base, latex_text(subscript)),
+    }
+}
+
+fn latex_product(names: &[String], exponents: &[Rational32]) -> String {
+    let factors: Vec<String> = names
+        .iter()
+        .zip(exponents)
+        .filter(|(_, k)| !k.is_zero())
+        .map(|(name, k)| match k == &Rational32::from_integer(1) {
+            true => name.clone(),
+            false => format!("{}^{{{}}}", name, k),
+        })
+        .collect();
+    match factors.is_empty() {
+        true => "1".to_string(),
+        false => factors.join("\\,"),
+    }
+}
+
+fn latex_dimension(dimension: &Dimension) -> String {
+    let factors: Vec<String> = DIMENSIONAL_LABELS
+        .iter()
+        .zip(dimension.get_exponents())
+        .filter(|(_, k)| !k.is_zero())
+        .map(|(label, k)| {
+            let label = if *label == "Θ" {
+                "\\Theta".to_string()
+            } else {
+                format!("\\mathsf{{{}}}", label)
+            };
+            match k == Rational32::from_integer(1) {
+                true => label,
+                false => format!("{}^{{{}}}", label, k),
+            }
+        })
+        .collect();
+    match factors.is_empty() {
+        true => "1".to_string(),
+        false => factors.join("\\,"),
+    }
+}
+
+// escape the characters that are special in LaTeX text
+fn latex_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '·' => escaped.push_str("$\\cdot$"),
+            'Θ' => escaped.push_str("$\\Theta$"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
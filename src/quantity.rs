@@ -0,0 +1,307 @@
+#![doc = include_str!("../doc/quantity.md")]
+use crate::dimensional::{Dimension, TEMPERATURE};
+use crate::expression::DerivedUnit;
+use crate::format::{FormatOptions, format_measurement};
+use crate::matrix::Matrix;
+use crate::quantity_kind::{QuantityKind, quantity_kinds};
+use crate::units::{Unit, UnitError};
+use std::fmt;
+
+/// A measured value with its standard uncertainty, both expressed in `unit`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub uncertainty: f64,
+    pub unit: Unit,
+    /// QUDT quantity kind, e.g. `Torque`; `None` when untagged
+    pub kind: Option<String>,
+    pub affine: Affine,
+}
+
+/// Whether a value is a point on the scale of its unit or a difference of
+/// two points. The distinction matters for temperatures: 20 °C is 293.15 K
+/// but a rise of 20 °C is a rise of 20 K.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Affine {
+    /// A point on a scale, e.g. the temperature of a bath; conversion
+    /// applies the offset of °C and °F
+    Absolute,
+    /// A difference, e.g. a temperature rise or an uncertainty; conversion
+    /// ignores offsets
+    Interval,
+}
+
+/// How arithmetic and conversion treat quantity kinds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KindMode {
+    /// Only dimensions are checked; differing kinds are dropped
+    #[default]
+    Lenient,
+    /// Tagged kinds must be compatible, see `QuantityKind::is_compatible_with`
+    Strict,
+}
+
+impl Quantity {
+    /// Create an exact quantity (zero uncertainty). Temperatures are
+    /// absolute, see `Quantity::interval` for temperature differences.
+    pub fn new(value: f64, unit: &Unit) -> Self {
+        let affine = match unit.dimension == Some(TEMPERATURE) {
+            true => Affine::Absolute,
+            false => Affine::Interval,
+        };
+        Quantity {
+            value,
+            uncertainty: 0.0,
+            unit: unit.clone(),
+            kind: None,
+            affine,
+        }
+    }
+
+    /// Create an exact difference, e.g. a temperature rise of 5 °C
+    pub fn interval(value: f64, unit: &Unit) -> Self {
+        Quantity::new(value, unit).with_affine(Affine::Interval)
+    }
+
+    /// Mark the quantity as a point or a difference
+    pub fn with_affine(mut self, affine: Affine) -> Self {
+        self.affine = affine;
+        self
+    }
+
+    /// Set the standard uncertainty
+    pub fn with_uncertainty(mut self, uncertainty: f64) -> Self {
+        self.uncertainty = uncertainty.abs();
+        self
+    }
+
+    /// Tag the quantity with a QUDT quantity kind of the unit's dimension.
+    ///
+    /// A quantity already tagged with another kind must be `cast` instead.
+    pub fn with_kind(self, kind: &str) -> Result<Self, UnitError> {
+        match &self.kind {
+            Some(current) if current != kind => Err(UnitError::KindMismatch {
+                from: current.clone(),
+                to: kind.to_string(),
+            }),
+            _ => self.cast(kind),
+        }
+    }
+
+    /// Explicitly re-tag the quantity, e.g. to treat a torque as an energy.
+    /// Only the dimension has to agree.
+    pub fn cast(mut self, kind: &str) -> Result<Self, UnitError> {
+        let quantity_kind = lookup_kind(kind)?;
+        if let (Some(a), Some(b)) = (&self.unit.dimension, &quantity_kind.dimension)
+            && a != b
+        {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.unit.name.clone(),
+                to: kind.to_string(),
+            });
+        }
+        self.kind = Some(quantity_kind.name.clone());
+        Ok(self)
+    }
+
+    /// The dimension of the unit, tagged with the quantity kind if any
+    pub fn dimension(&self) -> Option<Dimension> {
+        let dimension = self.unit.dimension.clone()?;
+        Some(match &self.kind {
+            Some(kind) => dimension.with_kind(kind),
+            None => dimension,
+        })
+    }
+
+    /// Relative standard uncertainty `u/|x|`
+    pub fn relative_uncertainty(&self) -> f64 {
+        self.uncertainty / self.value.abs()
+    }
+
+    /// Express the quantity in another unit of the same dimension; the
+    /// offset of °C and °F applies to absolute values only
+    pub fn convert_to(&self, unit: &Unit) -> Result<Quantity, UnitError> {
+        let value = match self.affine {
+            Affine::Absolute => self.unit.convert(self.value, unit)?,
+            Affine::Interval => self.unit.convert_interval(self.value, unit)?,
+        };
+        Ok(Quantity {
+            value,
+            // the offset cancels out for the uncertainty
+            uncertainty: self.unit.convert_interval(self.uncertainty, unit)?,
+            unit: unit.clone(),
+            kind: self.kind.clone(),
+            affine: self.affine,
+        })
+    }
+
+    /// Convert, rejecting in strict mode a target unit whose quantity kinds
+    /// are all incompatible with the tag: newton metres of torque cannot be
+    /// converted to joules
+    pub fn checked_convert_to(&self, unit: &Unit, mode: KindMode) -> Result<Quantity, UnitError> {
+        if mode == KindMode::Strict
+            && let Some(kind) = &self.kind
+            && !unit.quantity_kinds.is_empty()
+        {
+            let tag = lookup_kind(kind)?;
+            let compatible = unit
+                .quantity_kinds
+                .iter()
+                .filter_map(|k| quantity_kinds().get(k))
+                .any(|k| tag.is_compatible_with(k));
+            if !compatible {
+                return Err(UnitError::KindMismatch {
+                    from: kind.clone(),
+                    to: unit.quantity_kinds.join(", "),
+                });
+            }
+        }
+        self.convert_to(unit)
+    }
+
+    /// Sum in the unit of `self`, uncertainties added in quadrature. An
+    /// interval can be added to an absolute value, two absolute values
+    /// cannot be added.
+    pub fn checked_add(&self, other: &Quantity, mode: KindMode) -> Result<Quantity, UnitError> {
+        self.combine(other, mode, 1.0)
+    }
+
+    /// Difference in the unit of `self`, uncertainties added in quadrature.
+    /// The difference of two absolute values is an interval in the unit of
+    /// differences (`Unit::interval_unit`): 25 °C - 20 °C is 5 K.
+    pub fn checked_sub(&self, other: &Quantity, mode: KindMode) -> Result<Quantity, UnitError> {
+        self.combine(other, mode, -1.0)
+    }
+
+    fn combine(&self, other: &Quantity, mode: KindMode, sign: f64) -> Result<Quantity, UnitError> {
+        let kind = self.common_kind(other, mode)?;
+        let mismatch = |reason: &str| UnitError::AffineMismatch {
+            from: other.to_string(),
+            to: self.to_string(),
+            reason: reason.to_string(),
+        };
+        let affine = match (self.affine, other.affine) {
+            (Affine::Absolute, Affine::Absolute) if sign > 0.0 => {
+                return Err(mismatch(
+                    "absolute values cannot be added, subtract them or add an interval",
+                ));
+            }
+            (Affine::Absolute, Affine::Absolute) => Affine::Interval,
+            (Affine::Interval, Affine::Absolute) if sign < 0.0 => {
+                return Err(mismatch(
+                    "an absolute value cannot be subtracted from an interval",
+                ));
+            }
+            (Affine::Interval, Affine::Interval) => Affine::Interval,
+            _ => Affine::Absolute,
+        };
+        let other = other.convert_to(&self.unit)?;
+        let result = Quantity {
+            value: self.value + sign * other.value,
+            uncertainty: self.uncertainty.hypot(other.uncertainty),
+            unit: self.unit.clone(),
+            kind,
+            affine,
+        };
+        match self.affine == Affine::Absolute && affine == Affine::Interval {
+            true => result.convert_to(&self.unit.interval_unit()),
+            false => Ok(result),
+        }
+    }
+
+    // the kind of a sum: the more general of two compatible kinds
+    fn common_kind(&self, other: &Quantity, mode: KindMode) -> Result<Option<String>, UnitError> {
+        let (a, b) = match (&self.kind, &other.kind) {
+            (Some(a), Some(b)) if a != b => (lookup_kind(a)?, lookup_kind(b)?),
+            (Some(k), _) | (_, Some(k)) => return Ok(Some(k.clone())),
+            (None, None) => return Ok(None),
+        };
+        if !a.is_compatible_with(b) {
+            return match mode {
+                KindMode::Strict => Err(UnitError::KindMismatch {
+                    from: b.name.clone(),
+                    to: a.name.clone(),
+                }),
+                KindMode::Lenient => Ok(None),
+            };
+        }
+        let general = if a.ancestors().iter().any(|k| k.iri == b.iri) {
+            b
+        } else {
+            a
+        };
+        Ok(Some(general.name.clone()))
+    }
+
+    /// Format the measurement result, see `FormatOptions`
+    pub fn format(&self, options: &FormatOptions) -> String {
+        format_measurement(self.value, self.uncertainty, &self.unit, options)
+    }
+}
+
+/// The product `Π xᵢ^pᵢ` of quantities whose errors are correlated with the
+/// coefficients `correlation[(i, j)]`. The uncertainties combine as
+/// `u² = Σᵢ Σⱼ cᵢ cⱼ rᵢⱼ uᵢ uⱼ` with the sensitivities `cᵢ = ∂y/∂xᵢ`
+/// (GUM 5.1.2 and 5.2.2), so a factor may be zero; pass `Matrix::identity`
+/// for uncorrelated inputs.
+///
+/// Absolute values on a scale with an offset are converted to the unit of
+/// differences first: 10 °C enters as 283.15 K.
+pub fn power_product(factors: &[(&Quantity, i32)], correlation: &Matrix<f64>) -> Quantity {
+    assert_eq!(
+        correlation.dimensions(),
+        (factors.len(), factors.len()),
+        "one correlation coefficient per pair of factors"
+    );
+    let factors: Vec<(Quantity, i32)> = factors
+        .iter()
+        .map(
+            |(q, p)| match q.affine == Affine::Absolute && q.unit.conversion_offset != 0.0 {
+                true => (
+                    q.convert_to(&q.unit.interval_unit())
+                        .unwrap_or_else(|_| (*q).clone()),
+                    *p,
+                ),
+                false => ((*q).clone(), *p),
+            },
+        )
+        .collect();
+    let mut unit = DerivedUnit::one();
+    let mut value = 1.0;
+    for (quantity, power) in &factors {
+        unit.push(quantity.unit.clone(), *power);
+        value *= quantity.value.powi(*power);
+    }
+    let sensitivities: Vec<f64> = (0..factors.len())
+        .map(|i| {
+            factors
+                .iter()
+                .enumerate()
+                .map(|(k, (q, p))| match k == i {
+                    true => *p as f64 * q.value.powi(p - 1),
+                    false => q.value.powi(*p),
+                })
+                .product::<f64>()
+                * factors[i].0.uncertainty
+        })
+        .collect();
+    let mut variance = 0.0;
+    for (i, a) in sensitivities.iter().enumerate() {
+        for (j, b) in sensitivities.iter().enumerate() {
+            variance += a * b * correlation[(i, j)];
+        }
+    }
+    Quantity::new(value, &unit.to_unit()).with_uncertainty(variance.max(0.0).sqrt())
+}
+
+fn lookup_kind(kind: &str) -> Result<&'static QuantityKind, UnitError> {
+    quantity_kinds()
+        .get(kind)
+        .ok_or_else(|| UnitError::UnknownQuantityKind(kind.to_string()))
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&FormatOptions::default()))
+    }
+}
@@ -0,0 +1,301 @@
+#![doc = include_str!("../doc/quantity_kind.md")]
+use crate::dimensional::Dimension;
+use crate::qudt_records::{KindRecord, kind_records};
+use crate::rdf::Triple;
+use crate::units::{Unit, catalog};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// A quantity kind of the QUDT `quantity` vocabulary, e.g. `Torque`
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantityKind {
+    pub iri: String,
+    pub name: String,
+    pub label: String,
+    pub symbol: Option<String>,
+    /// Local names of the more general kinds (`qudt:generalization`)
+    pub generalizations: Vec<String>,
+    /// `skos:exactMatch` links to external vocabularies
+    pub exact_matches: Vec<String>,
+    /// `skos:closeMatch` links to external vocabularies
+    pub close_matches: Vec<String>,
+    /// QUDT category, e.g. `MechanicsQuantityKind`
+    pub categories: Vec<String>,
+    pub dimension: Option<Dimension>,
+}
+
+/// `qudt:generalization` links that do not make the kinds compatible. The SI
+/// reserves the hertz for periodic phenomena and the becquerel for stochastic
+/// processes, so activity is kept apart from frequency.
+pub const SEPARATE_KINDS: &[(&str, &str)] = &[("StochasticProcess", "Frequency")];
+
+/// The quantity kinds and their `qudt:generalization` links
+#[derive(Clone, Debug, Default)]
+pub struct QuantityKindGraph {
+    kinds: Vec<QuantityKind>,
+    index: HashMap<String, usize>,
+}
+
+/// A conversion between units whose quantity kinds share a dimension but
+/// are not related, e.g. newton metre (torque) to joule (energy)
+#[derive(Clone, Debug, PartialEq)]
+pub struct KindWarning {
+    pub from: String,
+    pub to: String,
+    pub from_kinds: Vec<String>,
+    pub to_kinds: Vec<String>,
+}
+
+impl fmt::Display for KindWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "converting {} ({}) to {} ({}): same dimension, unrelated quantity kinds",
+            self.from,
+            self.from_kinds.join(", "),
+            self.to,
+            self.to_kinds.join(", ")
+        )
+    }
+}
+
+impl QuantityKindGraph {
+    /// Build the graph from the triples of a QUDT quantity vocabulary
+    pub fn from_triples(triples: &[Triple]) -> Self {
+        let mut graph = QuantityKindGraph::default();
+        for kind in kind_records(triples) {
+            graph.insert(QuantityKind::from(kind));
+        }
+        graph
+    }
+
+    /// Add a quantity kind, replacing any kind with the same IRI
+    pub fn insert(&mut self, kind: QuantityKind) {
+        match self.index.get(&kind.iri) {
+            Some(&i) => self.kinds[i] = kind,
+            None => {
+                let i = self.kinds.len();
+                self.index.insert(kind.iri.clone(), i);
+                self.index.entry(kind.name.clone()).or_insert(i);
+                self.kinds.push(kind);
+            }
+        }
+    }
+
+    /// Look up a quantity kind by local name (`Torque`) or full IRI
+    pub fn get(&self, name: &str) -> Option<&QuantityKind> {
+        self.index.get(name).map(|&i| &self.kinds[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &QuantityKind> {
+        self.kinds.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// The transitive generalizations of `kind`, nearest first
+    pub fn ancestors(&self, kind: &QuantityKind) -> Vec<&QuantityKind> {
+        self.ancestors_by(kind, |_, _| true)
+    }
+
+    // the generalizations reached through the links accepted by `follow`
+    fn ancestors_by(
+        &self,
+        kind: &QuantityKind,
+        follow: impl Fn(&str, &str) -> bool,
+    ) -> Vec<&QuantityKind> {
+        let mut ancestors: Vec<&QuantityKind> = Vec::new();
+        let mut pending = vec![kind];
+        while !pending.is_empty() {
+            let mut next = Vec::new();
+            for child in pending {
+                for name in &child.generalizations {
+                    if let Some(parent) = self.get(name)
+                        && follow(&child.name, &parent.name)
+                        && parent.iri != kind.iri
+                        && !ancestors.iter().any(|a| a.iri == parent.iri)
+                    {
+                        ancestors.push(parent);
+                        next.push(parent);
+                    }
+                }
+            }
+            pending = next;
+        }
+        ancestors
+    }
+
+    /// The transitive specializations of `kind`, nearest first
+    pub fn descendants(&self, kind: &QuantityKind) -> Vec<&QuantityKind> {
+        let mut descendants: Vec<&QuantityKind> = Vec::new();
+        let mut pending = vec![kind.name.as_str()];
+        while !pending.is_empty() {
+            let mut next = Vec::new();
+            for name in pending {
+                for child in self
+                    .kinds
+                    .iter()
+                    .filter(|k| k.generalizations.iter().any(|g| g == name))
+                {
+                    if child.iri != kind.iri && !descendants.iter().any(|d| d.iri == child.iri) {
+                        descendants.push(child);
+                        next.push(child.name.as_str());
+                    }
+                }
+            }
+            pending = next;
+        }
+        descendants
+    }
+}
+
+impl QuantityKind {
+    /// The transitive generalizations in the bundled graph, nearest first
+    pub fn ancestors(&self) -> Vec<&'static QuantityKind> {
+        quantity_kinds().ancestors(self)
+    }
+
+    /// The transitive specializations in the bundled graph
+    pub fn descendants(&self) -> Vec<&'static QuantityKind> {
+        quantity_kinds().descendants(self)
+    }
+
+    /// The units of the bundled catalog linked to this kind or to one of its
+    /// generalizations: a thrust can be given in newtons
+    pub fn applicable_units(&self) -> Vec<&'static Unit> {
+        let mut kinds = vec![self.name.clone()];
+        kinds.extend(self.ancestors().iter().map(|k| k.name.clone()));
+        catalog()
+            .iter()
+            .filter(|u| u.quantity_kinds.iter().any(|k| kinds.contains(k)))
+            .collect()
+    }
+
+    /// Whether values of the two kinds may be mixed: the kinds are the same
+    /// or one generalizes the other. Torque and energy share a dimension but
+    /// are not compatible.
+    ///
+    /// QUDT 1.1 links a few kinds of different dimensions (`AngularMomentum`
+    /// → `Momentum`), so known dimensions must also agree. The links in
+    /// `SEPARATE_KINDS` are not followed.
+    pub fn is_compatible_with(&self, other: &QuantityKind) -> bool {
+        if self.iri == other.iri {
+            return true;
+        }
+        if let (Some(a), Some(b)) = (&self.dimension, &other.dimension)
+            && a != b
+        {
+            return false;
+        }
+        let graph = quantity_kinds();
+        let follow = |child: &str, parent: &str| !SEPARATE_KINDS.contains(&(child, parent));
+        graph
+            .ancestors_by(self, follow)
+            .iter()
+            .any(|k| k.iri == other.iri)
+            || graph
+                .ancestors_by(other, follow)
+                .iter()
+                .any(|k| k.iri == self.iri)
+    }
+}
+
+/// The quantity kinds of the bundled QUDT vocabulary, built on first use
+/// from the constants generated at compile time
+pub fn quantity_kinds() -> &'static QuantityKindGraph {
+    static GRAPH: OnceLock<QuantityKindGraph> = OnceLock::new();
+    GRAPH.get_or_init(|| {
+        let mut graph = QuantityKindGraph::default();
+        for kind in QUANTITY_KINDS {
+            graph.insert(QuantityKind::from(kind));
+        }
+        graph
+    })
+}
+
+/// A quantity kind of the bundled QUDT vocabulary, generated by `build.rs`
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantityKindConstant {
+    pub iri: &'static str,
+    pub name: &'static str,
+    pub label: &'static str,
+    pub symbol: Option<&'static str>,
+    pub generalizations: &'static [&'static str],
+    pub exact_matches: &'static [&'static str],
+    pub close_matches: &'static [&'static str],
+    pub categories: &'static [&'static str],
+    pub dimension: Option<Dimension>,
+}
+
+impl From<&QuantityKindConstant> for QuantityKind {
+    fn from(kind: &QuantityKindConstant) -> Self {
+        let strings = |values: &[&str]| values.iter().map(|s| s.to_string()).collect();
+        QuantityKind {
+            iri: kind.iri.to_string(),
+            name: kind.name.to_string(),
+            label: kind.label.to_string(),
+            symbol: kind.symbol.map(str::to_string),
+            generalizations: strings(kind.generalizations),
+            exact_matches: strings(kind.exact_matches),
+            close_matches: strings(kind.close_matches),
+            categories: strings(kind.categories),
+            dimension: kind.dimension.clone(),
+        }
+    }
+}
+
+impl From<KindRecord> for QuantityKind {
+    fn from(kind: KindRecord) -> Self {
+        QuantityKind {
+            iri: kind.iri,
+            name: kind.name,
+            label: kind.label,
+            symbol: kind.symbol,
+            generalizations: kind.generalizations,
+            exact_matches: kind.exact_matches,
+            close_matches: kind.close_matches,
+            categories: kind.categories,
+            dimension: kind.dimension.map(Dimension::PowerLawIA),
+        }
+    }
+}
+
+mod constants {
+    use super::{Dimension, QuantityKindConstant};
+    include!(concat!(env!("OUT_DIR"), "/quantity_kinds.rs"));
+}
+pub use constants::QUANTITY_KINDS;
+
+/// Warn about a conversion between commensurable units whose quantity kinds
+/// are unrelated, e.g. `NewtonMeter` (torque) to `Joule` (energy).
+///
+/// Units without quantity kinds are not checked.
+pub fn check_conversion(from: &Unit, to: &Unit) -> Option<KindWarning> {
+    let graph = quantity_kinds();
+    let kinds = |unit: &Unit| -> Vec<&QuantityKind> {
+        unit.quantity_kinds
+            .iter()
+            .filter_map(|k| graph.get(k))
+            .collect()
+    };
+    let (from_kinds, to_kinds) = (kinds(from), kinds(to));
+    if !from.is_commensurable(to) || from_kinds.is_empty() || to_kinds.is_empty() {
+        return None;
+    }
+    let related = from_kinds
+        .iter()
+        .any(|a| to_kinds.iter().any(|b| a.is_compatible_with(b)));
+    (!related).then(|| KindWarning {
+        from: from.name.clone(),
+        to: to.name.clone(),
+        from_kinds: from_kinds.iter().map(|k| k.name.clone()).collect(),
+        to_kinds: to_kinds.iter().map(|k| k.name.clone()).collect(),
+    })
+}
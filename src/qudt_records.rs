@@ -0,0 +1,276 @@
+// Reading the units and quantity kinds of the QUDT vocabularies from their
+// triples. Shared with `build.rs`, which generates the unit and quantity kind
+// constants from the same records.
+use crate::rdf::{self, OWL_NS, QUDT_NS, RDF_TYPE, RDFS_NS, SKOS_NS, Term, Triple};
+use std::collections::{HashMap, HashSet};
+
+include!("ontology/quantity_kind_dimensions.rs");
+
+pub const QUANTITY_KIND_NS: &str = "http://qudt.org/vocab/quantity#";
+
+/// A unit as described by one document; properties it does not state are `None`
+pub struct UnitRecord {
+    pub iri: String,
+    pub name: String,
+    pub label: Option<String>,
+    pub symbol: Option<String>,
+    pub abbreviation: Option<String>,
+    pub multiplier: Option<f64>,
+    pub offset: Option<f64>,
+    pub quantity_kinds: Vec<String>,
+    pub unit_types: Vec<String>,
+    pub dimension: Option<[i32; 7]>,
+    pub unece_common_code: Option<String>,
+}
+
+/// Values the QUDT 1.1 unit vocabulary states wrongly: the unit, the
+/// property, the stated value and the correct one. Records stating the wrong
+/// value are corrected, so that the constants of `build.rs` and the units
+/// read at run time agree.
+const CORRECTIONS: &[(&str, &str, f64, f64)] = &[
+    // 459.67·5/9 K, rounded to 255.37037 K
+    (
+        "DegreeFahrenheit",
+        "conversionOffset",
+        255.37037037037037,
+        2298.35 / 9.0,
+    ),
+    // 13.5951 g/cm³ · 1 mm · 9.80665 m/s²
+    (
+        "MillimeterOfMercury",
+        "conversionMultiplier",
+        86014.2806,
+        133.322387415,
+    ),
+];
+
+fn correct(unit: &mut UnitRecord) {
+    for (name, property, stated, correct) in CORRECTIONS {
+        if unit.name != *name {
+            continue;
+        }
+        let value = match *property {
+            "conversionMultiplier" => &mut unit.multiplier,
+            _ => &mut unit.offset,
+        };
+        if value.is_some_and(|v| (v - stated).abs() <= 1e-12 * stated.abs()) {
+            *value = Some(*correct);
+        }
+    }
+}
+
+/// The units among the subjects of `triples`: resources typed with a QUDT
+/// `...Unit` class, and the resources `known` accepts without a type
+pub fn unit_records(triples: &[Triple], known: impl Fn(&str) -> bool) -> Vec<UnitRecord> {
+    let mut subjects: Vec<&str> = Vec::new();
+    let mut by_subject: HashMap<&str, Vec<&Triple>> = HashMap::new();
+    for triple in triples {
+        if let Term::Iri(subject) = &triple.subject {
+            let entry = by_subject.entry(subject.as_str()).or_default();
+            if entry.is_empty() {
+                subjects.push(subject);
+            }
+            entry.push(triple);
+        }
+    }
+
+    let mut units = Vec::new();
+    for subject in subjects {
+        let triples = &by_subject[subject];
+        let unit_types: Vec<String> = triples
+            .iter()
+            .filter(|t| t.predicate == RDF_TYPE)
+            .filter_map(|t| t.object.as_iri())
+            .filter_map(|iri| iri.strip_prefix(QUDT_NS))
+            .map(str::to_string)
+            .collect();
+        if !known(subject)
+            && !unit_types
+                .iter()
+                .any(|t| t.ends_with("Unit") && !t.starts_with("SystemOf"))
+        {
+            continue;
+        }
+        let literal = |predicate: &str| {
+            triples
+                .iter()
+                .find(|t| t.predicate == predicate)
+                .and_then(|t| t.object.as_literal())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let number = |predicate: &str| literal(predicate).and_then(|s| s.parse::<f64>().ok());
+        let mut unit = UnitRecord {
+            iri: subject.to_string(),
+            name: rdf::local_name(subject).to_string(),
+            label: literal(&format!("{}label", RDFS_NS)),
+            symbol: literal(&format!("{}symbol", QUDT_NS)),
+            abbreviation: literal(&format!("{}abbreviation", QUDT_NS)),
+            multiplier: number(&format!("{}conversionMultiplier", QUDT_NS)),
+            offset: number(&format!("{}conversionOffset", QUDT_NS)),
+            quantity_kinds: Vec::new(),
+            unit_types,
+            dimension: None,
+            unece_common_code: literal(&format!("{}uneceCommonCode", QUDT_NS)),
+        };
+        correct(&mut unit);
+        units.push(unit);
+    }
+    units
+}
+
+/// Attach the `qudt:quantityKind` links found in `triples` and derive the
+/// dimensions of the units
+pub fn link_quantity_kinds(units: &mut [UnitRecord], triples: &[Triple]) {
+    let index: HashMap<String, usize> = units
+        .iter()
+        .enumerate()
+        .map(|(i, u)| (u.iri.clone(), i))
+        .collect();
+    let quantity_kind = format!("{}quantityKind", QUDT_NS);
+    for triple in triples {
+        if triple.predicate != quantity_kind {
+            continue;
+        }
+        let (Term::Iri(subject), Term::Iri(kind)) = (&triple.subject, &triple.object) else {
+            continue;
+        };
+        if let Some(&i) = index.get(subject.as_str()) {
+            let unit = &mut units[i];
+            let kind = rdf::local_name(kind).to_string();
+            if unit.dimension.is_none() {
+                unit.dimension = dimension(&kind);
+            }
+            unit.quantity_kinds.push(kind);
+        }
+    }
+
+    // some units (Millimeter, ...) only carry a class such as qudt:LengthUnit
+    let class_kinds = class_quantity_kinds(triples);
+    for unit in units.iter_mut().filter(|u| u.dimension.is_none()) {
+        unit.dimension = unit
+            .unit_types
+            .iter()
+            .filter_map(|t| class_kinds.get(t.as_str()).copied())
+            .chain(
+                unit.unit_types
+                    .iter()
+                    .filter_map(|t| t.strip_suffix("Unit")),
+            )
+            .find_map(dimension);
+    }
+}
+
+/// The quantity kind a unit class is restricted to by an `owl:hasValue`
+/// restriction on `qudt:quantityKind`, e.g. `EnergyDensityUnit` → `EnergyDensity`
+fn class_quantity_kinds(triples: &[Triple]) -> HashMap<&str, &str> {
+    let on_property = format!("{}onProperty", OWL_NS);
+    let has_value = format!("{}hasValue", OWL_NS);
+    let quantity_kind = format!("{}quantityKind", QUDT_NS);
+    let restrictions: HashSet<&Term> = triples
+        .iter()
+        .filter(|t| t.predicate == on_property && t.object.as_iri() == Some(quantity_kind.as_str()))
+        .map(|t| &t.subject)
+        .collect();
+    let values: HashMap<&Term, &str> = triples
+        .iter()
+        .filter(|t| t.predicate == has_value && restrictions.contains(&t.subject))
+        .filter_map(|t| Some((&t.subject, rdf::local_name(t.object.as_iri()?))))
+        .collect();
+    let sub_class_of = format!("{}subClassOf", RDFS_NS);
+    let equivalent_class = format!("{}equivalentClass", OWL_NS);
+    let mut classes = HashMap::new();
+    for triple in triples {
+        if (triple.predicate == sub_class_of || triple.predicate == equivalent_class)
+            && let Term::Iri(class) = &triple.subject
+            && let Some(kind) = values.get(&triple.object)
+        {
+            classes.entry(rdf::local_name(class)).or_insert(*kind);
+        }
+    }
+    classes
+}
+
+/// The exponents (L, M, T, I, Θ, N, J) of a QUDT quantity kind given by its local name
+pub fn dimension(kind: &str) -> Option<[i32; 7]> {
+    QUANTITY_KIND_DIMENSIONS
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map(|(_, exponents)| *exponents)
+}
+
+/// A quantity kind of the QUDT `quantity` vocabulary
+pub struct KindRecord {
+    pub iri: String,
+    pub name: String,
+    pub label: String,
+    pub symbol: Option<String>,
+    pub generalizations: Vec<String>,
+    pub exact_matches: Vec<String>,
+    pub close_matches: Vec<String>,
+    pub categories: Vec<String>,
+    pub dimension: Option<[i32; 7]>,
+}
+
+/// The quantity kinds among the subjects of `triples`: resources of the
+/// quantity kind namespace typed with a QUDT `...QuantityKind` class
+pub fn kind_records(triples: &[Triple]) -> Vec<KindRecord> {
+    let mut by_subject: Vec<(&str, Vec<&Triple>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for triple in triples {
+        if let Term::Iri(subject) = &triple.subject
+            && subject.starts_with(QUANTITY_KIND_NS)
+        {
+            let i = *positions.entry(subject).or_insert_with(|| {
+                by_subject.push((subject, Vec::new()));
+                by_subject.len() - 1
+            });
+            by_subject[i].1.push(triple);
+        }
+    }
+
+    let mut kinds = Vec::new();
+    for (subject, triples) in by_subject {
+        let categories: Vec<String> = triples
+            .iter()
+            .filter(|t| t.predicate == RDF_TYPE)
+            .filter_map(|t| t.object.as_iri()?.strip_prefix(QUDT_NS))
+            .filter(|t| t.ends_with("QuantityKind"))
+            .map(str::to_string)
+            .collect();
+        if categories.is_empty() {
+            continue;
+        }
+        let iris = |predicate: &str| -> Vec<String> {
+            triples
+                .iter()
+                .filter(|t| t.predicate == predicate)
+                .filter_map(|t| t.object.as_iri())
+                .map(str::to_string)
+                .collect()
+        };
+        let literal = |predicate: &str| {
+            triples
+                .iter()
+                .find(|t| t.predicate == predicate)
+                .and_then(|t| t.object.as_literal())
+                .map(|s| s.trim().to_string())
+        };
+        let name = rdf::local_name(subject).to_string();
+        kinds.push(KindRecord {
+            iri: subject.to_string(),
+            label: literal(&format!("{}label", RDFS_NS)).unwrap_or_else(|| name.clone()),
+            symbol: literal(&format!("{}symbol", QUDT_NS)),
+            generalizations: iris(&format!("{}generalization", QUDT_NS))
+                .iter()
+                .map(|iri| rdf::local_name(iri).to_string())
+                .collect(),
+            exact_matches: iris(&format!("{}exactMatch", SKOS_NS)),
+            close_matches: iris(&format!("{}closeMatch", SKOS_NS)),
+            categories,
+            dimension: dimension(&name),
+            name,
+        });
+    }
+    kinds
+}
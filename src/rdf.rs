@@ -0,0 +1,663 @@
+#![doc = include_str!("../doc/rdf.md")]
+use std::collections::HashMap;
+use std::fmt;
+
+pub const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+pub const RDFS_NS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+pub const QUDT_NS: &str = "http://qudt.org/schema/qudt#";
+pub const SKOS_NS: &str = "http://www.w3.org/2004/02/skos/core#";
+pub const OWL_NS: &str = "http://www.w3.org/2002/07/owl#";
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// An RDF term: IRI, blank node or literal
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    Iri(String),
+    BlankNode(String),
+    Literal {
+        value: String,
+        datatype: Option<String>,
+        lang: Option<String>,
+    },
+}
+
+impl Term {
+    /// The IRI of an `Iri` term
+    pub fn as_iri(&self) -> Option<&str> {
+        match self {
+            Term::Iri(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    /// The lexical value of a `Literal` term
+    pub fn as_literal(&self) -> Option<&str> {
+        match self {
+            Term::Literal { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Iri(iri) => write!(f, "<{}>", iri),
+            Term::BlankNode(id) => write!(f, "_:{}", id),
+            Term::Literal {
+                value,
+                datatype,
+                lang,
+            } => {
+                write!(f, "{:?}", value)?;
+                if let Some(lang) = lang {
+                    write!(f, "@{}", lang)?;
+                } else if let Some(datatype) = datatype {
+                    write!(f, "^^<{}>", datatype)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A single RDF statement
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term,
+}
+
+/// Error raised while reading an RDF document
+#[derive(Clone, Debug, PartialEq)]
+pub struct RdfError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RdfError {}
+
+/// Returns the part of an IRI after the last `#` or `/`
+pub fn local_name(iri: &str) -> &str {
+    match iri.rfind(['#', '/']) {
+        Some(pos) => &iri[pos + 1..],
+        None => iri,
+    }
+}
+
+/// Resolves a (possibly relative) IRI reference against a base IRI
+pub fn resolve_iri(base: &str, reference: &str) -> String {
+    if reference.is_empty() {
+        return base.to_string();
+    }
+    if reference.starts_with('#') {
+        let stem = base.split('#').next().unwrap_or(base);
+        return format!("{}{}", stem, reference);
+    }
+    // anything with a scheme is already absolute
+    if let Some(colon) = reference.find(':')
+        && reference[..colon]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+    {
+        return reference.to_string();
+    }
+    match base.rfind('/') {
+        Some(pos) => format!("{}{}", &base[..pos + 1], reference),
+        None => reference.to_string(),
+    }
+}
+
+pub(crate) fn unescape_xml(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                // QUDT descriptions use "&#xD;\n" line endings
+                if c != '\r' {
+                    result.push(c);
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[derive(Debug)]
+enum XmlEvent {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+        line: usize,
+    },
+    End {
+        name: String,
+        line: usize,
+    },
+    Text(String),
+}
+
+struct XmlReader<'a> {
+    src: &'a str,
+    pos: usize,
+    // line number of `counted`, advanced lazily
+    counted: std::cell::Cell<(usize, usize)>,
+}
+
+impl<'a> XmlReader<'a> {
+    fn new(src: &'a str) -> Self {
+        XmlReader {
+            src,
+            pos: 0,
+            counted: std::cell::Cell::new((0, 1)),
+        }
+    }
+
+    fn line(&self) -> usize {
+        let (counted, line) = self.counted.get();
+        let line = line + self.src[counted..self.pos].matches('\n').count();
+        self.counted.set((self.pos, line));
+        line
+    }
+
+    fn error(&self, message: &str) -> RdfError {
+        RdfError {
+            line: self.line(),
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_until(&mut self, pattern: &str) -> Result<(), RdfError> {
+        match self.src[self.pos..].find(pattern) {
+            Some(end) => {
+                self.pos += end + pattern.len();
+                Ok(())
+            }
+            None => Err(self.error(&format!("unterminated markup, expected `{}`", pattern))),
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<XmlEvent>, RdfError> {
+        loop {
+            let rest = &self.src[self.pos..];
+            if rest.is_empty() {
+                return Ok(None);
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..end];
+                self.pos += end;
+                return Ok(Some(XmlEvent::Text(unescape_xml(text))));
+            }
+            if rest.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                let start = self.pos + "<![CDATA[".len();
+                self.skip_until("]]>")?;
+                let text = &self.src[start..self.pos - "]]>".len()];
+                return Ok(Some(XmlEvent::Text(text.to_string())));
+            } else if rest.starts_with("<!") {
+                self.skip_until(">")?;
+            } else if let Some(stripped) = rest.strip_prefix("</") {
+                let line = self.line();
+                let end = stripped
+                    .find('>')
+                    .ok_or_else(|| self.error("unterminated end tag"))?;
+                let name = stripped[..end].trim().to_string();
+                self.pos += end + 3;
+                return Ok(Some(XmlEvent::End { name, line }));
+            } else {
+                return self.read_start_tag().map(Some);
+            }
+        }
+    }
+
+    fn read_start_tag(&mut self) -> Result<XmlEvent, RdfError> {
+        let line = self.line();
+        let bytes = self.src.as_bytes();
+        let mut i = self.pos + 1;
+        let mut quote: Option<u8> = None;
+        while i < bytes.len() {
+            match (quote, bytes[i]) {
+                (None, b'"') | (None, b'\'') => quote = Some(bytes[i]),
+                (Some(q), c) if c == q => quote = None,
+                (None, b'>') => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return Err(self.error("unterminated start tag"));
+        }
+        let mut inner = &self.src[self.pos + 1..i];
+        self.pos = i + 1;
+        let empty = inner.ends_with('/');
+        if empty {
+            inner = &inner[..inner.len() - 1];
+        }
+        let name_end = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        let name = inner[..name_end].to_string();
+        let mut attributes = Vec::new();
+        let mut rest = inner[name_end..].trim_start();
+        while !rest.is_empty() {
+            let eq = rest.find('=').ok_or_else(|| RdfError {
+                line,
+                message: format!("malformed attribute in <{}>", name),
+            })?;
+            let key = rest[..eq].trim().to_string();
+            let value_part = rest[eq + 1..].trim_start();
+            let q = value_part.chars().next().ok_or_else(|| RdfError {
+                line,
+                message: format!("missing attribute value in <{}>", name),
+            })?;
+            if q != '"' && q != '\'' {
+                return Err(RdfError {
+                    line,
+                    message: format!("unquoted attribute value in <{}>", name),
+                });
+            }
+            let close = value_part[1..].find(q).ok_or_else(|| RdfError {
+                line,
+                message: format!("unterminated attribute value in <{}>", name),
+            })?;
+            attributes.push((key, unescape_xml(&value_part[1..close + 1])));
+            rest = value_part[close + 2..].trim_start();
+        }
+        Ok(XmlEvent::Start {
+            name,
+            attributes,
+            empty,
+            line,
+        })
+    }
+}
+
+struct Scope {
+    namespaces: HashMap<String, String>,
+    base: String,
+    lang: Option<String>,
+}
+
+/// Reads the RDF/XML subset used by the bundled QUDT ontologies
+struct RdfXmlParser<'a> {
+    reader: XmlReader<'a>,
+    scopes: Vec<Scope>,
+    triples: Vec<Triple>,
+    blank_counter: usize,
+}
+
+impl<'a> RdfXmlParser<'a> {
+    fn push_scope(&mut self, attributes: &[(String, String)]) {
+        let parent = self.scopes.last();
+        let mut scope = Scope {
+            namespaces: parent.map(|s| s.namespaces.clone()).unwrap_or_default(),
+            base: parent.map(|s| s.base.clone()).unwrap_or_default(),
+            lang: parent.and_then(|s| s.lang.clone()),
+        };
+        for (key, value) in attributes {
+            if let Some(prefix) = key.strip_prefix("xmlns:") {
+                scope.namespaces.insert(prefix.to_string(), value.clone());
+            } else if key == "xmlns" {
+                scope.namespaces.insert(String::new(), value.clone());
+            } else if key == "xml:base" {
+                scope.base = value.clone();
+            } else if key == "xml:lang" {
+                scope.lang = Some(value.clone()).filter(|l| !l.is_empty());
+            }
+        }
+        self.scopes.push(scope);
+    }
+
+    fn scope(&self) -> &Scope {
+        self.scopes.last().expect("namespace scope")
+    }
+
+    fn expand(&self, qname: &str, line: usize) -> Result<String, RdfError> {
+        let (prefix, local) = match qname.find(':') {
+            Some(pos) => (&qname[..pos], &qname[pos + 1..]),
+            None => ("", qname),
+        };
+        match self.scope().namespaces.get(prefix) {
+            Some(ns) => Ok(format!("{}{}", ns, local)),
+            None => Err(RdfError {
+                line,
+                message: format!("undeclared namespace prefix in `{}`", qname),
+            }),
+        }
+    }
+
+    fn new_blank(&mut self) -> Term {
+        self.blank_counter += 1;
+        Term::BlankNode(format!("genid{}", self.blank_counter))
+    }
+
+    fn is_syntax_attribute(key: &str) -> bool {
+        key.starts_with("xmlns") || key.starts_with("xml:")
+    }
+
+    fn parse_document(&mut self) -> Result<(), RdfError> {
+        while let Some(event) = self.reader.next_event()? {
+            match event {
+                XmlEvent::Start {
+                    name,
+                    attributes,
+                    empty,
+                    line,
+                } => {
+                    self.push_scope(&attributes);
+                    let iri = self.expand(&name, line)?;
+                    if iri == format!("{}RDF", RDF_NS) {
+                        if !empty {
+                            self.parse_node_list(&name)?;
+                        }
+                    } else {
+                        self.parse_node(&name, &attributes, empty, line)?;
+                    }
+                    self.scopes.pop();
+                }
+                XmlEvent::Text(text) if text.trim().is_empty() => {}
+                XmlEvent::Text(_) => {
+                    return Err(self.reader.error("unexpected text outside of rdf:RDF"));
+                }
+                XmlEvent::End { name, line } => {
+                    return Err(RdfError {
+                        line,
+                        message: format!("unexpected end tag </{}>", name),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_node_list(&mut self, parent: &str) -> Result<(), RdfError> {
+        while let Some(event) = self.reader.next_event()? {
+            match event {
+                XmlEvent::Start {
+                    name,
+                    attributes,
+                    empty,
+                    line,
+                } => {
+                    self.push_scope(&attributes);
+                    self.parse_node(&name, &attributes, empty, line)?;
+                    self.scopes.pop();
+                }
+                XmlEvent::End { name, .. } if name == parent => return Ok(()),
+                XmlEvent::End { name, line } => {
+                    return Err(RdfError {
+                        line,
+                        message: format!("mismatched end tag </{}>", name),
+                    });
+                }
+                XmlEvent::Text(_) => {}
+            }
+        }
+        Err(self.reader.error(&format!("missing </{}>", parent)))
+    }
+
+    /// Parses a node element and returns its subject term
+    fn parse_node(
+        &mut self,
+        name: &str,
+        attributes: &[(String, String)],
+        empty: bool,
+        line: usize,
+    ) -> Result<Term, RdfError> {
+        let mut subject = None;
+        for (key, value) in attributes {
+            match key.as_str() {
+                "rdf:about" => subject = Some(Term::Iri(resolve_iri(&self.scope().base, value))),
+                "rdf:ID" => {
+                    subject = Some(Term::Iri(resolve_iri(
+                        &self.scope().base,
+                        &format!("#{}", value),
+                    )))
+                }
+                "rdf:nodeID" => subject = Some(Term::BlankNode(value.clone())),
+                _ => {}
+            }
+        }
+        let subject = match subject {
+            Some(subject) => subject,
+            None => self.new_blank(),
+        };
+
+        let class = self.expand(name, line)?;
+        if class != format!("{}Description", RDF_NS) {
+            self.triples.push(Triple {
+                subject: subject.clone(),
+                predicate: RDF_TYPE.to_string(),
+                object: Term::Iri(class),
+            });
+        }
+        for (key, value) in attributes {
+            if Self::is_syntax_attribute(key)
+                || matches!(key.as_str(), "rdf:about" | "rdf:ID" | "rdf:nodeID")
+            {
+                continue;
+            }
+            let predicate = self.expand(key, line)?;
+            let object = if predicate == RDF_TYPE {
+                Term::Iri(resolve_iri(&self.scope().base, value))
+            } else {
+                Term::Literal {
+                    value: value.clone(),
+                    datatype: None,
+                    lang: self.scope().lang.clone(),
+                }
+            };
+            self.triples.push(Triple {
+                subject: subject.clone(),
+                predicate,
+                object,
+            });
+        }
+
+        if empty {
+            return Ok(subject);
+        }
+        while let Some(event) = self.reader.next_event()? {
+            match event {
+                XmlEvent::Start {
+                    name: property,
+                    attributes,
+                    empty,
+                    line,
+                } => {
+                    self.push_scope(&attributes);
+                    self.parse_property(&subject, &property, &attributes, empty, line)?;
+                    self.scopes.pop();
+                }
+                XmlEvent::End { name: end, .. } if end == name => return Ok(subject),
+                XmlEvent::End { name: end, line } => {
+                    return Err(RdfError {
+                        line,
+                        message: format!("mismatched end tag </{}>, expected </{}>", end, name),
+                    });
+                }
+                XmlEvent::Text(_) => {}
+            }
+        }
+        Err(self.reader.error(&format!("missing </{}>", name)))
+    }
+
+    fn parse_property(
+        &mut self,
+        subject: &Term,
+        name: &str,
+        attributes: &[(String, String)],
+        empty: bool,
+        line: usize,
+    ) -> Result<(), RdfError> {
+        let predicate = self.expand(name, line)?;
+        let mut datatype = None;
+        for (key, value) in attributes {
+            match key.as_str() {
+                "rdf:resource" => {
+                    let object = Term::Iri(resolve_iri(&self.scope().base, value));
+                    self.triples.push(Triple {
+                        subject: subject.clone(),
+                        predicate: predicate.clone(),
+                        object,
+                    });
+                }
+                "rdf:nodeID" => {
+                    self.triples.push(Triple {
+                        subject: subject.clone(),
+                        predicate: predicate.clone(),
+                        object: Term::BlankNode(value.clone()),
+                    });
+                }
+                "rdf:datatype" => datatype = Some(value.clone()),
+                "rdf:parseType" => {
+                    return Err(RdfError {
+                        line,
+                        message: format!("rdf:parseType=\"{}\" is not supported", value),
+                    });
+                }
+                _ => {}
+            }
+        }
+        let has_object = attributes
+            .iter()
+            .any(|(k, _)| k == "rdf:resource" || k == "rdf:nodeID");
+        if empty {
+            if !has_object {
+                self.triples.push(Triple {
+                    subject: subject.clone(),
+                    predicate,
+                    object: Term::Literal {
+                        value: String::new(),
+                        datatype,
+                        lang: self.scope().lang.clone(),
+                    },
+                });
+            }
+            return Ok(());
+        }
+
+        let mut text = String::new();
+        while let Some(event) = self.reader.next_event()? {
+            match event {
+                XmlEvent::Text(t) => text.push_str(&t),
+                XmlEvent::Start {
+                    name: node,
+                    attributes,
+                    empty,
+                    line,
+                } => {
+                    // nested node element as the object of this property
+                    self.push_scope(&attributes);
+                    let object = self.parse_node(&node, &attributes, empty, line)?;
+                    self.scopes.pop();
+                    self.triples.push(Triple {
+                        subject: subject.clone(),
+                        predicate: predicate.clone(),
+                        object,
+                    });
+                    return self.expect_end(name);
+                }
+                XmlEvent::End { name: end, .. } if end == name => {
+                    if !has_object {
+                        self.triples.push(Triple {
+                            subject: subject.clone(),
+                            predicate,
+                            object: Term::Literal {
+                                value: text,
+                                datatype,
+                                lang: self.scope().lang.clone(),
+                            },
+                        });
+                    }
+                    return Ok(());
+                }
+                XmlEvent::End { name: end, line } => {
+                    return Err(RdfError {
+                        line,
+                        message: format!("mismatched end tag </{}>, expected </{}>", end, name),
+                    });
+                }
+            }
+        }
+        Err(self.reader.error(&format!("missing </{}>", name)))
+    }
+
+    fn expect_end(&mut self, name: &str) -> Result<(), RdfError> {
+        while let Some(event) = self.reader.next_event()? {
+            match event {
+                XmlEvent::Text(t) if t.trim().is_empty() => {}
+                XmlEvent::End { name: end, .. } if end == name => return Ok(()),
+                _ => {
+                    return Err(self
+                        .reader
+                        .error(&format!("property <{}> has more than one object", name)));
+                }
+            }
+        }
+        Err(self.reader.error(&format!("missing </{}>", name)))
+    }
+}
+
+/// Parses an RDF/XML document into a list of triples
+pub fn parse_rdf_xml(src: &str) -> Result<Vec<Triple>, RdfError> {
+    let mut parser = RdfXmlParser {
+        reader: XmlReader::new(src),
+        scopes: vec![Scope {
+            namespaces: HashMap::from([
+                (
+                    "xml".to_string(),
+                    "http://www.w3.org/XML/1998/namespace".to_string(),
+                ),
+                ("rdf".to_string(), RDF_NS.to_string()),
+            ]),
+            base: String::new(),
+            lang: None,
+        }],
+        triples: Vec::new(),
+        blank_counter: 0,
+    };
+    parser.parse_document()?;
+    Ok(parser.triples)
+}
@@ -0,0 +1,824 @@
+#![doc = include_str!("../doc/repl.md")]
+use crate::dimensional::{DIMENSIONLESS, Dimension};
+use crate::expression::DerivedUnit;
+use crate::format::{FormatOptions, format_measurement, round_significant};
+use crate::quantity::Affine;
+use crate::unit_system::unit_system;
+use crate::units::{UnitError, catalog};
+use num::Rational32;
+use std::collections::BTreeMap;
+use std::f64::consts::{E, PI};
+use std::sync::OnceLock;
+
+/// A value of the calculator: the value in coherent SI units, its dimension,
+/// the unit to show it in and its contributions to the uncertainty
+#[derive(Clone, Debug, PartialEq)]
+pub struct Value {
+    pub value: f64,
+    pub dimension: Dimension,
+    /// The unit to show the value in, `None` for the coherent SI unit
+    pub unit: Option<DerivedUnit>,
+    /// A reading such as 20 °C, or a difference; see `Quantity::checked_add`
+    pub affine: Affine,
+    // `∂value/∂xᵢ · u(xᵢ)` for each uncertain input `xᵢ`, so that a variable
+    // used twice is correlated with itself
+    contributions: BTreeMap<usize, f64>,
+}
+
+pub const FUNCTIONS: &[&str] = &[
+    "sqrt", "cbrt", "abs", "exp", "ln", "log10", "sin", "cos", "tan", "asin", "acos", "atan",
+];
+pub const CONSTANTS: &[(&str, f64)] = &[("pi", PI), ("π", PI), ("e", E)];
+pub const COMMANDS: &[&str] = &[":dim", ":vars", ":help", ":quit"];
+
+pub const HELP: &str = "\
+  L = 2.5 m ± 1 mm          assign a variable (`+/-` works too)
+  T = 2*pi*sqrt(L/g) in ms  evaluate, show the result in a unit
+  g = 9.81 m/s^2; L/g       several statements on one line
+  :dim <expression>         the dimension of an expression
+  :vars                     the variables
+  :quit                     leave
+functions: sqrt cbrt abs exp ln log10 sin cos tan asin acos atan; constants: pi e";
+
+impl Value {
+    /// An exact value in coherent SI units
+    pub fn exact(value: f64, dimension: Dimension) -> Self {
+        Value {
+            value,
+            dimension,
+            unit: None,
+            affine: Affine::Interval,
+            contributions: BTreeMap::new(),
+        }
+    }
+
+    // a reading in a unit with an offset, e.g. 20 °C
+    fn reading(value: f64, unit: DerivedUnit) -> Result<Self, UnitError> {
+        let target = unit.to_unit();
+        let mut value = Value::exact(
+            target.to_si(value)?,
+            target.dimension.unwrap_or(DIMENSIONLESS),
+        );
+        value.unit = Some(unit);
+        value.affine = Affine::Absolute;
+        Ok(value)
+    }
+
+    /// The combined standard uncertainty in coherent SI units
+    pub fn uncertainty(&self) -> f64 {
+        self.contributions
+            .values()
+            .map(|c| c * c)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// The unit the value is shown in: its own unit, else the coherent SI
+    /// unit of the dimension
+    pub fn display_unit(&self) -> Result<DerivedUnit, UnitError> {
+        match &self.unit {
+            Some(unit) => Ok(unit.clone()),
+            None => Ok(DerivedUnit::from(
+                unit_system("SI")
+                    .expect("SI")
+                    .coherent_unit(&self.dimension)?,
+            )),
+        }
+    }
+
+    /// The value and the uncertainty in `unit`; the offset of °C and °F
+    /// applies to the value
+    pub fn in_unit(&self, unit: &DerivedUnit) -> Result<(f64, f64), UnitError> {
+        let target = unit.to_unit();
+        if target.dimension.as_ref() != Some(&self.dimension) {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.dimension.to_string(),
+                to: unit.symbol(),
+            });
+        }
+        let multiplier = unit
+            .conversion_multiplier()
+            .ok_or_else(|| UnitError::MissingConversion(unit.symbol()))?;
+        Ok((target.from_si(self.value)?, self.uncertainty() / multiplier))
+    }
+
+    /// Show the value in its display unit; a pure number without unit, and
+    /// a value without SI unit (fractional powers) in SI with its dimension
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let round = |v: f64, u: f64| match u == 0.0 {
+            true => (round_significant(v, 12), u),
+            false => (v, u),
+        };
+        let mut bare = catalog().require("Unitless").expect("Unitless").clone();
+        bare.symbol = Some(String::new());
+        if self.unit.is_none() && self.dimension.is_dimensionless() {
+            let (v, u) = round(self.value, self.uncertainty());
+            return format_measurement(v, u, &bare, options);
+        }
+        match self
+            .display_unit()
+            .and_then(|unit| Ok((self.in_unit(&unit)?, unit)))
+        {
+            Ok(((v, u), unit)) => {
+                let (v, u) = round(v, u);
+                format_measurement(v, u, &unit.to_unit(), options)
+            }
+            Err(_) => {
+                let (v, u) = round(self.value, self.uncertainty());
+                format!(
+                    "{} [{}]",
+                    format_measurement(v, u, &bare, options),
+                    self.dimension
+                )
+            }
+        }
+    }
+
+    fn is_exact(&self) -> bool {
+        self.contributions.is_empty()
+    }
+
+    // `f(self, other)` with the partial derivatives `da` and `db`
+    fn combine(&self, other: &Value, value: f64, da: f64, db: f64, dimension: Dimension) -> Value {
+        let mut contributions = BTreeMap::new();
+        for (source, c) in &self.contributions {
+            *contributions.entry(*source).or_insert(0.0) += da * c;
+        }
+        for (source, c) in &other.contributions {
+            *contributions.entry(*source).or_insert(0.0) += db * c;
+        }
+        Value {
+            value,
+            dimension: Dimension::PowerLawRA(dimension.get_exponents()),
+            unit: None,
+            affine: Affine::Interval,
+            contributions,
+        }
+    }
+
+    // `f(self)` with the derivative `d`
+    fn map(&self, value: f64, d: f64, dimension: Dimension) -> Value {
+        self.combine(&Value::exact(0.0, DIMENSIONLESS), value, d, 0.0, dimension)
+    }
+
+    fn check_same_dimension(&self, other: &Value) -> Result<(), UnitError> {
+        match self.dimension == other.dimension {
+            true => Ok(()),
+            false => Err(UnitError::IncompatibleDimensions {
+                from: other.dimension.to_string(),
+                to: self.dimension.to_string(),
+            }),
+        }
+    }
+
+    // the rules of `Quantity::checked_add` for readings and differences
+    fn add(&self, other: &Value, sign: f64) -> Result<Value, UnitError> {
+        self.check_same_dimension(other)?;
+        let options = FormatOptions::default();
+        let mismatch = |reason: &str| UnitError::AffineMismatch {
+            from: other.format(&options),
+            to: self.format(&options),
+            reason: reason.to_string(),
+        };
+        let (affine, unit) = match (self.affine, other.affine) {
+            (Affine::Absolute, Affine::Absolute) if sign > 0.0 => {
+                return Err(mismatch(
+                    "readings cannot be added, subtract them or add a difference",
+                ));
+            }
+            (Affine::Interval, Affine::Absolute) if sign < 0.0 => {
+                return Err(mismatch("a reading cannot be subtracted from a difference"));
+            }
+            // a difference of two °C readings is not a °C reading
+            (Affine::Absolute, Affine::Absolute) => (Affine::Interval, None),
+            (Affine::Interval, Affine::Absolute) => (Affine::Absolute, other.unit.clone()),
+            (affine, _) => (affine, self.unit.clone()),
+        };
+        let mut result = self.combine(
+            other,
+            self.value + sign * other.value,
+            1.0,
+            sign,
+            self.dimension.clone(),
+        );
+        result.unit = unit.filter(|u| affine == Affine::Absolute || !has_offset(u));
+        result.affine = affine;
+        Ok(result)
+    }
+
+    fn mul(&self, other: &Value) -> Value {
+        let dimension = self.dimension.clone() * other.dimension.clone();
+        let mut result = self.combine(
+            other,
+            self.value * other.value,
+            other.value,
+            self.value,
+            dimension,
+        );
+        result.unit = product_unit(self, other, 1);
+        result
+    }
+
+    fn div(&self, other: &Value) -> Value {
+        let (a, b) = (self.value, other.value);
+        let dimension = self.dimension.clone() / other.dimension.clone();
+        let mut result = self.combine(other, a / b, 1.0 / b, -a / (b * b), dimension);
+        result.unit = product_unit(self, other, -1);
+        result
+    }
+
+    fn pow(&self, exponent: &Value) -> Result<Value, UnitError> {
+        if !exponent.dimension.is_dimensionless() {
+            return Err(UnitError::IncompatibleDimensions {
+                from: exponent.dimension.to_string(),
+                to: "an exponent".to_string(),
+            });
+        }
+        let (a, p) = (self.value, exponent.value);
+        let value = a.powf(p);
+        if self.dimension.is_dimensionless() {
+            let db = if exponent.is_exact() {
+                0.0
+            } else {
+                value * a.ln()
+            };
+            return Ok(self.combine(exponent, value, p * a.powf(p - 1.0), db, DIMENSIONLESS));
+        }
+        if !exponent.is_exact() {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.dimension.to_string(),
+                to: "an uncertain exponent".to_string(),
+            });
+        }
+        if p.abs() * 12.0 > i32::MAX as f64 {
+            return Err(UnitError::InvalidExpression {
+                expression: p.to_string(),
+                position: 0,
+                message: "exponent out of range".to_string(),
+            });
+        }
+        let rational = (1..=12)
+            .map(|d| (p * d as f64, d))
+            .find(|(n, _)| (n - n.round()).abs() < 1e-9)
+            .map(|(n, d)| Rational32::new(n.round() as i32, d))
+            .ok_or_else(|| UnitError::IncompatibleDimensions {
+                from: self.dimension.to_string(),
+                to: format!("the irrational power {}", p),
+            })?;
+        let mut result = self.map(
+            value,
+            p * a.powf(p - 1.0),
+            Dimension::PowerLawRA(self.dimension.get_exponents().map(|e| e * rational)),
+        );
+        result.unit = self
+            .unit
+            .clone()
+            .filter(|u| !has_offset(u))
+            .and_then(|u| unit_power(u, rational));
+        Ok(result)
+    }
+
+    fn function(&self, name: &str) -> Result<Value, UnitError> {
+        let x = self.value;
+        match name {
+            "sqrt" => return self.pow(&Value::exact(0.5, DIMENSIONLESS)),
+            "cbrt" => return self.pow(&Value::exact(1.0 / 3.0, DIMENSIONLESS)),
+            "abs" => {
+                let mut result = self.map(x.abs(), x.signum(), self.dimension.clone());
+                result.unit = self.unit.clone();
+                return Ok(result);
+            }
+            _ => {}
+        }
+        if !self.dimension.is_dimensionless() {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.dimension.to_string(),
+                to: format!("the argument of {}", name),
+            });
+        }
+        let (value, d) = match name {
+            "exp" => (x.exp(), x.exp()),
+            "ln" => (x.ln(), 1.0 / x),
+            "log10" => (x.log10(), 1.0 / (x * std::f64::consts::LN_10)),
+            "sin" => (x.sin(), x.cos()),
+            "cos" => (x.cos(), -x.sin()),
+            "tan" => (x.tan(), 1.0 + x.tan().powi(2)),
+            "asin" => (x.asin(), 1.0 / (1.0 - x * x).sqrt()),
+            "acos" => (x.acos(), -1.0 / (1.0 - x * x).sqrt()),
+            "atan" => (x.atan(), 1.0 / (1.0 + x * x)),
+            _ => return Err(UnitError::UnknownUnit(name.to_string())),
+        };
+        Ok(self.map(value, d, DIMENSIONLESS))
+    }
+}
+
+// the unit of a product or quotient: the units of both sides combined, or
+// the unit of one side when the other is a pure number. Readings such as
+// 10 °C enter in kelvin, so a unit with an offset falls back to SI.
+fn product_unit(a: &Value, b: &Value, sign: i32) -> Option<DerivedUnit> {
+    let plain = |v: &Value| v.dimension.is_dimensionless() && v.unit.is_none();
+    match (&a.unit, &b.unit) {
+        (Some(x), Some(y)) => Some(x.clone() * y.clone().powi(sign)),
+        (Some(x), None) if plain(b) => Some(x.clone()),
+        (None, Some(y)) if plain(a) => Some(y.clone().powi(sign)),
+        _ => None,
+    }
+    .filter(|u| !has_offset(u) && !u.factors.is_empty())
+}
+
+// whether any factor is a unit with an offset, such as °C in `degC·m`
+fn has_offset(unit: &DerivedUnit) -> bool {
+    unit.factors.iter().any(|(u, _)| u.conversion_offset != 0.0)
+}
+
+// a rational power of a unit when every exponent stays an integer
+fn unit_power(unit: DerivedUnit, power: Rational32) -> Option<DerivedUnit> {
+    let mut result = DerivedUnit::one();
+    result.scale = unit
+        .scale
+        .powf(*power.numer() as f64 / *power.denom() as f64);
+    for (u, e) in unit.factors {
+        let exponent = Rational32::from_integer(e) * power;
+        if !exponent.is_integer() {
+            return None;
+        }
+        result.push(u, exponent.to_integer());
+    }
+    Some(result)
+}
+
+/// A completion candidate: `replacement` is inserted, `display` is listed
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Completion {
+    pub replacement: String,
+    pub display: String,
+}
+
+/// The state of the calculator: variables and the count of uncertain inputs
+#[derive(Clone, Debug, Default)]
+pub struct Session {
+    variables: BTreeMap<String, Value>,
+    inputs: usize,
+    pub options: FormatOptions,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    pub fn set(&mut self, name: &str, value: Value) {
+        self.variables.insert(name.to_string(), value);
+    }
+
+    pub fn variables(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.variables.iter()
+    }
+
+    /// Evaluate an expression, e.g. `2*pi*sqrt(L/g)` or `2.5 m ± 1 mm`
+    pub fn evaluate(&mut self, expression: &str) -> Result<Value, UnitError> {
+        let mut parser = Parser::new(expression, self);
+        let value = parser.uncertain()?;
+        parser.skip_spaces();
+        match parser.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("unexpected character")),
+        }
+    }
+
+    /// Run one line of `;`-separated statements and return the lines to
+    /// print: `name = expression [in unit]`, `expression [in unit]` or a
+    /// `:command`
+    pub fn run(&mut self, line: &str) -> Result<Vec<String>, UnitError> {
+        let mut output = Vec::new();
+        for statement in line.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            output.push(self.statement(statement)?);
+        }
+        Ok(output)
+    }
+
+    fn statement(&mut self, statement: &str) -> Result<String, UnitError> {
+        if let Some(command) = statement.strip_prefix(':') {
+            let (command, argument) = command.split_once(' ').unwrap_or((command, ""));
+            return match command {
+                "dim" => Ok(self.evaluate(argument)?.dimension.to_string()),
+                "vars" => Ok(self
+                    .variables
+                    .iter()
+                    .map(|(name, value)| format!("{} = {}", name, value.format(&self.options)))
+                    .collect::<Vec<_>>()
+                    .join("\n")),
+                "help" => Ok(HELP.to_string()),
+                _ => Err(UnitError::InvalidExpression {
+                    expression: statement.to_string(),
+                    position: 0,
+                    message: format!("unknown command, one of {}", COMMANDS.join(" ")),
+                }),
+            };
+        }
+        let (name, expression) = match statement.split_once('=') {
+            Some((name, rest)) if is_identifier(name.trim()) => (Some(name.trim()), rest.trim()),
+            _ => (None, statement),
+        };
+        let (expression, unit) = split_unit(expression);
+        let mut value = self.evaluate(expression)?;
+        if let Some(unit) = unit {
+            let unit = DerivedUnit::parse(unit)?;
+            value.in_unit(&unit)?;
+            // a value shown in °C is a reading
+            if has_offset(&unit) {
+                value.affine = Affine::Absolute;
+            }
+            value.unit = Some(unit);
+        }
+        let text = value.format(&self.options);
+        Ok(match name {
+            Some(name) => {
+                if FUNCTIONS.contains(&name) || CONSTANTS.iter().any(|(c, _)| *c == name) {
+                    return Err(UnitError::InvalidExpression {
+                        expression: statement.to_string(),
+                        position: 0,
+                        message: format!("`{}` is reserved", name),
+                    });
+                }
+                self.set(name, value);
+                format!("{} = {}", name, text)
+            }
+            None => text,
+        })
+    }
+
+    /// Completions of the word that ends at `position`: commands, variables,
+    /// functions, constants and the QUDT units by symbol or by name. Returns
+    /// the start of the word and the candidates.
+    pub fn complete(&self, line: &str, position: usize) -> (usize, Vec<Completion>) {
+        let head = &line[..position];
+        let start = head
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_identifier_char(*c) || *c == ':')
+            .last()
+            .map_or(position, |(i, _)| i);
+        let word = &head[start..];
+        let same = |text: &str| Completion {
+            replacement: text.to_string(),
+            display: text.to_string(),
+        };
+        if word.is_empty() {
+            return (start, Vec::new());
+        }
+        if word.starts_with(':') {
+            return (
+                start,
+                COMMANDS
+                    .iter()
+                    .filter(|c| c.starts_with(word))
+                    .map(|c| same(c))
+                    .collect(),
+            );
+        }
+        let lower = word.to_lowercase();
+        let mut candidates: Vec<Completion> = self
+            .variables
+            .keys()
+            .map(String::as_str)
+            .chain(FUNCTIONS.iter().copied())
+            .chain(CONSTANTS.iter().map(|(c, _)| *c))
+            .filter(|w| w.starts_with(word))
+            .map(same)
+            .collect();
+        candidates.extend(
+            unit_symbols()
+                .iter()
+                .filter(|(symbol, name)| {
+                    symbol.starts_with(word) || name.to_lowercase().starts_with(&lower)
+                })
+                .map(|(symbol, name)| Completion {
+                    replacement: symbol.clone(),
+                    display: format!("{} ({})", symbol, name),
+                }),
+        );
+        candidates.sort();
+        candidates.dedup_by(|a, b| a.replacement == b.replacement);
+        (start, candidates)
+    }
+}
+
+// the catalog units whose symbol parses back to them, for completion
+fn unit_symbols() -> &'static [(String, String)] {
+    static SYMBOLS: OnceLock<Vec<(String, String)>> = OnceLock::new();
+    SYMBOLS.get_or_init(|| {
+        catalog()
+            .iter()
+            .filter(|u| !u.display_symbol().contains(char::is_whitespace))
+            .filter(|u| {
+                DerivedUnit::parse(u.display_symbol())
+                    .is_ok_and(|d| matches!(d.factors.as_slice(), [(v, 1)] if v.iri == u.iri))
+            })
+            .map(|u| (u.display_symbol().to_string(), u.name.clone()))
+            .collect()
+    })
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '°' || c == '%'
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| !c.is_ascii_digit()) && text.chars().all(is_identifier_char)
+}
+
+// `expression in unit`: the last top-level ` in ` word
+fn split_unit(expression: &str) -> (&str, Option<&str>) {
+    let mut depth = 0;
+    let mut split = None;
+    for (i, c) in expression.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ' ' if depth == 0 && expression[i..].starts_with(" in ") => split = Some(i),
+            _ => {}
+        }
+    }
+    match split {
+        Some(i) => (&expression[..i], Some(expression[i + 4..].trim())),
+        None => (expression, None),
+    }
+}
+
+// uncertain := sum (`±` sum)?
+// sum       := product ((`+` | `-`) product)*
+// product   := unary ((`*` | `·` | `.` | `/` | juxtaposition) unary)*
+// unary     := (`-` | `+`) unary | power
+// power     := primary (`^` unary)?
+// primary   := number unit-with-offset? | `(` uncertain `)` | function `(` uncertain `)` | identifier
+struct Parser<'a> {
+    text: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    session: &'a mut Session,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, session: &'a mut Session) -> Self {
+        Parser {
+            text,
+            chars: text.char_indices().collect(),
+            pos: 0,
+            session,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(_, c)| *c)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map_or(self.text.len(), |(i, _)| *i)
+    }
+
+    fn rest(&self) -> &str {
+        &self.text[self.offset()..]
+    }
+
+    fn error(&self, message: &str) -> UnitError {
+        UnitError::InvalidExpression {
+            expression: self.text.to_string(),
+            position: self.offset(),
+            message: message.to_string(),
+        }
+    }
+
+    fn advance(&mut self, text: &str) {
+        self.pos += text.chars().count();
+    }
+
+    fn uncertain(&mut self) -> Result<Value, UnitError> {
+        let mut value = self.sum()?;
+        self.skip_spaces();
+        let Some(symbol) = ["±", "+/-", "+-"]
+            .into_iter()
+            .find(|s| self.rest().starts_with(s))
+        else {
+            return Ok(value);
+        };
+        self.advance(symbol);
+        let uncertainty = self.sum()?;
+        // `2.5 ± 0.1 m`: the unit of the uncertainty applies to both
+        if value.dimension.is_dimensionless()
+            && value.unit.is_none()
+            && let Some(unit) = uncertainty
+                .unit
+                .clone()
+                .filter(|_| !uncertainty.dimension.is_dimensionless())
+        {
+            let contributions = value.contributions;
+            value = match has_offset(&unit) {
+                true => Value::reading(value.value, unit)?,
+                false => Value {
+                    value: unit.to_unit().to_si(value.value)?,
+                    dimension: uncertainty.dimension.clone(),
+                    unit: Some(unit),
+                    affine: Affine::Interval,
+                    contributions: BTreeMap::new(),
+                },
+            };
+            value.contributions = contributions;
+        }
+        value.check_same_dimension(&uncertainty)?;
+        // the uncertainty is a difference, without the offset of °C
+        let offset = uncertainty
+            .unit
+            .as_ref()
+            .map_or(0.0, |u| u.to_unit().conversion_offset);
+        self.session.inputs += 1;
+        value
+            .contributions
+            .insert(self.session.inputs, (uncertainty.value - offset).abs());
+        Ok(value)
+    }
+
+    fn sum(&mut self) -> Result<Value, UnitError> {
+        let mut value = self.product()?;
+        loop {
+            self.skip_spaces();
+            let rest = self.rest();
+            let sign = match self.peek() {
+                _ if rest.starts_with("+/-") || rest.starts_with("+-") => return Ok(value),
+                Some('+') => 1.0,
+                Some('-') => -1.0,
+                _ => return Ok(value),
+            };
+            self.pos += 1;
+            let other = self.product()?;
+            value = value.add(&other, sign)?;
+        }
+    }
+
+    fn product(&mut self) -> Result<Value, UnitError> {
+        let mut value = self.unary()?;
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('*' | '·') => {
+                    self.pos += 1;
+                    value = value.mul(&self.unary()?);
+                }
+                Some('.')
+                    if !self
+                        .chars
+                        .get(self.pos + 1)
+                        .is_some_and(|(_, c)| c.is_ascii_digit()) =>
+                {
+                    self.pos += 1;
+                    value = value.mul(&self.unary()?);
+                }
+                Some('/') if !self.rest().starts_with("/-") => {
+                    self.pos += 1;
+                    value = value.div(&self.unary()?);
+                }
+                Some('(') => value = value.mul(&self.unary()?),
+                Some(c) if is_identifier_char(c) && !c.is_ascii_digit() && !self.at_keyword() => {
+                    value = value.mul(&self.unary()?)
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn at_keyword(&self) -> bool {
+        let rest = self.rest();
+        rest == "in" || rest.starts_with("in ")
+    }
+
+    fn unary(&mut self) -> Result<Value, UnitError> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                let value = self.unary()?;
+                let mut result = value.map(-value.value, -1.0, value.dimension.clone());
+                result.unit = value.unit.filter(|u| !has_offset(u));
+                Ok(result)
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Value, UnitError> {
+        let base = self.primary()?;
+        self.skip_spaces();
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return base.pow(&exponent);
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Value, UnitError> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let value = self.uncertain()?;
+                self.skip_spaces();
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected `)`"));
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if is_identifier_char(c) => {
+                let name = self.identifier();
+                self.skip_spaces();
+                if FUNCTIONS.contains(&name.as_str()) && self.peek() == Some('(') {
+                    let argument = self.primary()?;
+                    return argument.function(&name);
+                }
+                self.resolve(&name)
+            }
+            _ => Err(self.error("expected a number, a variable or a unit")),
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(is_identifier_char) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().map(|(_, c)| c).collect()
+    }
+
+    // a number, followed by a unit with an offset such as °C for a reading
+    fn number(&mut self) -> Result<Value, UnitError> {
+        let start = self.pos;
+        let mut previous = ' ';
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+') && (previous == 'e' || previous == 'E');
+            let exponent = (c == 'e' || c == 'E')
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|(_, d)| d.is_ascii_digit() || *d == '-' || *d == '+');
+            if !(c.is_ascii_digit() || c == '.' || exponent || exponent_sign) {
+                break;
+            }
+            previous = c;
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().map(|(_, c)| c).collect();
+        let number: f64 = text.parse().map_err(|_| self.error("invalid number"))?;
+
+        let save = self.pos;
+        self.skip_spaces();
+        if self.peek().is_some_and(is_identifier_char) && !self.at_keyword() {
+            let name = self.identifier();
+            if self.session.get(&name).is_none()
+                && let Ok(unit) = DerivedUnit::parse(&name)
+                && unit.to_unit().conversion_offset != 0.0
+            {
+                return Value::reading(number, unit);
+            }
+        }
+        self.pos = save;
+        Ok(Value::exact(number, DIMENSIONLESS))
+    }
+
+    // a variable, a constant or a unit
+    fn resolve(&self, name: &str) -> Result<Value, UnitError> {
+        if let Some(value) = self.session.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some((_, value)) = CONSTANTS.iter().find(|(c, _)| *c == name) {
+            return Ok(Value::exact(*value, DIMENSIONLESS));
+        }
+        let unit = DerivedUnit::parse(name)?;
+        let multiplier = unit
+            .conversion_multiplier()
+            .ok_or_else(|| UnitError::MissingConversion(name.to_string()))?;
+        let dimension = unit
+            .dimension()
+            .ok_or_else(|| UnitError::UnknownUnit(name.to_string()))?;
+        let mut value = Value::exact(multiplier, dimension);
+        value.unit = Some(unit);
+        Ok(value)
+    }
+}
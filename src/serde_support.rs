@@ -0,0 +1,263 @@
+//! `serde` implementations, enabled by the `serde` feature.
+//!
+//! - `Dimension` is a map of the nonzero exponents keyed by the labels of
+//!   `DIMENSIONAL_LABELS`, plus `kind` when tagged: `{"L": 1, "T": -2}`;
+//!   rational exponents are strings such as `"1/2"`. A dimension string
+//!   such as `"LT^-2"` is also accepted on input, see `Dimension::parse`
+//! - `Matrix<T>` is a list of rows
+//! - `DimensionalAnalysis` has the shape of `NamedAnalysis`, the variables
+//!   being named `y`, `x1`, `x2`, ...
+//! - `DimensionalAnalysisSolution` is tagged by `type`
+use crate::dimensional::{DIMENSIONAL_LABELS, Dimension};
+use crate::dimensional_analysis::{
+    DimensionalAnalysis, DimensionalAnalysisSolution, NamedAnalysis,
+};
+use crate::matrix::Matrix;
+use num::traits::NumAssign;
+use num::{One, Rational32, Signed, Zero};
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A rational exponent: an integer when possible, `"p/q"` otherwise
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Exponent(Rational32);
+
+impl Serialize for Exponent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.is_integer() {
+            true => serializer.serialize_i32(*self.0.numer()),
+            false => serializer.serialize_str(&format!("{}/{}", self.0.numer(), self.0.denom())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Exponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ExponentVisitor)
+    }
+}
+
+struct ExponentVisitor;
+
+impl Visitor<'_> for ExponentVisitor {
+    type Value = Exponent;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an integer or a fraction such as \"1/2\"")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Exponent, E> {
+        i32::try_from(v)
+            .map(|v| Exponent(Rational32::from_integer(v)))
+            .map_err(|_| E::custom(format!("exponent {} out of range", v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Exponent, E> {
+        i64::try_from(v)
+            .map_err(|_| E::custom(format!("exponent {} out of range", v)))
+            .and_then(|v| self.visit_i64(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Exponent, E> {
+        let invalid = || E::custom(format!("invalid exponent '{}'", v));
+        let parse = |s: &str| s.trim().parse::<i32>().map_err(|_| invalid());
+        match v.split_once('/') {
+            Some((numer, denom)) => {
+                let denom = parse(denom)?;
+                if denom == 0 {
+                    return Err(invalid());
+                }
+                Ok(Exponent(Rational32::new(parse(numer)?, denom)))
+            }
+            None => Ok(Exponent(Rational32::from_integer(parse(v)?))),
+        }
+    }
+}
+
+impl Serialize for Dimension {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let exponents = self.get_exponents();
+        let mut map = serializer.serialize_map(None)?;
+        for (label, exponent) in DIMENSIONAL_LABELS.iter().zip(exponents) {
+            if !exponent.is_zero() {
+                map.serialize_entry(label, &Exponent(exponent))?;
+            }
+        }
+        if let Some(kind) = self.kind() {
+            map.serialize_entry("kind", kind)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Dimension {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DimensionVisitor)
+    }
+}
+
+struct DimensionVisitor;
+
+impl<'de> Visitor<'de> for DimensionVisitor {
+    type Value = Dimension;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a map of exponents such as {{\"L\": 1, \"T\": -2}} or a string such as \"LT^-2\""
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Dimension, E> {
+        Dimension::parse(v).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Dimension, A::Error> {
+        let mut exponents = [Rational32::zero(); 7];
+        let mut kind = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "kind" {
+                kind = Some(map.next_value::<String>()?);
+                continue;
+            }
+            let label = if key == "Theta" { "Θ" } else { key.as_str() };
+            let index = DIMENSIONAL_LABELS
+                .iter()
+                .position(|l| *l == label)
+                .ok_or_else(|| de::Error::unknown_field(&key, &DIMENSIONAL_LABELS))?;
+            exponents[index] = map.next_value::<Exponent>()?.0;
+        }
+        let dimension = match exponents.iter().all(|e| e.is_integer()) {
+            true => Dimension::PowerLawIA(exponents.map(|e| *e.numer())),
+            false => Dimension::PowerLawRA(exponents),
+        };
+        Ok(match kind {
+            Some(kind) => dimension.with_kind(&kind),
+            None => dimension,
+        })
+    }
+}
+
+impl<T> Serialize for Matrix<T>
+where
+    T: Clone + Zero + One + Signed + NumAssign + fmt::Debug + PartialOrd + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (rows, cols) = self.dimensions();
+        let rows: Vec<Vec<&T>> = (0..rows)
+            .map(|i| (0..cols).map(|j| &self[(i, j)]).collect())
+            .collect();
+        rows.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Matrix<T>
+where
+    T: Clone + Zero + One + Signed + NumAssign + fmt::Debug + PartialOrd + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        if let Some(row) = rows.iter().find(|row| row.len() != rows[0].len()) {
+            return Err(de::Error::custom(format!(
+                "rows of different lengths {} and {}",
+                rows[0].len(),
+                row.len()
+            )));
+        }
+        Ok(Matrix::from_rows(rows))
+    }
+}
+
+impl Serialize for DimensionalAnalysis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NamedAnalysis::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DimensionalAnalysis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(NamedAnalysis::deserialize(deserializer)?.analysis())
+    }
+}
+
+// the serialized form of `DimensionalAnalysisSolution`, with the rational
+// matrices as rows of `Exponent`
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SolutionRepr {
+    NoSolution,
+    UniqueSolution {
+        exponents: Vec<Vec<Exponent>>,
+    },
+    MultipleSolutions {
+        rank: usize,
+        n: usize,
+        reduced_a: Vec<Vec<Exponent>>,
+        reduced_b: Vec<Vec<Exponent>>,
+    },
+}
+
+fn to_rows(matrix: &Matrix<Rational32>) -> Vec<Vec<Exponent>> {
+    let (rows, cols) = matrix.dimensions();
+    (0..rows)
+        .map(|i| (0..cols).map(|j| Exponent(matrix[(i, j)])).collect())
+        .collect()
+}
+
+fn from_rows<E: de::Error>(rows: Vec<Vec<Exponent>>) -> Result<Matrix<Rational32>, E> {
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err(E::custom("rows of different lengths"));
+    }
+    Ok(Matrix::from_rows(
+        rows.into_iter()
+            .map(|row| row.into_iter().map(|e| e.0).collect())
+            .collect(),
+    ))
+}
+
+impl Serialize for DimensionalAnalysisSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            DimensionalAnalysisSolution::NoSolution => SolutionRepr::NoSolution,
+            DimensionalAnalysisSolution::UniqueSolution(x) => SolutionRepr::UniqueSolution {
+                exponents: to_rows(x),
+            },
+            DimensionalAnalysisSolution::MultipleSolutions {
+                rank,
+                n,
+                reduced_a,
+                reduced_b,
+            } => SolutionRepr::MultipleSolutions {
+                rank: *rank,
+                n: *n,
+                reduced_a: to_rows(reduced_a),
+                reduced_b: to_rows(reduced_b),
+            },
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DimensionalAnalysisSolution {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SolutionRepr::deserialize(deserializer)? {
+            SolutionRepr::NoSolution => DimensionalAnalysisSolution::NoSolution,
+            SolutionRepr::UniqueSolution { exponents } => {
+                DimensionalAnalysisSolution::UniqueSolution(from_rows(exponents)?)
+            }
+            SolutionRepr::MultipleSolutions {
+                rank,
+                n,
+                reduced_a,
+                reduced_b,
+            } => DimensionalAnalysisSolution::MultipleSolutions {
+                rank,
+                n,
+                reduced_a: from_rows(reduced_a)?,
+                reduced_b: from_rows(reduced_b)?,
+            },
+        })
+    }
+}
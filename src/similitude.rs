@@ -0,0 +1,378 @@
+#![doc = include_str!("../doc/similitude.md")]
+use crate::dimensional_analysis::{NamedAnalysis, PiGroup, Variable};
+use crate::expression::DerivedUnit;
+use crate::format::round_significant;
+use crate::matrix::Matrix;
+use crate::units::UnitError;
+use num::{Rational32, Zero};
+use std::fmt;
+
+/// Relative tolerance under which a Pi group counts as matched
+const TOLERANCE: f64 = 1e-9;
+
+/// A model test planned from a prototype: the chosen scale factors
+/// (model value / prototype value) of some variables and the prototype
+/// values, see `Similitude::solve`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Similitude {
+    analysis: NamedAnalysis,
+    scales: Vec<Option<f64>>,
+    prototype: Vec<Option<(f64, DerivedUnit)>>,
+    priority: Vec<usize>,
+}
+
+/// Errors of planning a model test
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimilitudeError {
+    Unit(UnitError),
+    /// A name that is not a variable of the analysis
+    UnknownVariable(String),
+    /// A prototype value whose unit does not have the dimension of the variable
+    WrongDimension {
+        name: String,
+        unit: String,
+        dimension: String,
+    },
+    /// A scale factor that is not a positive number
+    InvalidScale {
+        name: String,
+        scale: f64,
+    },
+    /// A group index beyond the Pi groups of the analysis
+    UnknownGroup(usize),
+    /// Variables whose scale the chosen scales and the groups leave open
+    Underdetermined(Vec<String>),
+}
+
+impl fmt::Display for SimilitudeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimilitudeError::Unit(error) => write!(f, "{}", error),
+            SimilitudeError::UnknownVariable(name) => write!(f, "unknown variable `{}`", name),
+            SimilitudeError::WrongDimension {
+                name,
+                unit,
+                dimension,
+            } => {
+                write!(
+                    f,
+                    "`{}` in `{}` does not have the dimension {}",
+                    name, unit, dimension
+                )
+            }
+            SimilitudeError::InvalidScale { name, scale } => {
+                write!(f, "scale {} of `{}` is not a positive number", scale, name)
+            }
+            SimilitudeError::UnknownGroup(index) => {
+                write!(f, "there is no Pi group Π{}", index + 1)
+            }
+            SimilitudeError::Underdetermined(names) => {
+                write!(f, "choose the scale of one more of {}", names.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimilitudeError {}
+
+impl From<UnitError> for SimilitudeError {
+    fn from(error: UnitError) -> Self {
+        SimilitudeError::Unit(error)
+    }
+}
+
+/// A variable of the model test
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaledVariable {
+    pub name: String,
+    /// Model value / prototype value
+    pub scale: f64,
+    /// Whether the scale was chosen rather than derived from the groups
+    pub chosen: bool,
+    /// The prototype and model values in the unit of the prototype value
+    pub prototype: Option<f64>,
+    pub model: Option<f64>,
+    pub unit: Option<DerivedUnit>,
+}
+
+/// How well the model reproduces a Pi group of the prototype
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupMatch {
+    pub group: PiGroup,
+    /// Π(model) / Π(prototype), 1 for a matched group
+    pub ratio: f64,
+    pub matched: bool,
+}
+
+impl GroupMatch {
+    /// The relative deviation `ratio − 1`
+    pub fn deviation(&self) -> f64 {
+        self.ratio - 1.0
+    }
+}
+
+/// The model test: the scale and the values of every variable, and the
+/// groups that the model does or does not reproduce
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalingReport {
+    pub variables: Vec<ScaledVariable>,
+    /// The Pi groups of the analysis, in their original order
+    pub groups: Vec<GroupMatch>,
+}
+
+impl ScalingReport {
+    /// Whether every Pi group is matched: complete similarity
+    pub fn is_similar(&self) -> bool {
+        self.groups.iter().all(|g| g.matched)
+    }
+
+    pub fn violated(&self) -> Vec<&GroupMatch> {
+        self.groups.iter().filter(|g| !g.matched).collect()
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&ScaledVariable> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+}
+
+impl fmt::Display for ScalingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = |v: f64| round_significant(v, 6).to_string();
+        let names: Vec<&str> = self.variables.iter().map(|v| v.name.as_str()).collect();
+        for v in &self.variables {
+            let unit = v
+                .unit
+                .as_ref()
+                .map_or(String::new(), |u| format!(" {}", u.symbol()));
+            let values = match (v.prototype, v.model) {
+                (Some(p), Some(m)) => format!(": {}{} -> {}{}", number(p), unit, number(m), unit),
+                _ => String::new(),
+            };
+            let chosen = if v.chosen { " (chosen)" } else { "" };
+            writeln!(f, "{} × {}{}{}", v.name, number(v.scale), chosen, values)?;
+        }
+        for (i, g) in self.groups.iter().enumerate() {
+            let state = match g.matched {
+                true => "matched".to_string(),
+                false => format!("violated, model/prototype = {}", number(g.ratio)),
+            };
+            write!(f, "\nΠ{} = {}: {}", i + 1, g.group.format(&names), state)?;
+        }
+        Ok(())
+    }
+}
+
+impl Similitude {
+    pub fn new(analysis: &NamedAnalysis) -> Self {
+        let n = analysis.dependencies.len() + 1;
+        Similitude {
+            analysis: analysis.clone(),
+            scales: vec![None; n],
+            prototype: vec![None; n],
+            priority: Vec::new(),
+        }
+    }
+
+    /// Choose the scale factor (model / prototype) of a variable, e.g.
+    /// `1/20` for the lengths of a 1:20 model or 1 for the same fluid
+    pub fn set_scale(&mut self, name: &str, scale: f64) -> Result<(), SimilitudeError> {
+        let index = self.index(name)?;
+        if !(scale > 0.0 && scale.is_finite()) {
+            return Err(SimilitudeError::InvalidScale {
+                name: name.to_string(),
+                scale,
+            });
+        }
+        self.scales[index] = Some(scale);
+        Ok(())
+    }
+
+    /// Give the value of a variable for the prototype in a unit expression
+    pub fn set_prototype(
+        &mut self,
+        name: &str,
+        value: f64,
+        unit: &str,
+    ) -> Result<(), SimilitudeError> {
+        let index = self.index(name)?;
+        let unit = DerivedUnit::parse(unit)?;
+        let dimension = &self.variables()[index].dimension;
+        if unit.dimension().as_ref() != Some(dimension) {
+            return Err(SimilitudeError::WrongDimension {
+                name: name.to_string(),
+                unit: unit.symbol(),
+                dimension: dimension.to_string(),
+            });
+        }
+        self.prototype[index] = Some((value, unit));
+        Ok(())
+    }
+
+    /// Match the Pi group at `index` (0 for Π1) before the groups that
+    /// are not preferred, when not all of them can be matched; called
+    /// several times, the first call has the highest priority
+    pub fn prefer(&mut self, index: usize) -> Result<(), SimilitudeError> {
+        if index >= self.analysis.pi_groups().len() {
+            return Err(SimilitudeError::UnknownGroup(index));
+        }
+        if !self.priority.contains(&index) {
+            self.priority.push(index);
+        }
+        Ok(())
+    }
+
+    /// Derive the scales that are not chosen so that the Pi groups of the
+    /// model equal those of the prototype. The groups are taken in order
+    /// of priority; a group that contradicts the chosen scales and the
+    /// groups already matched is reported as violated, with the ratio of
+    /// its model and prototype values.
+    pub fn solve(&self) -> Result<ScalingReport, SimilitudeError> {
+        let groups = self.analysis.pi_groups();
+        let free: Vec<usize> = (0..self.scales.len())
+            .filter(|&i| self.scales[i].is_none())
+            .collect();
+        // each group gives Σ_free eᵢ·ln sᵢ = −Σ_chosen eᵢ·ln sᵢ
+        let equation = |group: &PiGroup| {
+            let row: Vec<Rational32> = free
+                .iter()
+                .map(|&i| Rational32::from_integer(group.exponents[i]))
+                .collect();
+            let rhs: f64 = -group
+                .exponents
+                .iter()
+                .zip(&self.scales)
+                .filter_map(|(e, s)| s.map(|s| *e as f64 * s.ln()))
+                .sum::<f64>();
+            (row, rhs)
+        };
+
+        let mut order = self.priority.clone();
+        order.extend((0..groups.len()).filter(|i| !self.priority.contains(i)));
+        let mut kept: Vec<(Vec<Rational32>, f64)> = Vec::new();
+        let mut matched = vec![false; groups.len()];
+        for &k in &order {
+            let (row, rhs) = equation(&groups[k]);
+            match combination(&kept, &row) {
+                None => {
+                    kept.push((row, rhs));
+                    matched[k] = true;
+                }
+                Some(c) => {
+                    let implied: f64 = c.iter().zip(&kept).map(|(c, (_, b))| to_f64(*c) * b).sum();
+                    matched[k] = (rhs - implied).abs() <= TOLERANCE * rhs.abs().max(1.0);
+                }
+            }
+        }
+
+        // the free scales are determined when the kept rows span them all
+        if kept.len() < free.len() {
+            let open: Vec<String> = free
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| {
+                    let mut unit = vec![Rational32::zero(); free.len()];
+                    unit[*j] = Rational32::from_integer(1);
+                    combination(&kept, &unit).is_none()
+                })
+                .map(|(_, &i)| self.variables()[i].name.clone())
+                .collect();
+            return Err(SimilitudeError::Underdetermined(open));
+        }
+        let mut system = Matrix::new(free.len(), free.len() + 1);
+        for (i, (row, rhs)) in kept.iter().enumerate() {
+            for (j, e) in row.iter().enumerate() {
+                system[(i, j)] = to_f64(*e);
+            }
+            system[(i, free.len())] = *rhs;
+        }
+        system.to_row_echelon_form();
+        let mut log_scales: Vec<f64> = self.scales.iter().map(|s| s.map_or(0.0, f64::ln)).collect();
+        for (j, &i) in free.iter().enumerate() {
+            log_scales[i] = system[(j, free.len())];
+        }
+
+        let mut variables = Vec::new();
+        for (i, variable) in self.variables().iter().enumerate() {
+            let scale = log_scales[i].exp();
+            let (prototype, model, unit) = match &self.prototype[i] {
+                Some((value, unit)) => {
+                    let si = unit.to_unit().to_si(*value)?;
+                    let model = unit.to_unit().from_si(si * scale)?;
+                    (Some(*value), Some(model), Some(unit.clone()))
+                }
+                None => (None, None, None),
+            };
+            variables.push(ScaledVariable {
+                name: variable.name.clone(),
+                scale,
+                chosen: self.scales[i].is_some(),
+                prototype,
+                model,
+                unit,
+            });
+        }
+        let groups = groups
+            .into_iter()
+            .zip(matched)
+            .map(|(group, matched)| {
+                let log_ratio: f64 = group
+                    .exponents
+                    .iter()
+                    .zip(&log_scales)
+                    .map(|(e, s)| *e as f64 * s)
+                    .sum();
+                GroupMatch {
+                    ratio: if matched { 1.0 } else { log_ratio.exp() },
+                    group,
+                    matched,
+                }
+            })
+            .collect();
+        Ok(ScalingReport { variables, groups })
+    }
+
+    fn variables(&self) -> Vec<&Variable> {
+        std::iter::once(&self.analysis.target)
+            .chain(&self.analysis.dependencies)
+            .collect()
+    }
+
+    fn index(&self, name: &str) -> Result<usize, SimilitudeError> {
+        self.analysis
+            .names()
+            .iter()
+            .position(|n| *n == name)
+            .ok_or_else(|| SimilitudeError::UnknownVariable(name.to_string()))
+    }
+}
+
+// the coefficients `c` with `Σ cⱼ·keptⱼ = row`, or `None` when `row` is
+// independent of the kept rows (which are independent themselves)
+fn combination(kept: &[(Vec<Rational32>, f64)], row: &[Rational32]) -> Option<Vec<Rational32>> {
+    if row.iter().all(|e| e.is_zero()) {
+        return Some(vec![Rational32::zero(); kept.len()]);
+    }
+    if kept.is_empty() {
+        return None;
+    }
+    // the columns are the kept rows, the last column is `row`
+    let mut columns: Vec<Vec<Rational32>> = kept.iter().map(|(r, _)| r.clone()).collect();
+    columns.push(row.to_vec());
+    let mut augmented = Matrix::from_cols(columns);
+    if augmented
+        .to_row_echelon_form()
+        .pivot_row(kept.len())
+        .is_some()
+    {
+        return None;
+    }
+    Some(
+        (0..kept.len())
+            .map(|j| augmented[(j, kept.len())])
+            .collect(),
+    )
+}
+
+fn to_f64(value: Rational32) -> f64 {
+    *value.numer() as f64 / *value.denom() as f64
+}
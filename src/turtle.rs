@@ -0,0 +1,435 @@
+#![doc = include_str!("../doc/turtle.md")]
+use crate::rdf::{RDF_TYPE, RdfError, Term, Triple, resolve_iri};
+use std::collections::HashMap;
+
+const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Parse a Turtle document into triples
+pub fn parse_turtle(src: &str) -> Result<Vec<Triple>, RdfError> {
+    let mut parser = TurtleParser {
+        chars: src.chars().collect(),
+        pos: 0,
+        line: 1,
+        base: String::new(),
+        prefixes: HashMap::new(),
+        blank_nodes: 0,
+        triples: Vec::new(),
+    };
+    loop {
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Ok(parser.triples);
+        }
+        parser.statement()?;
+    }
+}
+
+struct TurtleParser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    base: String,
+    prefixes: HashMap<String, String>,
+    blank_nodes: usize,
+    triples: Vec<Triple>,
+}
+
+impl TurtleParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> RdfError {
+        RdfError {
+            line: self.line,
+            message: message.to_string(),
+        }
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn starts_with_keyword(&self, keyword: &str) -> bool {
+        keyword
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i).is_some_and(|d| d.eq_ignore_ascii_case(&c)))
+            && self
+                .peek_at(keyword.chars().count())
+                .is_some_and(|c| c.is_whitespace() || c == '<')
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), RdfError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.next();
+            } else if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.next();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn statement(&mut self) -> Result<(), RdfError> {
+        if self.starts_with("@prefix") {
+            self.pos += "@prefix".len();
+            self.prefix_directive()?;
+            return self.expect('.');
+        }
+        if self.starts_with("@base") {
+            self.pos += "@base".len();
+            self.skip_whitespace();
+            self.base = self.iri_ref()?;
+            return self.expect('.');
+        }
+        // SPARQL-style directives have no trailing `.`
+        if self.starts_with_keyword("PREFIX") {
+            self.pos += "PREFIX".len();
+            return self.prefix_directive();
+        }
+        if self.starts_with_keyword("BASE") {
+            self.pos += "BASE".len();
+            self.skip_whitespace();
+            self.base = self.iri_ref()?;
+            return Ok(());
+        }
+
+        if self.peek() == Some('[') {
+            let subject = self.blank_node_property_list()?;
+            self.skip_whitespace();
+            if self.peek() != Some('.') {
+                self.predicate_object_list(&subject)?;
+            }
+        } else {
+            let subject = self.subject()?;
+            self.predicate_object_list(&subject)?;
+        }
+        self.expect('.')
+    }
+
+    fn prefix_directive(&mut self) -> Result<(), RdfError> {
+        self.skip_whitespace();
+        let mut prefix = String::new();
+        while let Some(c) = self.peek() {
+            if c == ':' {
+                break;
+            }
+            if c.is_whitespace() {
+                return Err(self.error("expected `:` after the prefix name"));
+            }
+            prefix.push(c);
+            self.next();
+        }
+        self.expect(':')?;
+        self.skip_whitespace();
+        let iri = self.iri_ref()?;
+        self.prefixes.insert(prefix, iri);
+        Ok(())
+    }
+
+    fn subject(&mut self) -> Result<Term, RdfError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('_') if self.peek_at(1) == Some(':') => self.blank_node_label(),
+            Some('(') => Err(self.error("RDF collections are not supported")),
+            _ => Ok(Term::Iri(self.iri()?)),
+        }
+    }
+
+    fn predicate_object_list(&mut self, subject: &Term) -> Result<(), RdfError> {
+        loop {
+            self.skip_whitespace();
+            let predicate = if self.peek() == Some('a')
+                && self
+                    .peek_at(1)
+                    .is_some_and(|c| c.is_whitespace() || c == '<' || c == '[' || c == '"')
+            {
+                self.next();
+                RDF_TYPE.to_string()
+            } else {
+                self.iri()?
+            };
+            loop {
+                let object = self.object()?;
+                self.triples.push(Triple {
+                    subject: subject.clone(),
+                    predicate: predicate.clone(),
+                    object,
+                });
+                self.skip_whitespace();
+                if self.peek() == Some(',') {
+                    self.next();
+                } else {
+                    break;
+                }
+            }
+            // `;` may repeat and may end the list
+            let mut separated = false;
+            while self.peek() == Some(';') {
+                self.next();
+                self.skip_whitespace();
+                separated = true;
+            }
+            if !separated || matches!(self.peek(), Some('.' | ']') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Term, RdfError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => self.literal(),
+            Some('[') => self.blank_node_property_list(),
+            Some('_') if self.peek_at(1) == Some(':') => self.blank_node_label(),
+            Some('(') => Err(self.error("RDF collections are not supported")),
+            Some(c) if c.is_ascii_digit() || c == '+' || c == '-' => self.numeric_literal(),
+            Some('.') if self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) => {
+                self.numeric_literal()
+            }
+            _ if self.starts_with("true") || self.starts_with("false") => {
+                let value = if self.starts_with("true") {
+                    "true"
+                } else {
+                    "false"
+                };
+                self.pos += value.len();
+                Ok(typed_literal(value.to_string(), "boolean"))
+            }
+            _ => Ok(Term::Iri(self.iri()?)),
+        }
+    }
+
+    fn blank_node_label(&mut self) -> Result<Term, RdfError> {
+        self.pos += 2;
+        let label = self.name_chars();
+        if label.is_empty() {
+            return Err(self.error("empty blank node label"));
+        }
+        Ok(Term::BlankNode(label))
+    }
+
+    fn blank_node_property_list(&mut self) -> Result<Term, RdfError> {
+        self.expect('[')?;
+        self.blank_nodes += 1;
+        let node = Term::BlankNode(format!("b{}", self.blank_nodes));
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            self.predicate_object_list(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn iri(&mut self) -> Result<String, RdfError> {
+        self.skip_whitespace();
+        if self.peek() == Some('<') {
+            return self.iri_ref();
+        }
+        let name = self.name_chars();
+        let Some((prefix, local)) = name.split_once(':') else {
+            return Err(self.error(&format!("expected an IRI, found `{}`", name)));
+        };
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Ok(format!("{}{}", namespace, local)),
+            None => Err(self.error(&format!("undefined prefix `{}`", prefix))),
+        }
+    }
+
+    fn iri_ref(&mut self) -> Result<String, RdfError> {
+        if self.peek() != Some('<') {
+            return Err(self.error("expected `<`"));
+        }
+        self.next();
+        let mut iri = String::new();
+        loop {
+            match self.next() {
+                Some('>') => return Ok(resolve_iri(&self.base, &iri)),
+                Some('\\') => iri.push(self.unicode_escape()?),
+                Some(c) if c.is_whitespace() => return Err(self.error("whitespace in IRI")),
+                Some(c) => iri.push(c),
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+    }
+
+    // prefixed names and blank node labels; a trailing `.` ends the statement
+    fn name_chars(&mut self) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || "_-.:%".contains(c) || (!c.is_ascii() && !c.is_whitespace()) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        while self.pos > start && self.chars[self.pos - 1] == '.' {
+            self.pos -= 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn literal(&mut self) -> Result<Term, RdfError> {
+        let value = self.string()?;
+        if self.peek() == Some('@') {
+            self.next();
+            let mut lang = String::new();
+            while let Some(c) = self.peek() {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    lang.push(c);
+                    self.next();
+                } else {
+                    break;
+                }
+            }
+            return Ok(Term::Literal {
+                value,
+                datatype: None,
+                lang: Some(lang),
+            });
+        }
+        if self.starts_with("^^") {
+            self.pos += 2;
+            let datatype = self.iri()?;
+            return Ok(Term::Literal {
+                value,
+                datatype: Some(datatype),
+                lang: None,
+            });
+        }
+        Ok(Term::Literal {
+            value,
+            datatype: None,
+            lang: None,
+        })
+    }
+
+    fn string(&mut self) -> Result<String, RdfError> {
+        let quote = self.next().expect("string starts with a quote");
+        let long = self.peek() == Some(quote) && self.peek_at(1) == Some(quote);
+        if long {
+            self.pos += 2;
+        }
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => {
+                    if !long {
+                        return Ok(value);
+                    }
+                    if self.peek() == Some(quote) && self.peek_at(1) == Some(quote) {
+                        self.pos += 2;
+                        return Ok(value);
+                    }
+                    value.push(c);
+                }
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('t') => '\t',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some(c @ ('"' | '\'' | '\\')) => c,
+                        Some('u' | 'U') => {
+                            self.pos -= 1;
+                            self.unicode_escape()?
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    value.push(escaped);
+                }
+                Some('\n') if !long => return Err(self.error("newline in string")),
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    // `\uXXXX` or `\UXXXXXXXX`, after the backslash
+    fn unicode_escape(&mut self) -> Result<char, RdfError> {
+        let digits = match self.next() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("invalid escape sequence")),
+        };
+        let hex: String = (0..digits).filter_map(|_| self.next()).collect();
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn numeric_literal(&mut self) -> Result<Term, RdfError> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.pos += 1;
+        }
+        let digits = |p: &mut Self| {
+            let s = p.pos;
+            while p.peek().is_some_and(|c| c.is_ascii_digit()) {
+                p.pos += 1;
+            }
+            p.pos - s
+        };
+        let mut count = digits(self);
+        let mut datatype = "integer";
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+            count += digits(self);
+            datatype = "decimal";
+        }
+        if count == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return Err(self.error("invalid exponent"));
+            }
+            datatype = "double";
+        }
+        let value: String = self.chars[start..self.pos].iter().collect();
+        Ok(typed_literal(value, datatype))
+    }
+}
+
+fn typed_literal(value: String, datatype: &str) -> Term {
+    Term::Literal {
+        value,
+        datatype: Some(format!("{}{}", XSD_NS, datatype)),
+        lang: None,
+    }
+}
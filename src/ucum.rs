@@ -0,0 +1,441 @@
+#![doc = include_str!("../doc/ucum.md")]
+use crate::expression::DerivedUnit;
+use crate::prefix::{Prefix, PrefixKind, find_prefix, split_prefixed_name};
+use crate::units::{UNIT_NS, Unit, UnitError, catalog};
+
+/// UCUM prefix codes and the QUDT prefix they stand for
+const UCUM_PREFIXES: [(&str, &str); 24] = [
+    ("da", "Deca"),
+    ("Ki", "Kibi"),
+    ("Mi", "Mebi"),
+    ("Gi", "Gibi"),
+    ("Ti", "Tebi"),
+    ("Y", "Yotta"),
+    ("Z", "Zetta"),
+    ("E", "Exa"),
+    ("P", "Peta"),
+    ("T", "Tera"),
+    ("G", "Giga"),
+    ("M", "Mega"),
+    ("k", "Kilo"),
+    ("h", "Hecto"),
+    ("d", "Deci"),
+    ("c", "Centi"),
+    ("m", "Milli"),
+    ("u", "Micro"),
+    ("n", "Nano"),
+    ("p", "Pico"),
+    ("f", "Femto"),
+    ("a", "Atto"),
+    ("z", "Zepto"),
+    ("y", "Yocto"),
+];
+
+/// UCUM atoms (case sensitive) and the QUDT unit they denote; the first
+/// code of a unit is the one emitted
+pub const UCUM_ATOMS: &[(&str, &str)] = &[
+    ("m", "Meter"),
+    ("g", "Gram"),
+    ("s", "SecondTime"),
+    ("A", "Ampere"),
+    ("K", "Kelvin"),
+    ("mol", "Mole"),
+    ("cd", "Candela"),
+    ("rad", "Radian"),
+    ("sr", "Steradian"),
+    ("Hz", "Hertz"),
+    ("N", "Newton"),
+    ("Pa", "Pascal"),
+    ("J", "Joule"),
+    ("W", "Watt"),
+    ("C", "Coulomb"),
+    ("V", "Volt"),
+    ("F", "Farad"),
+    ("Ohm", "Ohm"),
+    ("S", "Siemens"),
+    ("Wb", "Weber"),
+    ("T", "Tesla"),
+    ("H", "Henry"),
+    ("Cel", "DegreeCelsius"),
+    ("lm", "Lumen"),
+    ("lx", "Lux"),
+    ("Bq", "Becquerel"),
+    ("Gy", "Gray"),
+    ("Sv", "Sievert"),
+    ("kat", "Katal"),
+    ("L", "Liter"),
+    ("l", "Liter"),
+    ("min", "MinuteTime"),
+    ("h", "Hour"),
+    ("d", "Day"),
+    ("t", "MetricTon"),
+    ("bar", "Bar"),
+    ("eV", "ElectronVolt"),
+    ("u", "AtomicMassUnit"),
+    ("deg", "DegreeAngle"),
+    ("'", "MinuteAngle"),
+    ("''", "SecondAngle"),
+    ("%", "Percent"),
+    ("bit", "Bit"),
+    ("By", "Byte"),
+    ("Ao", "Angstrom"),
+    ("atm", "AtmosphereStandard"),
+    ("cal", "CalorieThermochemical"),
+    ("cal_th", "CalorieThermochemical"),
+    ("dyn", "Dyne"),
+    ("erg", "Erg"),
+    ("P", "Poise"),
+    ("St", "Stokes"),
+    ("G", "Gauss"),
+    ("Oe", "Oersted"),
+    ("Mx", "Maxwell"),
+    ("Ci", "Curie"),
+    ("R", "Roentgen"),
+    ("[in_i'Hg]", "InchOfMercury"),
+    ("[in_i'H2O]", "InchOfWater"),
+    ("[in_i]", "Inch"),
+    ("[ft_i]", "Foot"),
+    ("[yd_i]", "Yard"),
+    ("[mi_i]", "MileInternational"),
+    ("[nmi_i]", "NauticalMile"),
+    ("[kn_i]", "Knot"),
+    ("[lb_av]", "PoundMass"),
+    ("[oz_av]", "OunceMass"),
+    ("[lbf_av]", "PoundForce"),
+    ("[psi]", "PoundForcePerSquareInch"),
+    ("[degF]", "DegreeFahrenheit"),
+    ("[degR]", "DegreeRankine"),
+    ("[gal_us]", "GallonUS"),
+    ("[gal_br]", "GallonImperial"),
+    ("[Btu_IT]", "BritishThermalUnitInternationalTable"),
+    ("[HP]", "Horsepower"),
+    ("[ly]", "LightYear"),
+];
+
+/// UCUM's metric atoms that QUDT only has with a prefix: the code, the name,
+/// label and symbol of the unit, and the QUDT unit it is `factor` times
+const METRIC_ATOMS: [(&str, &str, &str, &str, &str, f64); 2] = [
+    (
+        "m[Hg]",
+        "MeterOfMercury",
+        "Meter of Mercury",
+        "mHg",
+        "MillimeterOfMercury",
+        1e3,
+    ),
+    (
+        "m[H2O]",
+        "MeterOfWater",
+        "Meter of Water",
+        "mH2O",
+        "CentimeterOfWater",
+        1e2,
+    ),
+];
+
+/// Parse a UCUM code such as `kg.m/s2`, `mm[Hg]` or `10*3/uL`.
+///
+/// Annotations (`{cells}`) carry no meaning in UCUM and are dropped; an
+/// annotation on its own stands for the unit `1`.
+pub fn parse_ucum(code: &str) -> Result<DerivedUnit, UnitError> {
+    let mut parser = UcumParser {
+        code,
+        chars: code.chars().collect(),
+        pos: 0,
+    };
+    let unit = parser.term()?;
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected character"));
+    }
+    Ok(unit)
+}
+
+/// Write a unit as a UCUM code, e.g. `kg.m/s2` or `W/(m2.K)`
+pub fn format_ucum(unit: &DerivedUnit) -> Result<String, UnitError> {
+    let mut numerator: Vec<String> = Vec::new();
+    if unit.scale != 1.0 {
+        numerator.push(format_scale(unit.scale)?);
+    }
+    let mut denominator: Vec<String> = Vec::new();
+    for (factor, exponent) in &unit.factors {
+        let code = ucum_code(factor).ok_or_else(|| UnitError::NoCode {
+            unit: factor.name.clone(),
+            system: "UCUM".to_string(),
+        })?;
+        let power = |e: i32| {
+            if e == 1 {
+                code.clone()
+            } else {
+                format!("{}{}", code, e)
+            }
+        };
+        if *exponent > 0 {
+            numerator.push(power(*exponent));
+        } else {
+            denominator.push(power(-exponent));
+        }
+    }
+    Ok(match denominator.len() {
+        0 if numerator.is_empty() => "1".to_string(),
+        0 => numerator.join("."),
+        1 => format!("{}/{}", numerator.join("."), denominator[0]),
+        _ => format!("{}/({})", numerator.join("."), denominator.join(".")),
+    })
+}
+
+/// The UCUM code of a single unit, with a UCUM prefix where needed
+pub fn ucum_code(unit: &Unit) -> Option<String> {
+    let atom = |name: &str| UCUM_ATOMS.iter().find(|(_, n)| *n == name).map(|(c, _)| *c);
+    if let Some(code) = atom(&unit.name) {
+        return Some(code.to_string());
+    }
+    for (code, name, ..) in METRIC_ATOMS {
+        if unit.name == name {
+            return Some(code.to_string());
+        }
+        let prefix_code = UCUM_PREFIXES.iter().find_map(|(c, prefix)| {
+            let rest = unit.name.strip_prefix(prefix)?;
+            rest.eq_ignore_ascii_case(name).then_some(*c)
+        });
+        if let Some(prefix_code) = prefix_code {
+            return Some(format!("{}{}", prefix_code, code));
+        }
+    }
+    let (prefix, base) = split_prefixed_name(catalog(), &unit.name)?;
+    let prefix_code = UCUM_PREFIXES
+        .iter()
+        .find(|(_, name)| *name == prefix.name)
+        .map(|(c, _)| *c)?;
+    Some(format!("{}{}", prefix_code, atom(&base.name)?))
+}
+
+fn format_scale(scale: f64) -> Result<String, UnitError> {
+    let exponent = scale.log10().round() as i32;
+    if exponent != 0 && 10f64.powi(exponent) == scale {
+        Ok(format!("10*{}", exponent))
+    } else if scale.fract() == 0.0 && scale > 0.0 {
+        Ok(format!("{}", scale))
+    } else {
+        Err(UnitError::NoCode {
+            unit: format!("{}", scale),
+            system: "UCUM".to_string(),
+        })
+    }
+}
+
+fn resolve_atom(atom: &str) -> Result<Unit, UnitError> {
+    let units = catalog();
+    let lookup = |code: &str| {
+        UCUM_ATOMS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .and_then(|(_, name)| units.get(name))
+    };
+    if let Some(unit) = lookup(atom) {
+        return Ok(unit.clone());
+    }
+    if let Some(unit) = metric_atom(atom) {
+        return Ok(unit);
+    }
+    for (code, name) in UCUM_PREFIXES {
+        let Some(rest) = atom.strip_prefix(code) else {
+            continue;
+        };
+        let Some(prefix) = find_prefix(name) else {
+            continue;
+        };
+        if let Some(unit) = lookup(rest) {
+            return prefix.apply(unit);
+        }
+        if let Some(unit) = metric_atom(rest)
+            && prefix.kind == PrefixKind::Decimal
+        {
+            return Ok(metric_prefixed(prefix, &unit));
+        }
+    }
+    Err(UnitError::UnknownUnit(atom.to_string()))
+}
+
+// `m[Hg]` and `m[H2O]`, from the prefixed QUDT unit
+fn metric_atom(atom: &str) -> Option<Unit> {
+    let (_, name, label, symbol, qudt, factor) =
+        METRIC_ATOMS.into_iter().find(|(code, ..)| *code == atom)?;
+    let unit = catalog().get(qudt)?;
+    Some(Unit {
+        iri: format!("{}{}", UNIT_NS, name),
+        name: name.to_string(),
+        label: label.to_string(),
+        symbol: Some(symbol.to_string()),
+        abbreviation: Some(symbol.to_string()),
+        conversion_multiplier: unit.conversion_multiplier.map(|m| m * factor),
+        unece_common_code: None,
+        ..unit.clone()
+    })
+}
+
+// UCUM lets metric atoms take any prefix, unlike the SI rules of
+// `Prefix::apply`: `cm[Hg]` is synthesized, `mm[Hg]` is the QUDT unit
+fn metric_prefixed(prefix: &Prefix, unit: &Unit) -> Unit {
+    let name = format!("{}{}", prefix.name, unit.name);
+    if let Some(existing) = catalog()
+        .iter()
+        .find(|u| u.name.eq_ignore_ascii_case(&name))
+    {
+        return existing.clone();
+    }
+    let with_prefix =
+        |text: &Option<String>| text.as_ref().map(|s| format!("{}{}", prefix.symbol, s));
+    Unit {
+        iri: format!("{}{}", UNIT_NS, name),
+        label: format!("{}{}", prefix.name, unit.label.to_lowercase()),
+        name,
+        symbol: with_prefix(&unit.symbol),
+        abbreviation: with_prefix(&unit.abbreviation),
+        conversion_multiplier: unit.conversion_multiplier.map(|m| m * prefix.factor()),
+        ..unit.clone()
+    }
+}
+
+struct UcumParser<'a> {
+    code: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl UcumParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> UnitError {
+        UnitError::InvalidExpression {
+            expression: self.code.to_string(),
+            position: self.chars[..self.pos].iter().map(|c| c.len_utf8()).sum(),
+            message: message.to_string(),
+        }
+    }
+
+    // term := `/`? component ((`.` | `/`) component)*
+    fn term(&mut self) -> Result<DerivedUnit, UnitError> {
+        let mut result = if self.peek() == Some('/') {
+            self.pos += 1;
+            DerivedUnit::one() / self.component()?
+        } else {
+            self.component()?
+        };
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    result = result * self.component()?;
+                }
+                Some('/') => {
+                    self.pos += 1;
+                    result = result / self.component()?;
+                }
+                _ => return Ok(result),
+            }
+        }
+    }
+
+    // component := (`(` term `)` | simple-unit exponent? | annotation) annotation?
+    fn component(&mut self) -> Result<DerivedUnit, UnitError> {
+        let unit = match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.term()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected `)`"));
+                }
+                self.pos += 1;
+                inner
+            }
+            Some('{') => {
+                self.annotation()?;
+                return Ok(DerivedUnit::one());
+            }
+            Some(c) if c.is_ascii_digit() => self.number()?,
+            Some(c) if !"./)}".contains(c) => {
+                let atom = self.atom()?;
+                let unit = DerivedUnit::from(resolve_atom(&atom)?);
+                match self.exponent()? {
+                    Some(e) => unit.powi(e),
+                    None => unit,
+                }
+            }
+            _ => return Err(self.error("expected a unit")),
+        };
+        if self.peek() == Some('{') {
+            self.annotation()?;
+        }
+        Ok(unit)
+    }
+
+    fn annotation(&mut self) -> Result<(), UnitError> {
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == '}' {
+                return Ok(());
+            }
+        }
+        Err(self.error("unterminated annotation"))
+    }
+
+    // an integer factor, or `10*n` / `10^n`
+    fn number(&mut self) -> Result<DerivedUnit, UnitError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        let mut unit = DerivedUnit::one();
+        if digits == "10" && matches!(self.peek(), Some('*' | '^')) {
+            self.pos += 1;
+            let exponent = self
+                .exponent()?
+                .ok_or_else(|| self.error("expected an exponent"))?;
+            unit.scale = 10f64.powi(exponent);
+        } else {
+            unit.scale = digits
+                .parse::<f64>()
+                .map_err(|_| self.error("invalid number"))?;
+        }
+        Ok(unit)
+    }
+
+    fn atom(&mut self) -> Result<String, UnitError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '[' {
+                while self.peek().is_some_and(|c| c != ']') {
+                    self.pos += 1;
+                }
+                if self.peek().is_none() {
+                    return Err(self.error("unterminated `[`"));
+                }
+            } else if "./(){}+-".contains(c) || c.is_ascii_digit() {
+                break;
+            }
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn exponent(&mut self) -> Result<Option<i32>, UnitError> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| self.error("invalid exponent"))
+    }
+}
@@ -0,0 +1,150 @@
+#![doc = include_str!("../doc/unece.md")]
+use crate::expression::DerivedUnit;
+use crate::units::{Unit, UnitError, catalog};
+use std::sync::OnceLock;
+
+/// Common codes missing from QUDT 1.1, mapped to a QUDT unit name or,
+/// for units QUDT does not list, to a unit expression
+pub const SUPPLEMENTARY_CODES: &[(&str, &str)] = &[
+    ("KGM", "Kilogram"),
+    ("GRM", "Gram"),
+    ("MGM", "mg"),
+    ("MC", "µg"),
+    ("TNE", "MetricTon"),
+    ("LTR", "Liter"),
+    ("MLT", "mL"),
+    ("CLT", "cL"),
+    ("DLT", "dL"),
+    ("HLT", "hL"),
+    ("MTQ", "CubicMeter"),
+    ("CMQ", "CubicCentimeter"),
+    ("MTK", "SquareMeter"),
+    ("CMK", "SquareCentimeter"),
+    ("KMK", "km^2"),
+    ("HAR", "Hectare"),
+    ("ACR", "Acre"),
+    ("FTK", "SquareFoot"),
+    ("FTQ", "CubicFoot"),
+    ("KMT", "Kilometer"),
+    ("MMT", "Millimeter"),
+    ("INH", "Inch"),
+    ("FOT", "Foot"),
+    ("YRD", "Yard"),
+    ("SMI", "MileInternational"),
+    ("SEC", "SecondTime"),
+    ("C26", "MilliSecond"),
+    ("MIN", "MinuteTime"),
+    ("HUR", "Hour"),
+    ("DAY", "Day"),
+    ("ANN", "Year365Day"),
+    ("KEL", "Kelvin"),
+    ("CEL", "DegreeCelsius"),
+    ("FAH", "DegreeFahrenheit"),
+    ("NEW", "Newton"),
+    ("B47", "kN"),
+    ("PAL", "Pascal"),
+    ("KPA", "KiloPascal"),
+    ("BAR", "Bar"),
+    ("JOU", "Joule"),
+    ("KJO", "kJ"),
+    ("KWH", "kW·h"),
+    ("WTT", "Watt"),
+    ("KWT", "Kilowatt"),
+    ("AMP", "Ampere"),
+    ("VLT", "Volt"),
+    ("OHM", "Ohm"),
+    ("HTZ", "Hertz"),
+    ("KHZ", "KiloHertz"),
+    ("MHZ", "MegaHertz"),
+    ("MOL", "Mole"),
+    ("LBR", "PoundMass"),
+    ("ONZ", "OunceMass"),
+    ("GLL", "GallonUS"),
+    ("GLI", "GallonImperial"),
+    ("KMH", "KilometerPerHour"),
+    ("MTS", "MeterPerSecond"),
+    ("RPM", "RevolutionPerMinute"),
+    ("C62", "Unitless"),
+    ("P1", "Percent"),
+    ("AD", "Byte"),
+    ("A99", "Bit"),
+];
+
+/// Every (code, unit) pair: the QUDT `uneceCommonCode` values followed by
+/// the supplementary table
+fn code_table() -> &'static [(String, Unit)] {
+    static TABLE: OnceLock<Vec<(String, Unit)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let units = catalog();
+        let mut table: Vec<(String, Unit)> = units
+            .iter()
+            .filter_map(|u| Some((u.unece_common_code.clone()?, u.clone())))
+            .collect();
+        for (code, unit) in SUPPLEMENTARY_CODES {
+            let unit = match units.get(unit) {
+                Some(unit) => unit.clone(),
+                None => DerivedUnit::parse(unit)
+                    .expect("supplementary UN/ECE unit expression")
+                    .to_unit(),
+            };
+            table.push((code.to_string(), unit));
+        }
+        table
+    })
+}
+
+/// All units carrying the given UN/ECE common code
+pub fn units_for_unece_code(code: &str) -> Vec<&'static Unit> {
+    let mut units: Vec<&Unit> = Vec::new();
+    for (c, unit) in code_table() {
+        if c == code && !units.iter().any(|u| u.iri == unit.iri) {
+            units.push(unit);
+        }
+    }
+    units
+}
+
+/// Codes that map to several units, e.g. `A91` for both `Gon` and `Grad`
+pub fn ambiguous_unece_codes() -> Vec<(String, Vec<String>)> {
+    let mut codes: Vec<&str> = code_table().iter().map(|(c, _)| c.as_str()).collect();
+    codes.sort();
+    codes.dedup();
+    codes
+        .into_iter()
+        .filter_map(|code| {
+            let units = units_for_unece_code(code);
+            (units.len() > 1).then(|| {
+                (
+                    code.to_string(),
+                    units.iter().map(|u| u.name.clone()).collect(),
+                )
+            })
+        })
+        .collect()
+}
+
+impl Unit {
+    /// Look up a unit by its UN/ECE Recommendation 20 common code, e.g. `KGM`.
+    ///
+    /// A code shared by several units yields `UnitError::AmbiguousUnit`.
+    pub fn from_unece_code(code: &str) -> Result<Unit, UnitError> {
+        match units_for_unece_code(code).as_slice() {
+            [] => Err(UnitError::UnknownUnit(code.to_string())),
+            [unit] => Ok((*unit).clone()),
+            units => Err(UnitError::AmbiguousUnit {
+                symbol: code.to_string(),
+                candidates: units.iter().map(|u| u.name.clone()).collect(),
+            }),
+        }
+    }
+
+    /// The UN/ECE common code of the unit, from QUDT or the supplementary table
+    pub fn unece_code(&self) -> Option<&str> {
+        self.unece_common_code.as_deref().or_else(|| {
+            code_table()
+                .iter()
+                .find(|(_, unit)| unit.iri == self.iri)
+                .map(|(code, _)| code.as_str())
+        })
+    }
+}
@@ -0,0 +1,431 @@
+#![doc = include_str!("../doc/unit_system.md")]
+use crate::dimensional::{Dimension, DimensionTrait};
+use crate::dimensional_analysis::DimensionalAnalysis;
+use crate::expression::DerivedUnit;
+use crate::quantity::Quantity;
+use crate::quantity_kind::quantity_kinds;
+use crate::units::{Unit, UnitError, catalog};
+use num::rational::Rational32;
+use std::sync::OnceLock;
+
+/// A system of units of the bundled QUDT vocabulary, generated by `build.rs`
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitSystemConstant {
+    pub iri: &'static str,
+    /// Local name without the `SystemOfUnits_` prefix, e.g. `CGS-ESU`
+    pub name: &'static str,
+    pub label: &'static str,
+    pub abbreviation: Option<&'static str>,
+    /// Typed `qudt:SystemOfNaturalUnits` (Planck, atomic units)
+    pub natural: bool,
+    pub base_units: &'static [&'static str],
+    pub coherent_derived_units: &'static [&'static str],
+    pub derived_units: &'static [&'static str],
+    pub allowed_units: &'static [&'static str],
+    pub defined_units: &'static [&'static str],
+    pub prefix_units: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/unit_systems.rs"));
+
+/// How a system of units accounts for electric current
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Electromagnetism {
+    /// Electric quantities cannot be expressed (CGS, Imperial, US customary)
+    None,
+    /// Current or charge has a base unit of its own (SI, Planck)
+    Base,
+    /// CGS-ESU: charge defined by Coulomb's law, I = L^{3/2}M^{1/2}T^{-2}
+    Electrostatic,
+    /// CGS-EMU: current defined by Ampère's force law, I = L^{1/2}M^{1/2}T^{-1}
+    Electromagnetic,
+    /// Gaussian: electric quantities as in CGS-ESU, magnetic ones as in CGS-EMU
+    Gaussian,
+}
+
+/// A system of units with its members, completed by `SUPPLEMENTS`
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitSystem {
+    pub iri: String,
+    pub name: String,
+    pub label: String,
+    pub abbreviation: Option<String>,
+    /// A system of natural units, see also `natural_units::NaturalUnitSystem`
+    pub natural: bool,
+    pub electromagnetism: Electromagnetism,
+    base: Vec<String>,
+    derived: Vec<String>,
+    allowed: Vec<String>,
+    defined: Vec<String>,
+}
+
+/// Members QUDT 1.1 does not state: `(system, base units, systems whose
+/// derived units are inherited)`. The Gaussian, Imperial and US customary
+/// systems come without base units, and the electromagnetic CGS systems
+/// share the mechanical units of CGS.
+const SUPPLEMENTS: &[(&str, &[&str], &[&str])] = &[
+    (
+        "CGS-Gauss",
+        &["Centimeter", "Gram", "SecondTime", "Unitless"],
+        &["CGS", "CGS-ESU"],
+    ),
+    ("CGS-ESU", &[], &["CGS"]),
+    ("CGS-EMU", &[], &["CGS"]),
+    (
+        "MKS",
+        &["Meter", "Kilogram", "SecondTime", "Unitless"],
+        &["SI"],
+    ),
+    (
+        "Imperial",
+        &[
+            "Foot",
+            "PoundMass",
+            "SecondTime",
+            "DegreeRankine",
+            "Unitless",
+        ],
+        &[],
+    ),
+    (
+        "USCustomary",
+        &[
+            "Foot",
+            "PoundMass",
+            "SecondTime",
+            "DegreeRankine",
+            "Unitless",
+        ],
+        &[],
+    ),
+];
+
+/// Units standing in for the current in the electromagnetic CGS systems
+const STATCOULOMB: &str = "Statcoulomb";
+const ABAMPERE: &str = "Abampere";
+
+/// The systems of units of the bundled QUDT vocabulary, built on first use
+pub fn unit_systems() -> &'static [UnitSystem] {
+    static SYSTEMS: OnceLock<Vec<UnitSystem>> = OnceLock::new();
+    SYSTEMS.get_or_init(|| {
+        let strings =
+            |values: &[&str]| -> Vec<String> { values.iter().map(|s| s.to_string()).collect() };
+        let mut systems: Vec<UnitSystem> = UNIT_SYSTEMS
+            .iter()
+            .map(|system| UnitSystem {
+                iri: system.iri.to_string(),
+                name: system.name.to_string(),
+                label: system.label.to_string(),
+                abbreviation: system.abbreviation.map(str::to_string),
+                natural: system.natural,
+                electromagnetism: match system.name {
+                    "SI" | "Planck" => Electromagnetism::Base,
+                    "CGS-ESU" => Electromagnetism::Electrostatic,
+                    "CGS-EMU" => Electromagnetism::Electromagnetic,
+                    "CGS-Gauss" => Electromagnetism::Gaussian,
+                    _ => Electromagnetism::None,
+                },
+                base: strings(system.base_units),
+                derived: strings(system.coherent_derived_units)
+                    .into_iter()
+                    .chain(strings(system.derived_units))
+                    .collect(),
+                allowed: strings(system.allowed_units),
+                defined: strings(system.defined_units),
+            })
+            .collect();
+        for (name, base, inherited) in SUPPLEMENTS {
+            let derived: Vec<String> = inherited
+                .iter()
+                .flat_map(|parent| UNIT_SYSTEMS.iter().filter(move |s| s.name == *parent))
+                .flat_map(|s| s.coherent_derived_units.iter().chain(s.derived_units))
+                .map(|s| s.to_string())
+                .collect();
+            if let Some(system) = systems.iter_mut().find(|s| s.name == *name) {
+                for unit in base.iter().map(|s| s.to_string()) {
+                    if !system.base.contains(&unit) {
+                        system.base.push(unit);
+                    }
+                }
+                for unit in derived {
+                    if !system.derived.contains(&unit) {
+                        system.derived.push(unit);
+                    }
+                }
+            }
+        }
+        systems
+    })
+}
+
+/// Look up a system by name (`CGS-ESU`), abbreviation (`Gauss`), label or IRI
+pub fn unit_system(name: &str) -> Option<&'static UnitSystem> {
+    unit_systems().iter().find(|s| {
+        s.name == name
+            || s.abbreviation.as_deref() == Some(name)
+            || s.label == name
+            || s.iri == name
+    })
+}
+
+fn resolve(names: &[String]) -> Vec<&'static Unit> {
+    names
+        .iter()
+        .filter_map(|name| catalog().get(name))
+        .collect()
+}
+
+// QUDT 1.1 rounds the Planck units to five digits
+fn same_multiplier(a: Option<f64>, b: Option<f64>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => (a - b).abs() <= 1e-4 * a.abs().max(b.abs()),
+        _ => false,
+    }
+}
+
+// equal up to the 4π of the unrationalized CGS definitions
+fn same_multiplier_unrationalized(a: Option<f64>, b: Option<f64>) -> bool {
+    let four_pi = 4.0 * std::f64::consts::PI;
+    same_multiplier(a, b)
+        || same_multiplier(a, b.map(|b| b * four_pi))
+        || same_multiplier(a, b.map(|b| b / four_pi))
+}
+
+// magnetic quantities take their Gaussian dimension from CGS-EMU
+fn is_magnetic_kind(kind: &str) -> bool {
+    kind.contains("Magnet") || kind == "Permeability"
+}
+
+impl UnitSystem {
+    /// `qudt:systemBaseUnit`, completed by `SUPPLEMENTS`
+    pub fn base_units(&self) -> Vec<&'static Unit> {
+        resolve(&self.base)
+    }
+
+    /// `qudt:systemCoherentDerivedUnit` and `qudt:systemDerivedUnit`,
+    /// including the units inherited from a parent system
+    pub fn derived_units(&self) -> Vec<&'static Unit> {
+        resolve(&self.derived)
+    }
+
+    /// `qudt:systemAllowedUnit`: units of other systems accepted for use,
+    /// e.g. the kilogram in CGS
+    pub fn allowed_units(&self) -> Vec<&'static Unit> {
+        resolve(&self.allowed)
+    }
+
+    /// `qudt:systemDefinedUnit`: the only members of Imperial and US customary
+    pub fn defined_units(&self) -> Vec<&'static Unit> {
+        resolve(&self.defined)
+    }
+
+    /// Every member of the system
+    pub fn units(&self) -> Vec<&'static Unit> {
+        let mut units = self.base_units();
+        for unit in [
+            self.derived_units(),
+            self.allowed_units(),
+            self.defined_units(),
+        ]
+        .concat()
+        {
+            if !units.iter().any(|u| u.iri == unit.iri) {
+                units.push(unit);
+            }
+        }
+        units
+    }
+
+    /// Whether the unit may be used in the system: it is a member, or it is
+    /// the coherent unit of its dimension (`g·cm/s^2` in CGS)
+    pub fn is_allowed(&self, unit: &Unit) -> bool {
+        if self.units().iter().any(|u| u.iri == unit.iri) {
+            return true;
+        }
+        match &unit.dimension {
+            Some(dimension) => self.coherent_unit(dimension).is_ok_and(|coherent| {
+                coherent.iri == unit.iri
+                    || same_multiplier(coherent.conversion_multiplier, unit.conversion_multiplier)
+            }),
+            None => false,
+        }
+    }
+
+    fn not_in_system(&self, dimension: &Dimension, reason: &str) -> UnitError {
+        UnitError::NotInSystem {
+            quantity: dimension.to_string(),
+            system: self.name.clone(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Whether the Gaussian system treats the quantity as magnetic: decided
+    /// by the kind tag, else by the quantity kinds of the dimension
+    fn is_magnetic(&self, dimension: &Dimension) -> Result<bool, UnitError> {
+        if let Some(kind) = dimension.kind() {
+            return Ok(is_magnetic_kind(kind));
+        }
+        let kinds: Vec<&str> = quantity_kinds()
+            .iter()
+            .filter(|k| k.dimension.as_ref() == Some(dimension))
+            .map(|k| k.name.as_str())
+            .collect();
+        let magnetic = kinds.iter().filter(|k| is_magnetic_kind(k)).count();
+        if magnetic == 0 || magnetic == kinds.len() {
+            Ok(magnetic > 0)
+        } else {
+            Err(self.not_in_system(
+                dimension,
+                &format!(
+                    "electric or magnetic depending on the quantity kind ({}), tag it",
+                    kinds.join(", ")
+                ),
+            ))
+        }
+    }
+
+    // the unit standing in for the current, `None` for non-electric quantities
+    // and for systems with a base unit of current
+    fn current_unit(&self, dimension: &Dimension) -> Result<Option<&'static str>, UnitError> {
+        if dimension.get_exponent(3) == Rational32::from_integer(0) {
+            return Ok(None);
+        }
+        match self.electromagnetism {
+            Electromagnetism::None => {
+                Err(self.not_in_system(dimension, "no electromagnetic units"))
+            }
+            Electromagnetism::Base => Ok(None),
+            Electromagnetism::Electrostatic => Ok(Some(STATCOULOMB)),
+            Electromagnetism::Electromagnetic => Ok(Some(ABAMPERE)),
+            Electromagnetism::Gaussian => Ok(Some(if self.is_magnetic(dimension)? {
+                ABAMPERE
+            } else {
+                STATCOULOMB
+            })),
+        }
+    }
+
+    /// The dimension in the system's own base quantities. The electromagnetic
+    /// CGS systems have no base quantity for the current, which is replaced
+    /// by a power of L, M and T: the electric field is L^{-1/2}M^{1/2}T^{-1}
+    /// in CGS-ESU. In the Gaussian system the replacement depends on whether
+    /// the quantity is electric or magnetic, so an ambiguous dimension must
+    /// carry a kind tag (`Dimension::with_kind`).
+    pub fn system_dimension(&self, dimension: &Dimension) -> Result<Dimension, UnitError> {
+        let current = match self.current_unit(dimension)? {
+            Some(STATCOULOMB) => [
+                Rational32::new(3, 2),
+                Rational32::new(1, 2),
+                Rational32::from_integer(-2),
+            ],
+            Some(_) => [
+                Rational32::new(1, 2),
+                Rational32::new(1, 2),
+                Rational32::from_integer(-1),
+            ],
+            None => return Ok(dimension.clone()),
+        };
+        let mut exponents = dimension.get_exponents();
+        let power = exponents[3];
+        for (exponent, per_current) in exponents.iter_mut().zip(current) {
+            *exponent += power * per_current;
+        }
+        exponents[3] = Rational32::from_integer(0);
+        let system_dimension = Dimension::PowerLawRA(exponents);
+        Ok(match dimension.kind() {
+            Some(kind) => system_dimension.with_kind(kind),
+            None => system_dimension,
+        })
+    }
+
+    /// The coherent unit of a dimension: the product of base units with
+    /// exponent 1 and no numerical factor. A member unit equal to that
+    /// product is preferred (`Dyne` for g·cm/s² in CGS); among several, the
+    /// one of the dimension's kind tag (`NewtonMeter` for a torque), else
+    /// one with a symbol of its own (`Joule`).
+    ///
+    /// For electromagnetic quantities of the CGS systems a member unit may
+    /// differ from the product by the 4π of the unrationalized definitions
+    /// (1 Oe = 1000/4π A/m, not 1 abA/cm).
+    pub fn coherent_unit(&self, dimension: &Dimension) -> Result<Unit, UnitError> {
+        if dimension.is_dimensionless() {
+            return catalog().require("Unitless").cloned();
+        }
+        if !dimension.get_exponents().iter().all(|e| e.is_integer()) {
+            return Err(self.not_in_system(dimension, "fractional power of a base unit"));
+        }
+        let current = self.current_unit(dimension)?;
+        let mut bases: Vec<&Unit> = self
+            .base_units()
+            .into_iter()
+            .filter(|u| u.dimension.as_ref().is_some_and(|d| !d.is_dimensionless()))
+            .collect();
+        if let Some(name) = current {
+            bases.push(catalog().require(name)?);
+        }
+        let analysis = DimensionalAnalysis {
+            target: dimension.standard_form(),
+            dependencies: bases.iter().filter_map(|u| u.dimension.clone()).collect(),
+        };
+        let exponents = analysis
+            .exponents()
+            .ok_or_else(|| self.not_in_system(dimension, "not a product of the base units"))?;
+        let mut product = DerivedUnit::one();
+        for (unit, exponent) in bases.iter().zip(exponents) {
+            if !exponent.is_integer() {
+                return Err(self.not_in_system(dimension, "fractional power of a base unit"));
+            }
+            product.push((*unit).clone(), exponent.to_integer());
+        }
+
+        let multiplier = product.conversion_multiplier();
+        let members = [
+            self.derived_units(),
+            self.defined_units(),
+            self.base_units(),
+        ]
+        .concat();
+        let candidates: Vec<&Unit> = members
+            .into_iter()
+            .filter(|u| u.dimension.as_ref() == Some(dimension))
+            .filter(|u| u.conversion_offset == 0.0)
+            .filter(|u| match current {
+                Some(_) => same_multiplier_unrationalized(u.conversion_multiplier, multiplier),
+                None => same_multiplier(u.conversion_multiplier, multiplier),
+            })
+            .collect();
+        let preferred = match dimension.kind().and_then(|k| quantity_kinds().get(k)) {
+            Some(kind) => candidates
+                .iter()
+                .find(|u| u.quantity_kinds.iter().any(|k| k == &kind.name))
+                .or_else(|| {
+                    candidates.iter().find(|u| {
+                        u.quantity_kinds
+                            .iter()
+                            .filter_map(|k| quantity_kinds().get(k))
+                            .any(|k| kind.is_compatible_with(k))
+                    })
+                }),
+            // a unit with a name of its own (J) before a compound (N·m)
+            None => candidates
+                .iter()
+                .find(|u| !u.display_symbol().contains(['/', '·', '.', ' ', '^', '-']))
+                .or(candidates.first()),
+        };
+        Ok(match preferred {
+            Some(unit) => (*unit).clone(),
+            None => product.to_unit(),
+        })
+    }
+
+    /// Express a quantity in the coherent unit of its dimension, see
+    /// `coherent_unit`; the kind tag chooses among units of equal dimension
+    pub fn express(&self, quantity: &Quantity) -> Result<Quantity, UnitError> {
+        let dimension = quantity.dimension().ok_or_else(|| UnitError::NotInSystem {
+            quantity: quantity.unit.name.clone(),
+            system: self.name.clone(),
+            reason: "unknown dimension".to_string(),
+        })?;
+        quantity.convert_to(&self.coherent_unit(&dimension)?)
+    }
+}
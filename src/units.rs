@@ -1 +1,557 @@
 #![doc = include_str!("../doc/qudt.md")]
+use crate::dimensional::Dimension;
+use crate::qudt_records::{self, UnitRecord, link_quantity_kinds, unit_records};
+use crate::rdf::{self, RdfError, Triple};
+use crate::turtle;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+pub const UNIT_NS: &str = "http://qudt.org/vocab/unit#";
+pub use crate::qudt_records::QUANTITY_KIND_NS;
+
+/// OpenPHACTS concentration units, Turtle serialization
+pub const OPS_TURTLE: &str = include_str!("ontology/ops.ttl");
+/// OpenPHACTS concentration units, RDF/XML serialization of the same data
+pub const OPS_RDF_XML: &str = include_str!("ontology/ops");
+
+/// A unit of measure as described by the QUDT `unit` vocabulary
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit {
+    pub iri: String,
+    pub name: String,
+    pub label: String,
+    pub symbol: Option<String>,
+    pub abbreviation: Option<String>,
+    /// Factor to the coherent SI unit: `si = value * multiplier + offset`
+    pub conversion_multiplier: Option<f64>,
+    pub conversion_offset: f64,
+    /// Local names of the QUDT quantity kinds, e.g. `Length`
+    pub quantity_kinds: Vec<String>,
+    /// Local names of the QUDT unit classes, e.g. `SIBaseUnit`, `LengthUnit`
+    pub unit_types: Vec<String>,
+    pub dimension: Option<Dimension>,
+    /// `qudt:uneceCommonCode`, UN/ECE Recommendation 20 common code
+    pub unece_common_code: Option<String>,
+}
+
+/// Errors raised by unit lookup and conversion
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnitError {
+    UnknownUnit(String),
+    MissingConversion(String),
+    IncompatibleDimensions {
+        from: String,
+        to: String,
+    },
+    PrefixNotAllowed {
+        prefix: String,
+        unit: String,
+        reason: String,
+    },
+    AmbiguousUnit {
+        symbol: String,
+        candidates: Vec<String>,
+    },
+    InvalidExpression {
+        expression: String,
+        position: usize,
+        message: String,
+    },
+    NoCode {
+        unit: String,
+        system: String,
+    },
+    UnknownQuantityKind(String),
+    KindMismatch {
+        from: String,
+        to: String,
+    },
+    NotInSystem {
+        quantity: String,
+        system: String,
+        reason: String,
+    },
+    UnknownConstant(String),
+    MissingExchangeRate {
+        from: String,
+        to: String,
+        at: String,
+    },
+    AffineMismatch {
+        from: String,
+        to: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitError::UnknownUnit(name) => write!(f, "unknown unit `{}`", name),
+            UnitError::MissingConversion(name) => {
+                write!(f, "unit `{}` has no conversion multiplier", name)
+            }
+            UnitError::IncompatibleDimensions { from, to } => {
+                write!(f, "cannot convert from `{}` to `{}`", from, to)
+            }
+            UnitError::PrefixNotAllowed {
+                prefix,
+                unit,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "prefix `{}` cannot be applied to `{}`: {}",
+                    prefix, unit, reason
+                )
+            }
+            UnitError::AmbiguousUnit { symbol, candidates } => {
+                write!(
+                    f,
+                    "unit symbol `{}` is ambiguous: {}",
+                    symbol,
+                    candidates.join(", ")
+                )
+            }
+            UnitError::InvalidExpression {
+                expression,
+                position,
+                message,
+            } => {
+                write!(
+                    f,
+                    "invalid unit expression `{}` at {}: {}",
+                    expression, position, message
+                )
+            }
+            UnitError::NoCode { unit, system } => {
+                write!(f, "unit `{}` has no {} code", unit, system)
+            }
+            UnitError::UnknownQuantityKind(kind) => write!(f, "unknown quantity kind `{}`", kind),
+            UnitError::KindMismatch { from, to } => {
+                write!(
+                    f,
+                    "quantity kind `{}` does not match `{}`, cast explicitly",
+                    from, to
+                )
+            }
+            UnitError::NotInSystem {
+                quantity,
+                system,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "`{}` cannot be expressed in the {} system: {}",
+                    quantity, system, reason
+                )
+            }
+            UnitError::UnknownConstant(name) => write!(f, "unknown physical constant `{}`", name),
+            UnitError::MissingExchangeRate { from, to, at } => {
+                write!(
+                    f,
+                    "no exchange rate from {} to {} at or before {}",
+                    from, to, at
+                )
+            }
+            UnitError::AffineMismatch { from, to, reason } => {
+                write!(f, "cannot combine `{}` with `{}`: {}", from, to, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_symbol())
+    }
+}
+
+impl Unit {
+    /// The symbol used when printing a value in this unit:
+    /// `qudt:symbol`, falling back to the abbreviation and then the label
+    pub fn display_symbol(&self) -> &str {
+        self.symbol
+            .as_deref()
+            .or(self.abbreviation.as_deref())
+            .unwrap_or(&self.label)
+    }
+
+    /// Check whether the unit carries the given QUDT class, e.g. `SIBaseUnit`
+    pub fn has_type(&self, unit_type: &str) -> bool {
+        self.unit_types.iter().any(|t| t == unit_type)
+    }
+
+    /// Check whether the unit is part of the SI (base, derived or SI-flagged unit)
+    pub fn is_si(&self) -> bool {
+        ["SIBaseUnit", "SIDerivedUnit", "SIUnit"]
+            .iter()
+            .any(|t| self.has_type(t))
+    }
+
+    /// Convert a value in this unit to the coherent SI unit
+    pub fn to_si(&self, value: f64) -> Result<f64, UnitError> {
+        let multiplier = self
+            .conversion_multiplier
+            .ok_or_else(|| UnitError::MissingConversion(self.name.clone()))?;
+        Ok(value * multiplier + self.conversion_offset)
+    }
+
+    /// Convert a value in the coherent SI unit to this unit
+    pub fn from_si(&self, value: f64) -> Result<f64, UnitError> {
+        let multiplier = self
+            .conversion_multiplier
+            .ok_or_else(|| UnitError::MissingConversion(self.name.clone()))?;
+        Ok((value - self.conversion_offset) / multiplier)
+    }
+
+    /// Check whether values can be converted between the two units
+    pub fn is_commensurable(&self, other: &Unit) -> bool {
+        match (&self.dimension, &other.dimension) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Convert a value from this unit to another unit of the same dimension
+    pub fn convert(&self, value: f64, to: &Unit) -> Result<f64, UnitError> {
+        if !self.is_commensurable(to) {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.name.clone(),
+                to: to.name.clone(),
+            });
+        }
+        to.from_si(self.to_si(value)?)
+    }
+
+    /// Convert a difference of two values, ignoring the offsets: 10 °C
+    /// apart is 10 K apart, not 283.15 K
+    pub fn convert_interval(&self, value: f64, to: &Unit) -> Result<f64, UnitError> {
+        if !self.is_commensurable(to) {
+            return Err(UnitError::IncompatibleDimensions {
+                from: self.name.clone(),
+                to: to.name.clone(),
+            });
+        }
+        Ok(value * (self.to_si(1.0)? - self.to_si(0.0)?) / (to.to_si(1.0)? - to.to_si(0.0)?))
+    }
+
+    /// The unit of differences on this unit's scale, the catalog unit of
+    /// the same size without offset: kelvin for °C, degree Rankine for °F.
+    /// Units without offset measure their own differences.
+    pub fn interval_unit(&self) -> Unit {
+        if self.conversion_offset == 0.0 {
+            return self.clone();
+        }
+        let same_size = |u: &&Unit| {
+            u.conversion_offset == 0.0
+                && self.is_commensurable(u)
+                && matches!((u.conversion_multiplier, self.conversion_multiplier),
+                    (Some(a), Some(b)) if (a - b).abs() <= 1e-12 * b.abs())
+        };
+        let candidates: Vec<&Unit> = catalog().iter().filter(same_size).collect();
+        candidates
+            .iter()
+            .find(|u| u.is_si())
+            .or(candidates.first())
+            .map_or_else(|| self.clone(), |u| (*u).clone())
+    }
+
+    /// Fill in the properties this unit lacks from another definition of
+    /// the same unit and report the properties on which they disagree.
+    ///
+    /// Only properties both definitions state are compared: a label equal
+    /// to the local name was never stated, and the offset is stated along
+    /// with the multiplier.
+    fn merge(&mut self, other: UnitRecord) -> Vec<UnitConflict> {
+        let mut conflicts = Vec::new();
+        let mut conflict = |property: &str, existing: String, incoming: String| {
+            conflicts.push(UnitConflict {
+                iri: self.iri.clone(),
+                property: property.to_string(),
+                existing,
+                incoming,
+            })
+        };
+        match other.label {
+            Some(label) if self.label == self.name => self.label = label,
+            Some(label) if self.label != label => conflict("label", self.label.clone(), label),
+            _ => {}
+        }
+        for (property, existing, incoming) in [
+            ("symbol", &mut self.symbol, other.symbol),
+            ("abbreviation", &mut self.abbreviation, other.abbreviation),
+            (
+                "unece_common_code",
+                &mut self.unece_common_code,
+                other.unece_common_code,
+            ),
+        ] {
+            match (existing.as_ref(), incoming) {
+                (Some(a), Some(b)) if *a != b => conflict(property, a.clone(), b),
+                (None, Some(b)) => *existing = Some(b),
+                _ => {}
+            }
+        }
+        match (self.conversion_multiplier, other.offset) {
+            (Some(_), Some(b)) if self.conversion_offset != b => conflict(
+                "conversion_offset",
+                self.conversion_offset.to_string(),
+                b.to_string(),
+            ),
+            (None, Some(b)) => self.conversion_offset = b,
+            _ => {}
+        }
+        match (self.conversion_multiplier, other.multiplier) {
+            (Some(a), Some(b)) if (a - b).abs() > 1e-12 * a.abs().max(b.abs()) => {
+                conflict("conversion_multiplier", a.to_string(), b.to_string())
+            }
+            (None, Some(b)) => self.conversion_multiplier = Some(b),
+            _ => {}
+        }
+        match (&self.dimension, other.dimension) {
+            (Some(a), Some(b)) if *a != Dimension::PowerLawIA(b) => conflict(
+                "dimension",
+                a.to_string(),
+                Dimension::PowerLawIA(b).to_string(),
+            ),
+            (None, Some(b)) => self.dimension = Some(Dimension::PowerLawIA(b)),
+            _ => {}
+        }
+        for unit_type in other.unit_types {
+            if !self.has_type(&unit_type) {
+                self.unit_types.push(unit_type);
+            }
+        }
+        for kind in other.quantity_kinds {
+            if !self.quantity_kinds.contains(&kind) {
+                self.quantity_kinds.push(kind);
+            }
+        }
+        conflicts
+    }
+}
+
+/// A property on which two definitions of the same unit disagree
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitConflict {
+    pub iri: String,
+    /// Field of `Unit`, e.g. `conversion_multiplier`
+    pub property: String,
+    pub existing: String,
+    pub incoming: String,
+}
+
+impl fmt::Display for UnitConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<{}> {}: `{}` conflicts with `{}`",
+            self.iri, self.property, self.existing, self.incoming
+        )
+    }
+}
+
+/// The set of units loaded from the QUDT ontology
+#[derive(Clone, Debug, Default)]
+pub struct UnitCatalog {
+    units: Vec<Unit>,
+    index: HashMap<String, usize>,
+}
+
+impl UnitCatalog {
+    /// Build a catalog from the triples of a QUDT unit vocabulary and
+    /// the unit-to-quantity-kind links of a quantity vocabulary
+    pub fn from_triples(unit_triples: &[Triple], quantity_triples: &[Triple]) -> Self {
+        let mut catalog = UnitCatalog::default();
+        let mut units = unit_records(unit_triples, |_| false);
+        link_quantity_kinds(&mut units, quantity_triples);
+        for unit in units {
+            catalog.insert(Unit::from(unit));
+        }
+        catalog
+    }
+
+    /// Merge the units described by `triples` into the catalog.
+    ///
+    /// New units are added. For a unit already in the catalog, properties
+    /// it lacks are filled in, and properties on which both definitions
+    /// disagree are reported as conflicts; the existing value is kept.
+    /// Properties of a catalog unit may be given without restating its type.
+    pub fn merge_triples(&mut self, triples: &[Triple]) -> Vec<UnitConflict> {
+        let mut units = unit_records(triples, |iri| self.index.contains_key(iri));
+        link_quantity_kinds(&mut units, triples);
+        let mut conflicts = Vec::new();
+        for unit in units {
+            match self.index.get(&unit.iri) {
+                Some(&i) => conflicts.extend(self.units[i].merge(unit)),
+                None => self.insert(Unit::from(unit)),
+            }
+        }
+        conflicts
+    }
+
+    /// Merge a Turtle document, such as a user `.ttl` unit extension
+    pub fn merge_turtle(&mut self, src: &str) -> Result<Vec<UnitConflict>, RdfError> {
+        Ok(self.merge_triples(&turtle::parse_turtle(src)?))
+    }
+
+    /// Merge an RDF/XML document
+    pub fn merge_rdf_xml(&mut self, src: &str) -> Result<Vec<UnitConflict>, RdfError> {
+        Ok(self.merge_triples(&rdf::parse_rdf_xml(src)?))
+    }
+
+    /// Add a unit to the catalog, replacing any unit with the same IRI
+    pub fn insert(&mut self, unit: Unit) {
+        match self.index.get(&unit.iri) {
+            Some(&i) => self.units[i] = unit,
+            None => {
+                let i = self.units.len();
+                self.index.insert(unit.iri.clone(), i);
+                self.index.entry(unit.name.clone()).or_insert(i);
+                self.units.push(unit);
+            }
+        }
+    }
+
+    /// Get a unit by its IRI or local name, e.g. `Meter`
+    pub fn get(&self, name: &str) -> Option<&Unit> {
+        self.index.get(name).map(|&i| &self.units[i])
+    }
+
+    /// Get a unit by its IRI or local name, or fail with `UnitError::UnknownUnit`
+    pub fn require(&self, name: &str) -> Result<&Unit, UnitError> {
+        self.get(name)
+            .ok_or_else(|| UnitError::UnknownUnit(name.to_string()))
+    }
+
+    /// All units whose symbol or abbreviation is exactly `text`
+    pub fn find_by_symbol(&self, text: &str) -> Vec<&Unit> {
+        self.units
+            .iter()
+            .filter(|u| {
+                u.symbol.as_deref() == Some(text) || u.abbreviation.as_deref() == Some(text)
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Unit> {
+        self.units.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+}
+
+impl From<UnitRecord> for Unit {
+    fn from(unit: UnitRecord) -> Self {
+        Unit {
+            label: unit.label.unwrap_or_else(|| unit.name.clone()),
+            iri: unit.iri,
+            name: unit.name,
+            symbol: unit.symbol,
+            abbreviation: unit.abbreviation,
+            conversion_multiplier: unit.multiplier,
+            conversion_offset: unit.offset.unwrap_or(0.0),
+            quantity_kinds: unit.quantity_kinds,
+            unit_types: unit.unit_types,
+            dimension: unit.dimension.map(Dimension::PowerLawIA),
+            unece_common_code: unit.unece_common_code,
+        }
+    }
+}
+
+/// The catalog of the QUDT units bundled with the crate, built on first use
+/// from the constants generated at compile time
+pub fn catalog() -> &'static UnitCatalog {
+    static CATALOG: OnceLock<UnitCatalog> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let mut catalog = UnitCatalog::default();
+        for unit in UNITS {
+            catalog.insert(Unit::from(unit));
+        }
+        catalog
+    })
+}
+
+/// A unit of the bundled QUDT vocabulary, generated by `build.rs`.
+///
+/// Only `&'static str`, `f64` and `Dimension::PowerLawIA` appear in the
+/// constants, so they need neither parsing nor allocation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitConstant {
+    pub iri: &'static str,
+    pub name: &'static str,
+    pub label: &'static str,
+    pub symbol: Option<&'static str>,
+    pub abbreviation: Option<&'static str>,
+    /// Factor to the coherent SI unit: `si = value * multiplier + offset`
+    pub multiplier: Option<f64>,
+    pub offset: f64,
+    pub quantity_kinds: &'static [&'static str],
+    pub unit_types: &'static [&'static str],
+    pub dimension: Option<Dimension>,
+    pub unece_common_code: Option<&'static str>,
+}
+
+impl UnitConstant {
+    /// Convert a value in this unit to the coherent SI unit
+    pub const fn to_si(&self, value: f64) -> Option<f64> {
+        match self.multiplier {
+            Some(multiplier) => Some(value * multiplier + self.offset),
+            None => None,
+        }
+    }
+
+    /// Convert a value in the coherent SI unit to this unit
+    pub const fn from_si(&self, value: f64) -> Option<f64> {
+        match self.multiplier {
+            Some(multiplier) => Some((value - self.offset) / multiplier),
+            None => None,
+        }
+    }
+}
+
+impl From<&UnitConstant> for Unit {
+    fn from(unit: &UnitConstant) -> Self {
+        let strings = |values: &[&str]| values.iter().map(|s| s.to_string()).collect();
+        Unit {
+            iri: unit.iri.to_string(),
+            name: unit.name.to_string(),
+            label: unit.label.to_string(),
+            symbol: unit.symbol.map(str::to_string),
+            abbreviation: unit.abbreviation.map(str::to_string),
+            conversion_multiplier: unit.multiplier,
+            conversion_offset: unit.offset,
+            quantity_kinds: strings(unit.quantity_kinds),
+            unit_types: strings(unit.unit_types),
+            dimension: unit.dimension.clone(),
+            unece_common_code: unit.unece_common_code.map(str::to_string),
+        }
+    }
+}
+
+// the multipliers are QUDT's rounded values, not approximations of `PI`, `LN_10`, ...
+#[allow(clippy::approx_constant, clippy::excessive_precision)]
+mod constants {
+    use super::{Dimension, UnitConstant};
+    include!(concat!(env!("OUT_DIR"), "/unit_constants.rs"));
+}
+pub use constants::*;
+
+/// The `Dimension` of a QUDT quantity kind given by its local name.
+///
+/// The bundled QUDT 1.1 vocabulary carries no dimension vectors, so the
+/// exponents (L, M, T, I, Θ, N, J) are tabulated here. Kinds without a
+/// physical dimension (currencies, arbitrary units) return `None`.
+pub fn quantity_kind_dimension(kind: &str) -> Option<Dimension> {
+    qudt_records::dimension(kind).map(Dimension::PowerLawIA)
+}
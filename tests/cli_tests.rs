@@ -0,0 +1,167 @@
+#![cfg(feature = "cli")]
+use metrology4rs::cli::*;
+use std::fs;
+
+fn args(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_convert() {
+    assert_eq!(run(&args("convert 1 kW.h MJ")).unwrap(), "1 kW·hr = 3.6 MJ");
+    assert_eq!(run(&args("convert 100 cm m")).unwrap(), "100 cm = 1 m");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&run(&args("--json convert 3.2 psi Pa")).unwrap()).unwrap();
+    assert!((json["result"].as_f64().unwrap() - 22063.2).abs() < 0.1);
+    assert_eq!(json["dimension"], "L^-1MT^-2");
+
+    assert!(matches!(
+        run(&args("convert 1 m s")),
+        Err(CliError::Unit(_))
+    ));
+    assert!(matches!(
+        run(&args("convert x m cm")),
+        Err(CliError::Usage(_))
+    ));
+    assert!(matches!(run(&args("convert 1 m")), Err(CliError::Usage(_))));
+}
+
+#[test]
+fn test_dim() {
+    let table = run(&["dim".to_string(), "N*m/s".to_string()]).unwrap();
+    assert!(table.contains("dimension       L^2MT^-3"));
+    assert!(table.contains("Power"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&run(&args("dim kPa --json")).unwrap()).unwrap();
+    assert_eq!(
+        json["dimension"],
+        serde_json::json!({"L": -1, "M": 1, "T": -2})
+    );
+    assert_eq!(json["si_multiplier"], 1000.0);
+}
+
+#[test]
+fn test_analyze() {
+    let table = run(&args(
+        "analyze --target frequency --dep length=L --dep g=acceleration",
+    ))
+    .unwrap();
+    assert!(table.contains("unique solution: frequency = C·length^-1/2·g^1/2"));
+    assert!(table.contains("g          LT^-2 (Acceleration)"));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &run(&args("--json analyze --target=F=force --dep rho=density --dep V=velocity --dep D=L --dep mu=DynamicViscosity"))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["solution"]["type"], "multiple_solutions");
+    assert_eq!(json["pi_groups"][0]["product"], "F·rho^-1·V^-2·D^-2");
+    assert_eq!(json["pi_groups"][1]["exponents"]["mu"], 1);
+
+    let table = run(&args("analyze --target F=force --dep l=L --dep t=T")).unwrap();
+    assert!(table.contains("no solution"));
+    assert!(!table.contains("redundant"));
+    assert!(
+        table.contains("the exponent of M cannot be matched; add a quantity carrying M, e.g. Mass")
+    );
+
+    let table = run(&args(
+        "analyze --target F=force --dep rho=density --dep v=unit:m^3/kg --dep V=velocity --dep D=L",
+    ))
+    .unwrap();
+    assert!(
+        table.contains("redundant: rho and v are dimensionally dependent: rho·v is dimensionless")
+    );
+
+    assert!(matches!(
+        run(&args("analyze --dep l=L")),
+        Err(CliError::Usage(_))
+    ));
+    assert!(matches!(
+        run(&args("analyze --target")),
+        Err(CliError::Usage(_))
+    ));
+    assert!(matches!(
+        run(&args("analyze --target t=nothing")),
+        Err(CliError::Unit(_))
+    ));
+    assert!(matches!(run(&args("frobnicate")), Err(CliError::Usage(_))));
+}
+
+#[test]
+fn test_pi_groups_file() {
+    let dir = std::env::temp_dir().join("metrology4rs_cli_tests");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("pendulum.toml");
+    fs::write(
+        &path,
+        r#"
+[target]
+name = "t"
+dimension = "T"
+
+[[dependencies]]
+name = "l"
+dimension = "L"
+
+[[dependencies]]
+name = "g"
+dimension = { L = 1, T = -2 }
+
+[[dependencies]]
+name = "m"
+dimension = "M"
+"#,
+    )
+    .unwrap();
+    let table = run(&["pi-groups".to_string(), path.display().to_string()]).unwrap();
+    assert!(table.contains("Π1     t^2·l^-1·g"));
+
+    let missing = dir.join("missing.toml");
+    assert!(matches!(
+        run(&["pi-groups".to_string(), missing.display().to_string()]),
+        Err(CliError::File { .. })
+    ));
+}
+
+#[test]
+fn test_batch() {
+    let dir = std::env::temp_dir().join("metrology4rs_cli_batch_tests");
+    fs::create_dir_all(&dir).unwrap();
+    let pendulum = dir.join("pendulum.yaml");
+    fs::write(
+        &pendulum,
+        "title: Pendulum\ntarget: t = s\ndependencies: [l = m, g = acceleration]\n",
+    )
+    .unwrap();
+    let path = pendulum.display().to_string();
+
+    let markdown = run(&args(&format!("batch {}", path))).unwrap();
+    assert!(markdown.contains("**Unique solution**: t = C·l^1/2·g^-1/2"));
+    let json: serde_json::Value =
+        serde_json::from_str(&run(&args(&format!("--json batch {}", path))).unwrap()).unwrap();
+    assert_eq!(json[0]["title"], "Pendulum");
+    let latex = run(&args(&format!("batch --format=latex {}", path))).unwrap();
+    assert!(latex.starts_with("\\section*{Pendulum}"));
+
+    let output = dir.join("report.md");
+    let summary = run(&args(&format!("batch {} -o {}", path, output.display()))).unwrap();
+    assert!(summary.starts_with("1 problems solved"));
+    assert!(
+        fs::read_to_string(&output)
+            .unwrap()
+            .starts_with("## Pendulum")
+    );
+
+    assert!(matches!(run(&args("batch")), Err(CliError::Usage(_))));
+    assert!(matches!(
+        run(&args(&format!("batch --format html {}", path))),
+        Err(CliError::Usage(_))
+    ));
+    assert!(matches!(
+        run(&args("batch missing.toml")),
+        Err(CliError::File { .. })
+    ));
+}
@@ -0,0 +1,174 @@
+use metrology4rs::constants::*;
+use metrology4rs::dimensional::*;
+use metrology4rs::natural_units::*;
+use metrology4rs::units::UnitError;
+
+fn close(a: f64, b: f64, rel: f64) -> bool {
+    (a - b).abs() <= rel * b.abs()
+}
+
+#[test]
+fn test_bundled_constants() {
+    let data = codata();
+    assert!(data.len() > 70);
+    for constant in data.iter() {
+        assert!(
+            constant.unit.is_some(),
+            "no unit for {} [{}]",
+            constant.name,
+            constant.unit_symbol
+        );
+        assert_eq!(
+            constant.exact,
+            constant.uncertainty == 0.0,
+            "{}",
+            constant.name
+        );
+    }
+
+    let c = constant("c").unwrap();
+    assert_eq!(c.value, 299792458.0);
+    assert!(c.exact);
+    assert_eq!(c.dimension, Some(VELOCITY));
+    assert_eq!(constant("kB"), constant("Boltzmann constant"));
+    assert_eq!(constant("R∞").unwrap().unit_symbol, "m^-1");
+    assert_eq!(
+        constant("α").unwrap().unit.as_ref().unwrap().name,
+        "Unitless"
+    );
+    assert_eq!(constant("E_h").unwrap().dimension, Some(ENERGY));
+    assert!(constant("Hubble constant").is_none());
+    assert_eq!(
+        data.product(&[("H_0", 1)]),
+        Err(UnitError::UnknownConstant("H_0".to_string()))
+    );
+
+    let m_e = constant("m_e").unwrap().quantity().unwrap();
+    assert_eq!(m_e.unit.name, "Kilogram");
+    assert!(close(m_e.relative_uncertainty(), 3.0e-10, 0.05));
+}
+
+#[test]
+fn test_natural_unit_constants_agree() {
+    for defining in [
+        speed_of_light(),
+        reduced_planck_constant(),
+        gravitational_constant(),
+        boltzmann_constant(),
+        elementary_charge(),
+        electron_mass(),
+    ] {
+        let listed = constant(defining.name).unwrap();
+        assert!(
+            close(listed.value, defining.value, 1e-12),
+            "{}",
+            defining.name
+        );
+        assert_eq!(
+            listed.dimension.as_ref(),
+            Some(&defining.dimension),
+            "{}",
+            defining.name
+        );
+        assert_eq!(listed.symbol.as_deref(), Some(defining.symbol));
+    }
+
+    let coulomb = coulomb_constant();
+    assert!(close(coulomb.value, 8.9875517862e9, 1e-10));
+    assert_eq!(
+        coulomb.dimension,
+        Dimension::PowerLaw(3, 1, -4, -2, 0, 0, 0)
+    );
+}
+
+#[test]
+fn test_correlations() {
+    let data = codata();
+    let get = |name| constant(name).unwrap();
+    assert_eq!(data.correlation(get("G"), get("G")), 1.0);
+    assert_eq!(data.correlation(get("c"), get("G")), 0.0);
+    assert_eq!(data.correlation(get("α"), get("G")), 0.0);
+    assert!(data.correlation(get("α"), get("m_e")) < -0.99);
+    assert!(data.correlation(get("m_e"), get("m_p")) > 0.95);
+    assert!(close(data.correlation(get("α"), get("μ_0")), 1.0, 1e-12));
+    assert!(close(data.correlation(get("l_P"), get("m_P")), -1.0, 1e-12));
+
+    // CODATA 2022 correlates the relative atomic masses of the electron and
+    // the proton, CODATA 2018 hardly
+    let (a_e, a_p) = ("A_r(e)", "A_r(p)");
+    let r = data.adjusted_correlation(&get(a_e).name, &get(a_p).name);
+    assert!(r > 0.2 && r < 0.35, "{}", r);
+    assert!(data.correlation(get(a_e), get(a_p)) == r);
+    let old = Codata::parse(CODATA_2018).unwrap();
+    let r = old.correlation(old.get(a_e).unwrap(), old.get(a_p).unwrap());
+    assert!(r.abs() < 0.05, "{}", r);
+    // the listed neutron-proton mass ratio does not resolve the coefficient
+    assert_eq!(
+        data.correlation(get("neutron relative atomic mass"), get(a_p)),
+        0.0
+    );
+
+    let matrix = data.correlation_matrix(&[get("α"), get("m_e"), get("a_0")]);
+    assert_eq!(matrix.dimensions(), (3, 3));
+    for i in 0..3 {
+        assert_eq!(matrix[(i, i)], 1.0);
+        for j in 0..3 {
+            assert_eq!(matrix[(i, j)], matrix[(j, i)]);
+        }
+    }
+}
+
+#[test]
+fn test_correlated_products() {
+    let data = codata();
+    // α and R∞ cancel in m_p/m_e; treated as independent the uncertainty
+    // would be about 4.3e-10. The remaining 1.7e-11 of A_r(p) and A_r(e)
+    // takes their correlation into account.
+    let ratio = data.product(&[("m_p", 1), ("m_e", -1)]).unwrap();
+    let direct = constant("m_p/m_e").unwrap();
+    assert!(close(ratio.value, direct.value, 1e-10));
+    assert!(close(
+        ratio.relative_uncertainty(),
+        direct.relative_uncertainty(),
+        0.05
+    ));
+    assert_eq!(
+        ratio.unit.dimension.clone().map(|d| d.standard_form()),
+        Some(DIMENSIONLESS)
+    );
+
+    // a_0 = ħ/(m_e c α)
+    let a_0 = data
+        .product(&[("ħ", 1), ("m_e", -1), ("c", -1), ("α", -1)])
+        .unwrap();
+    let bohr = constant("a_0").unwrap();
+    assert!(close(a_0.value, bohr.value, 1e-9));
+    assert!(close(
+        a_0.relative_uncertainty(),
+        bohr.relative_uncertainty(),
+        0.05
+    ));
+    assert_eq!(a_0.unit.dimension, Some(LENGTH));
+
+    // k_B/h has the exact constants only
+    let ratio = data.product(&[("k_B", 1), ("h", -1)]).unwrap();
+    assert_eq!(ratio.uncertainty, 0.0);
+}
+
+#[test]
+fn test_parse_listing() {
+    let header = "  Quantity    Value    Uncertainty    Unit\n-----\n";
+    let line = format!(
+        "{:<60}{:<25}{:<25}{}\n",
+        "speed of light in vacuum", "299 792 458", "(exact)", "m s^-1"
+    );
+    let data = Codata::parse(&format!("{}{}", header, line)).unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data.get("c").unwrap().value, 299792458.0);
+
+    let bad = format!("{}{:<60}{:<25}{:<25}\n", header, "broken", "1.2x", "0.1");
+    assert!(matches!(
+        Codata::parse(&bad),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+}
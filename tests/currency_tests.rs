@@ -0,0 +1,200 @@
+use metrology4rs::currency::*;
+use metrology4rs::dimensional::*;
+use metrology4rs::expression::DerivedUnit;
+use metrology4rs::quantity::Quantity;
+use metrology4rs::units::*;
+
+fn unit(name: &str) -> &'static Unit {
+    catalog().get(name).unwrap()
+}
+
+fn kilowatt_hour() -> Unit {
+    DerivedUnit::parse("kW.h").unwrap().to_unit()
+}
+
+const RATES: &str = "\
+# ECB reference rates
+date,base,quote,rate
+2024-03-04,EUR,USD,1.0855
+2024-03-01,EUR,USD,1.0838
+2024-03-01T16:00:00Z,GBP,EUR,1.1685
+2024-03-01,USD,JPY,150.08
+";
+
+#[test]
+fn test_currencies() {
+    assert_eq!(CURRENCIES.len(), 180);
+    let usd = currency("USD").unwrap();
+    assert_eq!(usd.name, "USDollar");
+    assert_eq!(usd.numeric_code, Some("840"));
+    assert_eq!(currency("840"), Some(usd));
+    assert_eq!(currency("Euro").unwrap().code, Some("EUR"));
+    assert_eq!(currency("BHD").unwrap().exponent, Some(3));
+    assert_eq!(currency("XAU").unwrap().exponent, None);
+    assert_eq!(currency("MillionUSDollars").unwrap().code, None);
+    assert!(currency("ABC").is_none());
+
+    assert_eq!(usd.unit().name, "USDollar");
+    assert!(usd.unit().unit_types.contains(&"CurrencyUnit".to_string()));
+    assert_eq!(usd.round(12.345_1), 12.35);
+    // rounded as written, not as the binary value 1.00499…
+    assert_eq!(usd.round(1.005), 1.01);
+    assert_eq!(usd.round(-1.005), -1.01);
+    assert_eq!(usd.round(2.675), 2.68);
+    assert_eq!(usd.round(0.004), 0.0);
+    assert_eq!(usd.round(1e20), 1e20);
+    assert_eq!(currency("JPY").unwrap().round(1234.5), 1235.0);
+    assert_eq!(currency("XAU").unwrap().round(1.23456), 1.23456);
+}
+
+#[test]
+fn test_monetary_dimension() {
+    let amount = Money::of(10.0, "EUR").unwrap();
+    assert_eq!(amount.dimension(), MonetaryDimension::new(1, DIMENSIONLESS));
+    assert_eq!(amount.dimension().to_string(), "¤");
+
+    let tariff = Money::price(0.31, currency("EUR").unwrap(), &kilowatt_hour());
+    assert_eq!(
+        tariff.dimension(),
+        MonetaryDimension::new(1, DIMENSIONLESS / ENERGY)
+    );
+    let per_energy = tariff.dimension() / MonetaryDimension::new(0, DIMENSIONLESS);
+    assert_eq!(per_energy.currency, 1);
+    let energy = MonetaryDimension::new(0, ENERGY);
+    assert_eq!(
+        tariff.dimension() * energy.clone(),
+        MonetaryDimension::new(1, DIMENSIONLESS)
+    );
+    assert_eq!((energy / amount.dimension()).to_string(), "¤^-1L^2MT^-2");
+}
+
+#[test]
+fn test_money_arithmetic() {
+    let eur = currency("EUR").unwrap();
+    assert_eq!(Money::new(0.125, eur).amount, 0.13);
+    assert_eq!(Money::new(0.125, eur).to_string(), "0.13 EUR");
+    assert_eq!(
+        Money::new(1500.0, currency("JPY").unwrap()).to_string(),
+        "1500 JPY"
+    );
+
+    let tariff = Money::price(0.3125, eur, &kilowatt_hour());
+    assert_eq!(tariff.to_string(), "0.3125 EUR/kW·hr");
+    let cost = tariff.times(&Quantity::new(3.6e6, unit("Joule"))).unwrap();
+    assert_eq!(cost, Money::new(0.31, eur));
+    assert!(tariff.times(&Quantity::new(1.0, unit("Meter"))).is_err());
+    assert!(cost.times(&Quantity::new(1.0, unit("Meter"))).is_err());
+
+    let sum = Money::of(1.10, "EUR")
+        .unwrap()
+        .checked_add(&Money::of(2.25, "EUR").unwrap())
+        .unwrap();
+    assert_eq!(sum.amount, 3.35);
+    assert!(sum.checked_add(&Money::of(1.0, "USD").unwrap()).is_err());
+    assert!(sum.checked_add(&tariff).is_err());
+    // 0.0001 EUR/J is 360 EUR/kWh
+    let price = tariff
+        .checked_add(&Money::price(1e-4, eur, unit("Joule")))
+        .unwrap();
+    assert!((price.amount - 360.3125).abs() < 1e-9);
+
+    let run = Money::of(1200.0, "USD").unwrap();
+    let per_sample = run.divide(&Quantity::new(96.0, unit("Number"))).unwrap();
+    assert_eq!(per_sample.amount, 12.5);
+    assert_eq!(
+        per_sample
+            .times(&Quantity::new(10.0, unit("Number")))
+            .unwrap()
+            .amount,
+        125.0
+    );
+    assert!(
+        per_sample
+            .divide(&Quantity::new(1.0, unit("Number")))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_timestamps() {
+    let day: Timestamp = "2024-03-01".parse().unwrap();
+    assert_eq!(day, Timestamp::date(2024, 3, 1));
+    let afternoon: Timestamp = "2024-03-01T16:00:00Z".parse().unwrap();
+    assert!(day < afternoon);
+    assert_eq!(afternoon.to_string(), "2024-03-01T16:00:00Z");
+    assert_eq!("2024-03-01 16:00".parse::<Timestamp>(), Ok(afternoon));
+    assert_eq!(day.to_string(), "2024-03-01");
+    assert!("2024-13-01".parse::<Timestamp>().is_err());
+    assert!("01/03/2024".parse::<Timestamp>().is_err());
+    assert!("2024-03-01T25:00".parse::<Timestamp>().is_err());
+}
+
+#[test]
+fn test_exchange_rates() {
+    let rates = ExchangeRates::from_csv(RATES).unwrap();
+    assert_eq!(rates.len(), 4);
+    let times: Vec<Timestamp> = rates.iter().map(|r| r.at).collect();
+    assert!(times.windows(2).all(|w| w[0] <= w[1]));
+
+    let [eur, usd, gbp, jpy] = ["EUR", "USD", "GBP", "JPY"].map(|c| currency(c).unwrap());
+    let noon = "2024-03-01T12:00:00Z".parse().unwrap();
+    assert_eq!(rates.rate(eur, eur, noon), Ok(1.0));
+    assert_eq!(rates.rate(eur, usd, noon), Ok(1.0838));
+    assert_eq!(rates.rate(usd, eur, noon), Ok(1.0 / 1.0838));
+    assert_eq!(
+        rates.rate(eur, usd, Timestamp::date(2024, 3, 10)),
+        Ok(1.0855)
+    );
+    // quoted at 16:00
+    assert!(rates.rate(gbp, eur, noon).is_err());
+    let evening = "2024-03-01T18:00:00Z".parse().unwrap();
+    assert!((rates.rate(gbp, usd, evening).unwrap() - 1.1685 * 1.0838).abs() < 1e-12);
+    assert!((rates.rate(eur, jpy, noon).unwrap() - 1.0838 * 150.08).abs() < 1e-9);
+    assert_eq!(
+        rates.rate(eur, currency("CHF").unwrap(), noon),
+        Err(UnitError::MissingExchangeRate {
+            from: "EUR".to_string(),
+            to: "CHF".to_string(),
+            at: "2024-03-01T12:00:00Z".to_string(),
+        })
+    );
+
+    let converted = rates.convert(&Money::new(19.99, usd), eur, noon).unwrap();
+    assert_eq!(converted.currency, eur);
+    assert_eq!(converted.amount, 18.44);
+    let price = Money::price(1.799, eur, unit("Liter"));
+    let price = rates.convert(&price, usd, noon).unwrap();
+    assert!((price.amount - 1.799 * 1.0838).abs() < 1e-12);
+    assert_eq!(price.per.unwrap().name, "Liter");
+}
+
+#[test]
+fn test_rate_table_errors() {
+    assert!(ExchangeRates::from_csv("").unwrap().is_empty());
+    assert!(matches!(
+        ExchangeRates::from_csv("date,from,to\n2024-03-01,EUR,USD\n"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert_eq!(
+        ExchangeRates::from_csv("date,from,to,rate\n2024-03-01,EUR,XYZ,1.0\n"),
+        Err(UnitError::UnknownUnit("XYZ".to_string()))
+    );
+    assert!(ExchangeRates::from_csv("date,from,to,rate\n2024-03-01,EUR,USD,-1\n").is_err());
+    assert!(ExchangeRates::from_csv("date,from,to,rate\n2024-03-01,EUR,USD\n").is_err());
+
+    let mut rates = ExchangeRates::new();
+    let (eur, usd) = (currency("EUR").unwrap(), currency("USD").unwrap());
+    rates.insert(ExchangeRate {
+        at: Timestamp::date(2024, 3, 4),
+        from: eur,
+        to: usd,
+        rate: 1.0855,
+    });
+    rates.insert(ExchangeRate {
+        at: Timestamp::date(2024, 3, 1),
+        from: eur,
+        to: usd,
+        rate: 1.0838,
+    });
+    assert_eq!(rates.iter().next().unwrap().rate, 1.0838);
+}
@@ -82,4 +82,220 @@ fn test_solve_dimensional_analysis_case_4() {
         }
         _ => panic!("Expected multiple solutions"),
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_exponents_and_rank() {
+    let sys = DimensionalAnalysis {
+        target: FREQUENCY,
+        dependencies: vec![LENGTH, MASS, ACCELERATION],
+    };
+    assert_eq!(
+        sys.exponents(),
+        Some(vec![
+            Rational32::new(-1, 2),
+            Rational32::from_integer(0),
+            Rational32::new(1, 2)
+        ])
+    );
+    let sys = DimensionalAnalysis {
+        target: FORCE,
+        dependencies: vec![LENGTH, VELOCITY, TIME],
+    };
+    assert_eq!(sys.exponents(), None);
+
+    assert_eq!(dimension_rank(&[LENGTH, VELOCITY, TIME]), 2);
+    assert_eq!(dimension_rank(&[LENGTH, MASS, TIME]), 3);
+    assert_eq!(dimension_rank(&[]), 0);
+}
+
+#[test]
+fn test_pi_groups() {
+    // drag force on a sphere: F = f(ρ, V, D, μ)
+    let viscosity = PRESSURE * TIME;
+    let drag = NamedAnalysis {
+        target: Variable::new("F", FORCE),
+        dependencies: vec![
+            Variable::new("rho", DENSITY),
+            Variable::new("V", VELOCITY),
+            Variable::new("D", LENGTH),
+            Variable::new("mu", viscosity),
+        ],
+    };
+    let groups = drag.pi_groups();
+    assert_eq!(groups.len(), 2);
+    let names = drag.names();
+    assert_eq!(groups[0].format(&names), "F·rho^-1·V^-2·D^-2");
+    assert_eq!(groups[1].format(&names), "rho^-1·V^-1·D^-1·mu");
+    for group in &groups {
+        let product = group
+            .exponents
+            .iter()
+            .zip(drag.analysis().variables())
+            .fold(DIMENSIONLESS, |d, (k, v)| d * v.powi(*k));
+        assert!(product.is_dimensionless());
+    }
+
+    // the period of a pendulum does not depend on the mass
+    let pendulum = DimensionalAnalysis {
+        target: TIME,
+        dependencies: vec![LENGTH, ACCELERATION, MASS],
+    };
+    let groups = pendulum.pi_groups();
+    assert_eq!(
+        groups,
+        vec![PiGroup {
+            exponents: vec![2, -1, 1, 0]
+        }]
+    );
+    assert!(!groups[0].contains(3));
+
+    // a dimensionless dependency is a group on its own
+    let angle = DimensionalAnalysis {
+        target: TIME,
+        dependencies: vec![DIMENSIONLESS, LENGTH, ACCELERATION],
+    };
+    assert_eq!(angle.pi_groups().len(), 2);
+    assert!(angle.pi_groups().contains(&PiGroup {
+        exponents: vec![0, 1, 0, 0]
+    }));
+}
+
+#[test]
+fn test_variable_parse() {
+    assert_eq!(
+        Variable::parse("g", "LT^-2").unwrap().dimension,
+        ACCELERATION
+    );
+    let g = Variable::parse("g", "acceleration").unwrap();
+    assert_eq!(g.dimension, ACCELERATION);
+    assert_eq!(g.dimension.kind(), Some("Acceleration"));
+    assert_eq!(Variable::parse("p", "kPa").unwrap().dimension, PRESSURE);
+    assert_eq!(
+        Variable::parse("n", "N").unwrap().dimension,
+        AMOUNT_OF_SUBSTANCE
+    );
+    assert_eq!(Variable::parse("F", "unit:N").unwrap().dimension, FORCE);
+    assert_eq!(
+        Variable::parse("E", "kind:Torque")
+            .unwrap()
+            .dimension
+            .kind(),
+        Some("Torque")
+    );
+    assert!(Variable::parse("x", "no such thing").is_err());
+    assert!(Variable::parse("x", "dim:m").is_err());
+}
+
+#[test]
+fn test_diagnose() {
+    let pendulum = DimensionalAnalysis {
+        target: TIME,
+        dependencies: vec![LENGTH, ACCELERATION],
+    };
+    assert_eq!(pendulum.diagnose(), None);
+
+    // kinetic energy from density and time: L and M contradict each other,
+    // the exponent of ρ cannot be both 1 and -2/3
+    let energy = DimensionalAnalysis {
+        target: ENERGY,
+        dependencies: vec![DENSITY, TIME],
+    };
+    let diagnosis = energy.diagnose().unwrap();
+    assert_eq!(diagnosis.inconsistent, vec![0, 1]);
+    assert_eq!(diagnosis.missing, MASS);
+    assert_eq!(diagnosis.candidates[0], Candidate::Kind("Mass".to_string()));
+    let fixed = DimensionalAnalysis {
+        dependencies: vec![DENSITY, TIME, diagnosis.missing.clone()],
+        ..energy.clone()
+    };
+    assert!(fixed.exponents().is_some());
+
+    // energy from temperature: only the Boltzmann constant among the
+    // defining constants links them
+    let thermal = NamedAnalysis {
+        target: Variable::new("E", ENERGY),
+        dependencies: vec![Variable::new("T", TEMPERATURE)],
+    };
+    let diagnosis = thermal.diagnose().unwrap();
+    assert_eq!(diagnosis.inconsistent, vec![0, 1, 2]);
+    assert_eq!(diagnosis.missing, ENERGY);
+    assert!(
+        diagnosis
+            .candidates
+            .contains(&Candidate::Kind("EnergyAndWork".to_string()))
+    );
+    let constants: Vec<String> = diagnosis
+        .candidates
+        .iter()
+        .filter(|c| matches!(c, Candidate::Constant { .. }))
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(constants.len(), 3);
+    assert!(constants.contains(&"k_B (Boltzmann constant)".to_string()));
+
+    // a base dimension of the target that no dependency carries
+    let conduction = DimensionalAnalysis {
+        target: Dimension::parse("LMT^-3Θ^-1").unwrap(),
+        dependencies: vec![Dimension::parse("MT^-3").unwrap(), LENGTH],
+    };
+    let diagnosis = conduction.diagnose().unwrap();
+    assert_eq!(diagnosis.inconsistent, vec![4]);
+    assert_eq!(diagnosis.missing, TEMPERATURE);
+    assert!(diagnosis.to_string().starts_with(
+        "the exponent of Θ cannot be matched; add a quantity carrying Θ, e.g. ThermodynamicTemperature, "
+    ));
+}
+
+#[test]
+fn test_redundancy() {
+    let independent = DimensionalAnalysis {
+        target: TIME,
+        dependencies: vec![LENGTH, ACCELERATION],
+    };
+    assert!(independent.redundancy().is_empty());
+    assert_eq!(
+        DimensionalAnalysis {
+            target: TIME,
+            dependencies: vec![]
+        }
+        .redundancy(),
+        Redundancy::default()
+    );
+
+    let drag = NamedAnalysis {
+        target: Variable::new("F", FORCE),
+        dependencies: vec![
+            Variable::new("rho", DENSITY),
+            Variable::new("V", VELOCITY),
+            Variable::new("D", LENGTH),
+            Variable::new("mu", DENSITY * VELOCITY * LENGTH),
+            Variable::new("v", DENSITY.reciprocal()),
+            Variable::new("r", LENGTH),
+            Variable::new("L", LENGTH),
+            Variable::new("phi", DIMENSIONLESS),
+        ],
+    };
+    let redundancy = drag.redundancy();
+    assert_eq!(redundancy.dimensionless, vec![7]);
+    assert_eq!(redundancy.duplicates, vec![vec![2, 5, 6]]);
+    // one product per dependency beyond the rank 3
+    let names = drag.names();
+    let products: Vec<String> = redundancy
+        .dependent
+        .iter()
+        .map(|g| g.format(&names))
+        .collect();
+    assert_eq!(
+        products,
+        vec!["rho^-1·V^-1·D^-1·mu", "rho·v", "D^-1·r", "D^-1·L", "phi"]
+    );
+    assert!(redundancy.dependent.iter().all(|g| !g.contains(0)));
+    assert_eq!(
+        redundancy.format(&names),
+        vec![
+            "phi is dimensionless",
+            "D, r and L have the same dimension",
+            "rho and v are dimensionally dependent: rho·v is dimensionless",
+        ]
+    );
+}
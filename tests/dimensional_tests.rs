@@ -226,3 +226,70 @@ fn test_exponent_manipulation() {
     let length = LENGTH.with_exponent(0, Rational32::new(1, 2));
     assert_eq!(length[0], Rational32::new(1, 2));
 }
+
+// =============================================
+// Quantity Kind Tag Tests
+// =============================================
+
+#[test]
+fn test_kind_tag() {
+    let torque = ENERGY.with_kind("Torque");
+    assert_eq!(torque.kind(), Some("Torque"));
+    assert_eq!(torque, ENERGY);
+    assert!(torque.is_same_kind(&ENERGY));
+    assert!(!torque.is_same_kind(&ENERGY.with_kind("EnergyAndWork")));
+    assert_eq!(torque.clone().with_kind("Work").kind(), Some("Work"));
+    assert_eq!(torque.clone().without_kind().kind(), None);
+
+    // sums keep a common tag, products drop it
+    assert_eq!(
+        (torque.clone() + ENERGY.with_kind("Torque")).kind(),
+        Some("Torque")
+    );
+    assert_eq!(
+        (torque.clone() + ENERGY.with_kind("EnergyAndWork")).kind(),
+        None
+    );
+    assert_eq!((torque.clone() * LENGTH).kind(), None);
+    assert_eq!(
+        FREQUENCY.with_kind("Activity").to_string(),
+        "T^-1 (Activity)"
+    );
+}
+
+// =============================================
+// Parsing Tests
+// =============================================
+
+#[test]
+fn test_parse_dimension() {
+    // the `Display` format round-trips
+    for dimension in [
+        FORCE,
+        ENERGY,
+        DIMENSIONLESS,
+        TEMPERATURE,
+        CAPACITANCE,
+        LENGTH.sqrt(),
+    ] {
+        assert_eq!(Dimension::parse(&dimension.to_string()).unwrap(), dimension);
+    }
+    let torque: Dimension = "L^2MT^-2 (Torque)".parse().unwrap();
+    assert!(torque.is_same_kind(&ENERGY.with_kind("Torque")));
+
+    assert_eq!(Dimension::parse("L·T^-2").unwrap(), ACCELERATION);
+    assert_eq!(Dimension::parse("L T-2").unwrap(), ACCELERATION);
+    assert_eq!(Dimension::parse("L/T^2").unwrap(), ACCELERATION);
+    assert_eq!(Dimension::parse("M/L/T^2").unwrap(), PRESSURE);
+    assert_eq!(Dimension::parse("M L^-1 T^-2").unwrap(), PRESSURE);
+    assert_eq!(Dimension::parse("Theta").unwrap(), TEMPERATURE);
+    assert_eq!(Dimension::parse("1").unwrap(), DIMENSIONLESS);
+    assert_eq!(Dimension::parse("L^(1/2)").unwrap(), LENGTH.sqrt());
+    assert_eq!(Dimension::parse("L^2/T").unwrap(), AREA / TIME);
+
+    assert!(Dimension::parse("").is_err());
+    assert!(Dimension::parse("X").is_err());
+    assert!(Dimension::parse("L^").is_err());
+    assert!(Dimension::parse("L^(1/0)").is_err());
+    assert!(Dimension::parse("L/").is_err());
+}
@@ -0,0 +1,140 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::dimensional_analysis::*;
+use metrology4rs::dimensionless::*;
+use metrology4rs::quantity_kind::quantity_kinds;
+use num::rational::Rational32;
+
+fn variable(name: &str, spec: &str) -> Variable {
+    Variable::parse(name, spec).unwrap()
+}
+
+#[test]
+fn test_library() {
+    let symbols: Vec<&str> = DIMENSIONLESS_NUMBERS.iter().map(|n| n.symbol).collect();
+    for symbol in [
+        "Re", "Ma", "Fr", "We", "St", "Nu", "Pr", "Gr", "Bi", "Pe", "Kn", "Eu",
+    ] {
+        assert!(symbols.contains(&symbol), "{}", symbol);
+    }
+    // every definition is dimensionless and its kinds are QUDT kinds of the
+    // right dimension
+    for number in DIMENSIONLESS_NUMBERS {
+        let mut exponents = [Rational32::from_integer(0); 7];
+        for factor in number.definition {
+            for (e, d) in exponents.iter_mut().zip(factor.dimension.get_exponents()) {
+                *e += d * factor.exponent;
+            }
+            if let Some(kind) = factor.kind {
+                let kind = quantity_kinds()
+                    .get(kind)
+                    .unwrap_or_else(|| panic!("{}", kind));
+                assert_eq!(
+                    kind.dimension.as_ref(),
+                    Some(&factor.dimension),
+                    "{}",
+                    number.symbol
+                );
+            }
+        }
+        assert!(
+            exponents.iter().all(|e| *e == Rational32::from_integer(0)),
+            "{}",
+            number.symbol
+        );
+    }
+    assert_eq!(DimensionlessNumber::lookup("Re").len(), 2);
+    assert_eq!(
+        DimensionlessNumber::lookup("Re")[0].formula(),
+        "rho·V·L·mu^-1"
+    );
+    assert!(DimensionlessNumber::lookup("Xx").is_empty());
+}
+
+#[test]
+fn test_powers() {
+    let pipe = NamedAnalysis {
+        target: variable("dp", "Pressure"),
+        dependencies: vec![
+            variable("rho", "Density"),
+            variable("V", "Velocity"),
+            variable("D", "Length"),
+            variable("mu", "DynamicViscosity"),
+            variable("c", "unit:m/s"),
+        ],
+    };
+    let groups = pipe.pi_groups();
+    let names = pipe.names();
+    assert_eq!(groups[0].format(&names), "dp·rho^-1·V^-2");
+    assert_eq!(describe(&pipe, &groups[0]).unwrap(), "Eu");
+    assert_eq!(describe(&pipe, &groups[1]).unwrap(), "Re⁻¹");
+    // V/c rather than c/V: the names decide
+    assert_eq!(groups[2].format(&names), "V^-1·c");
+    assert_eq!(describe(&pipe, &groups[2]).unwrap(), "Ma⁻¹");
+
+    let mach = &recognize(&pipe, &groups[2])[0];
+    assert_eq!(mach.power, Rational32::from_integer(-1));
+    assert_eq!(mach.assignment, vec![2, 5]);
+
+    let re = &readings(&pipe, &groups[1])[0];
+    assert_eq!(re.number.definition.len(), 4);
+    assert_eq!(re.assignment, vec![1, 2, 3, 4]);
+    assert!(re.score >= 8);
+
+    // a squared or a fractional power
+    let group = PiGroup {
+        exponents: vec![0, 2, 2, 2, -2, 0],
+    };
+    assert_eq!(describe(&pipe, &group).unwrap(), "Re²");
+    let froude = NamedAnalysis {
+        target: variable("V", "LT^-1"),
+        dependencies: vec![variable("g", "LT^-2"), variable("L", "L")],
+    };
+    let group = &froude.pi_groups()[0];
+    assert_eq!(group.format(&froude.names()), "V^2·g^-1·L^-1");
+    assert_eq!(describe(&froude, group).unwrap(), "Fr²");
+    let half = PiGroup {
+        exponents: vec![1, 0, 0],
+    };
+    assert!(recognize(&froude, &half).is_empty());
+}
+
+#[test]
+fn test_heat_transfer() {
+    let convection = NamedAnalysis {
+        target: variable("h", "CoefficientOfHeatTransfer"),
+        dependencies: vec![
+            variable("L", "L"),
+            variable("k", "ThermalConductivity"),
+            variable("rho", "Density"),
+            variable("V", "Velocity"),
+            variable("mu", "DynamicViscosity"),
+            variable("c_p", "SpecificHeatCapacity"),
+        ],
+    };
+    let groups = convection.pi_groups();
+    let readings: Vec<Option<String>> = groups.iter().map(|g| describe(&convection, g)).collect();
+    assert_eq!(readings[0].as_deref(), Some("Nu"));
+    assert_eq!(readings[1].as_deref(), Some("Re⁻¹"));
+    // ρ·c_p·V·L/k is Re·Pr, a product and not a power of one number
+    assert_eq!(groups[2].format(&convection.names()), "L·k^-1·rho·V·c_p");
+    assert_eq!(readings[2], None);
+
+    // without names and kinds, Nusselt and Biot look the same
+    let bare = NamedAnalysis {
+        target: Variable::new("x1", Dimension::parse("MT^-3Θ^-1").unwrap()),
+        dependencies: vec![
+            Variable::new("x2", LENGTH),
+            Variable::new("x3", Dimension::parse("LMT^-3Θ^-1").unwrap()),
+        ],
+    };
+    let group = &bare.pi_groups()[0];
+    assert_eq!(describe(&bare, group).unwrap(), "Nu or Bi");
+    let solid = NamedAnalysis {
+        dependencies: vec![
+            Variable::new("L", LENGTH),
+            Variable::new("k_s", Dimension::parse("LMT^-3Θ^-1").unwrap()),
+        ],
+        ..bare.clone()
+    };
+    assert_eq!(describe(&solid, &solid.pi_groups()[0]).unwrap(), "Bi");
+}
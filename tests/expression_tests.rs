@@ -0,0 +1,127 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::expression::*;
+use metrology4rs::units::*;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+}
+
+#[test]
+fn test_parse_products_and_quotients() {
+    let newton = DerivedUnit::parse("kg·m/s^2").unwrap();
+    assert_eq!(newton.dimension(), Some(FORCE));
+    assert_eq!(newton.conversion_multiplier(), Some(1.0));
+    assert_eq!(newton.factors.len(), 3);
+    assert_eq!(DerivedUnit::parse("kg*m*s^-2").unwrap(), newton);
+    assert_eq!(DerivedUnit::parse("kg.m.s-2").unwrap(), newton);
+    assert_eq!(DerivedUnit::parse("kg m s⁻²").unwrap(), newton);
+
+    let torque = DerivedUnit::parse("N m").unwrap();
+    assert_eq!(torque.dimension(), Some(ENERGY));
+    assert_eq!(torque.to_string(), "N·m");
+
+    let u_value = DerivedUnit::parse("W/(m2.K)").unwrap();
+    assert_eq!(
+        u_value.dimension(),
+        Some(Dimension::PowerLaw(0, 1, -3, 0, -1, 0, 0))
+    );
+    assert_eq!(u_value.to_string(), "W/(m^2·K)");
+    assert_eq!(u_value.name(), "WattPerSquareMeterKelvin");
+
+    let speed = DerivedUnit::parse("km/h").unwrap();
+    assert_eq!(speed.dimension(), Some(VELOCITY));
+    assert!(close(speed.conversion_multiplier().unwrap(), 1.0 / 3.6));
+    assert!(close(
+        speed
+            .convert(36.0, &DerivedUnit::parse("m/s").unwrap())
+            .unwrap(),
+        10.0
+    ));
+}
+
+#[test]
+fn test_parse_merges_and_scales() {
+    let unit = DerivedUnit::parse("m·m/m").unwrap();
+    assert_eq!(unit.symbol(), "m");
+    let hertz = DerivedUnit::parse("1/s").unwrap();
+    assert_eq!(hertz.dimension(), Some(FREQUENCY));
+    assert_eq!(hertz.symbol(), "s^-1");
+    let scaled = DerivedUnit::parse("10^3 m").unwrap();
+    assert_eq!(scaled.conversion_multiplier(), Some(1000.0));
+    assert_eq!(DerivedUnit::parse("m²").unwrap().dimension(), Some(AREA));
+    assert_eq!(
+        DerivedUnit::parse("(m/s)^2").unwrap().dimension(),
+        Some(VELOCITY.powi(2))
+    );
+}
+
+#[test]
+fn test_parse_prefixed_atoms() {
+    let unit = DerivedUnit::parse("kN·mm").unwrap();
+    assert_eq!(unit.dimension(), Some(ENERGY));
+    assert!(close(unit.conversion_multiplier().unwrap(), 1.0));
+    let density = DerivedUnit::parse("g/cm3").unwrap();
+    assert_eq!(density.dimension(), Some(DENSITY));
+    assert!(close(density.conversion_multiplier().unwrap(), 1000.0));
+}
+
+#[test]
+fn test_symbol_resolution() {
+    // SI unit preferred over non-SI units sharing the symbol
+    assert_eq!(resolve_symbol("C").unwrap().unwrap().name, "Coulomb");
+    assert_eq!(resolve_symbol("cd").unwrap().unwrap().name, "Candela");
+    assert_eq!(resolve_symbol("L").unwrap().unwrap().name, "Liter");
+    assert_eq!(resolve_symbol("furlong"), Ok(None));
+    // a unit's own symbol wins over another unit's abbreviation
+    assert_eq!(
+        resolve_symbol("m^-3").unwrap().unwrap().name,
+        "PerCubicMeter"
+    );
+    match DerivedUnit::parse("min^-1") {
+        Err(UnitError::AmbiguousUnit { symbol, candidates }) => {
+            assert_eq!(symbol, "min");
+            assert!(candidates.contains(&"MinuteTime".to_string()));
+        }
+        other => panic!("expected an ambiguity error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        DerivedUnit::parse("m/furlong"),
+        Err(UnitError::UnknownUnit("furlong".to_string()))
+    );
+    assert!(matches!(
+        DerivedUnit::parse("W/(m2.K"),
+        Err(UnitError::InvalidExpression { position: 7, .. })
+    ));
+    assert!(matches!(
+        DerivedUnit::parse("m^"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        DerivedUnit::parse(""),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn test_to_unit() {
+    let units = catalog();
+    let speed = DerivedUnit::parse("km/h").unwrap().to_unit();
+    assert_eq!(speed.name, "KilometerPerHour");
+
+    let u_value = DerivedUnit::parse("W/(m2.K)").unwrap().to_unit();
+    assert_eq!(&u_value, units.get("WattPerSquareMeterKelvin").unwrap());
+
+    let synthesized = DerivedUnit::parse("kN/mm").unwrap().to_unit();
+    assert_eq!(synthesized.name, "KiloNewtonPerMillimeter");
+    assert_eq!(synthesized.display_symbol(), "kN/mm");
+    assert_eq!(synthesized.conversion_multiplier, Some(1e6));
+    assert!(synthesized.has_type("SIUnit"));
+    assert_eq!(
+        DerivedUnit::parse("Pa").unwrap().to_unit(),
+        units.get("Pascal").unwrap().clone()
+    );
+}
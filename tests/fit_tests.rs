@@ -0,0 +1,197 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::dimensional_analysis::*;
+use metrology4rs::fit::*;
+use metrology4rs::units::UnitError;
+
+fn drag() -> NamedAnalysis {
+    NamedAnalysis {
+        target: Variable::new("F", FORCE),
+        dependencies: vec![
+            Variable::new("rho", DENSITY),
+            Variable::new("V", VELOCITY),
+            Variable::new("D", LENGTH),
+            Variable::new("mu", DENSITY * VELOCITY * LENGTH),
+        ],
+    }
+}
+
+// F = C·ρV²D²·(μ/ρVD)^a with a multiplicative error `noise`
+fn drag_data(c: f64, a: f64, noise: &[f64]) -> DataTable {
+    let speeds = [1.0, 2.0, 5.0, 10.0, 20.0, 50.0];
+    let (rho, d, mu) = (1.2, 0.1, 1.8e-5);
+    let forces: Vec<f64> = speeds
+        .iter()
+        .zip(noise)
+        .map(|(v, e)| c * rho * v * v * d * d * (mu / (rho * v * d)).powf(a) * e)
+        .collect();
+    DataTable::new(vec![
+        Column::new("F", "mN", forces.iter().map(|f| f * 1000.0).collect()).unwrap(),
+        Column::new("rho", "kg/m^3", vec![rho; 6]).unwrap(),
+        Column::new("V", "m/s", speeds.to_vec()).unwrap(),
+        Column::new("D", "cm", vec![d * 100.0; 6]).unwrap(),
+        Column::new("mu", "Pa.s", vec![mu; 6]).unwrap(),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn test_exact_fit() {
+    let analysis = drag();
+    let fit = fit_power_law(&analysis, &drag_data(0.5, 0.25, &[1.0; 6])).unwrap();
+    assert_eq!(
+        fit.groups[0].format(&analysis.names()),
+        "F·rho^-1·V^-2·D^-2"
+    );
+    assert_eq!(
+        fit.groups[1].format(&analysis.names()),
+        "rho^-1·V^-1·D^-1·mu"
+    );
+    assert!((fit.coefficient.value - 0.5).abs() < 1e-9);
+    assert!((fit.exponents[0].value - 0.25).abs() < 1e-9);
+    assert!(fit.exponents[0].standard_error < 1e-6);
+    assert!(fit.residuals.iter().all(|r| r.abs() < 1e-9));
+    assert!((fit.r_squared - 1.0).abs() < 1e-12);
+    assert_eq!(fit.degrees_of_freedom, 4);
+    assert!((fit.predict(&[1e-4]) - 0.5 * 0.1).abs() < 1e-9);
+}
+
+#[test]
+fn test_noisy_fit() {
+    let noise = [1.02, 0.97, 1.01, 0.99, 1.03, 0.98];
+    let fit = fit_power_law(&drag(), &drag_data(0.5, 0.25, &noise)).unwrap();
+    let (a, c) = (fit.exponents[0], fit.coefficient);
+    assert!(a.standard_error > 0.0 && c.standard_error > 0.0);
+    assert!((a.value - 0.25).abs() < 3.0 * a.standard_error);
+    assert!((c.value - 0.5).abs() < 3.0 * c.standard_error);
+    assert!(fit.r_squared > 0.99 && fit.r_squared < 1.0);
+    // the residuals of a fit with an intercept sum to zero
+    assert!(fit.residuals.iter().sum::<f64>().abs() < 1e-12);
+    let rss: f64 = fit.residuals.iter().map(|r| r * r).sum();
+    assert!((fit.residual_std - (rss / 4.0).sqrt()).abs() < 1e-15);
+    assert!(fit.relation().starts_with("Π1 = 0."));
+    assert!(fit.relation().contains("·Π2^0.2"));
+}
+
+#[test]
+fn test_parse() {
+    let table = DataTable::parse(
+        "
+# run, temperature
+n, T [degC], L [mm]
+1, 20, 100.0
+
+2, 30, 100.1
+",
+    )
+    .unwrap();
+    assert_eq!(table.rows(), 2);
+    assert_eq!(
+        table.column("n").unwrap().unit,
+        metrology4rs::expression::DerivedUnit::one()
+    );
+    let kelvin = table.column("T").unwrap().si_values().unwrap();
+    assert!((kelvin[0] - 293.15).abs() < 1e-9);
+    assert_eq!(table.column("L").unwrap().values, vec![100.0, 100.1]);
+
+    assert!(matches!(
+        DataTable::parse("a, b\n1"),
+        Err(FitError::InvalidData { line: 2, .. })
+    ));
+    assert!(matches!(
+        DataTable::parse("a\nx"),
+        Err(FitError::InvalidData { line: 2, .. })
+    ));
+    assert!(matches!(
+        DataTable::parse(""),
+        Err(FitError::InvalidData { line: 0, .. })
+    ));
+    assert!(matches!(
+        DataTable::parse("a [furlongs]"),
+        Err(FitError::Unit(UnitError::UnknownUnit(_)))
+    ));
+    let ragged = DataTable::new(vec![
+        Column::new("a", "", vec![1.0, 2.0]).unwrap(),
+        Column::new("b", "", vec![1.0]).unwrap(),
+    ]);
+    assert!(matches!(
+        ragged,
+        Err(FitError::RaggedColumn {
+            rows: 1,
+            expected: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_errors() {
+    let analysis = drag();
+    let data = drag_data(0.5, 0.25, &[1.0; 6]);
+
+    let mut missing = data.clone();
+    missing.columns.retain(|c| c.name != "mu");
+    assert_eq!(
+        fit_power_law(&analysis, &missing),
+        Err(FitError::MissingVariable("mu".to_string()))
+    );
+
+    let mut wrong = data.clone();
+    wrong.columns[2] = Column::new("V", "m", vec![1.0; 6]).unwrap();
+    assert!(matches!(
+        fit_power_law(&analysis, &wrong),
+        Err(FitError::WrongDimension { .. })
+    ));
+
+    let mut negative = data.clone();
+    negative.columns[2].values[3] = -10.0;
+    assert_eq!(
+        fit_power_law(&analysis, &negative),
+        Err(FitError::NonPositive { group: 1, row: 3 })
+    );
+
+    // with V·D constant the Reynolds number does not vary
+    let mut constant = data.clone();
+    constant.columns[3].values = constant.columns[2]
+        .values
+        .iter()
+        .map(|v| 10.0 / v)
+        .collect();
+    assert_eq!(fit_power_law(&analysis, &constant), Err(FitError::Singular));
+
+    // nor when it varies only by rounding
+    let mut nearly = data.clone();
+    nearly.columns[3].values = constant.columns[2]
+        .values
+        .iter()
+        .enumerate()
+        .map(|(row, v)| 10.0 * (1.0 + 1e-12 * row as f64) / v)
+        .collect();
+    assert_eq!(fit_power_law(&analysis, &nearly), Err(FitError::Singular));
+
+    let short = DataTable::new(
+        data.columns
+            .iter()
+            .map(|c| Column {
+                values: c.values[..2].to_vec(),
+                ..c.clone()
+            })
+            .collect(),
+    )
+    .unwrap();
+    assert_eq!(
+        fit_power_law(&analysis, &short),
+        Err(FitError::TooFewRows {
+            rows: 2,
+            parameters: 2
+        })
+    );
+
+    let infeasible = NamedAnalysis {
+        target: Variable::new("F", FORCE),
+        dependencies: vec![Variable::new("V", VELOCITY), Variable::new("D", LENGTH)],
+    };
+    assert_eq!(
+        fit_power_law(&infeasible, &data),
+        Err(FitError::NoTargetGroup)
+    );
+}
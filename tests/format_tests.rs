@@ -0,0 +1,158 @@
+use metrology4rs::format::*;
+use metrology4rs::quantity::Quantity;
+use metrology4rs::units::catalog;
+
+#[test]
+fn test_round_to_uncertainty() {
+    let r = round_to_uncertainty(12.34567, 0.01234, 2).unwrap();
+    assert_eq!(
+        r,
+        RoundedValue {
+            value: 12346,
+            uncertainty: 12,
+            exponent: -3
+        }
+    );
+
+    let r = round_to_uncertainty(12.34567, 0.01234, 1).unwrap();
+    assert_eq!(
+        r,
+        RoundedValue {
+            value: 1235,
+            uncertainty: 1,
+            exponent: -2
+        }
+    );
+
+    // the uncertainty rounds up into the next decade
+    let r = round_to_uncertainty(1.2345, 0.0996, 2).unwrap();
+    assert_eq!(
+        r,
+        RoundedValue {
+            value: 123,
+            uncertainty: 10,
+            exponent: -2
+        }
+    );
+    let r = round_to_uncertainty(1.2345, 0.096, 1).unwrap();
+    assert_eq!(
+        r,
+        RoundedValue {
+            value: 12,
+            uncertainty: 1,
+            exponent: -1
+        }
+    );
+
+    assert_eq!(round_to_uncertainty(1.0, 0.0, 2), None);
+    // more digits than an f64 carries
+    assert_eq!(round_to_uncertainty(1e30, 1e-10, 2), None);
+
+    assert_eq!(round_significant(0.1 + 0.2, 12), 0.3);
+    assert_eq!(round_significant(22.063225248000002, 12), 22.063225248);
+    assert_eq!(round_significant(-1234.5, 2), -1200.0);
+    assert_eq!(round_significant(0.0, 3), 0.0);
+}
+
+#[test]
+fn test_format_scaled() {
+    assert_eq!(format_scaled(12345, -3, false), "12.345");
+    assert_eq!(format_scaled(-5, -3, false), "-0.005");
+    assert_eq!(format_scaled(123, 2, false), "12300");
+    assert_eq!(
+        format_scaled(1674927471, -9, true),
+        "1.674\u{2009}927\u{2009}471"
+    );
+    assert_eq!(
+        format_scaled(12345678, -1, true),
+        "1\u{2009}234\u{2009}567.8"
+    );
+    assert_eq!(format_scaled(12345, -1, true), "1234.5");
+}
+
+#[test]
+fn test_concise_format() {
+    let mm = catalog().get("Millimeter").unwrap();
+    let q = Quantity::new(12.34549, mm).with_uncertainty(0.0123);
+    assert_eq!(q.format(&FormatOptions::concise()), "12.345(12) mm");
+    assert_eq!(q.to_string(), "12.345(12) mm");
+    assert_eq!(
+        q.format(&FormatOptions::concise().with_uncertainty_digits(1)),
+        "12.35(1) mm"
+    );
+
+    // the uncertainty is written in units of the last digit shown
+    let m = catalog().get("Meter").unwrap();
+    let q = Quantity::new(12345.0, m).with_uncertainty(1234.0);
+    assert_eq!(q.format(&FormatOptions::concise()), "12300(1200) m");
+}
+
+#[test]
+fn test_plus_minus_format() {
+    let mm = catalog().get("Millimeter").unwrap();
+    let q = Quantity::new(12.3451, mm).with_uncertainty(0.012);
+    assert_eq!(
+        q.format(&FormatOptions::plus_minus(2.0)),
+        "(12.345 ± 0.024) mm, k=2"
+    );
+    assert_eq!(
+        q.format(&FormatOptions::plus_minus(1.0)),
+        "(12.345 ± 0.012) mm, k=1"
+    );
+}
+
+#[test]
+fn test_si_brochure_format() {
+    let kg = catalog().get("Kilogram").unwrap();
+    let q = Quantity::new(1.674927471e-27, kg).with_uncertainty(0.000000021e-27);
+    assert_eq!(
+        q.format(&FormatOptions::si_brochure().with_engineering_notation()),
+        "1.674\u{2009}927\u{2009}471(21)×10^-27 kg"
+    );
+}
+
+#[test]
+fn test_auto_prefix() {
+    let m = catalog().get("Meter").unwrap();
+    let q = Quantity::new(0.012345, m).with_uncertainty(0.000012);
+    assert_eq!(
+        q.format(&FormatOptions::concise().with_auto_prefix()),
+        "12.345(12) mm"
+    );
+
+    let hz = catalog().get("Hertz").unwrap();
+    let f = Quantity::new(2.4e9, hz).with_uncertainty(1.5e6);
+    assert_eq!(
+        f.format(&FormatOptions::concise().with_auto_prefix()),
+        "2.4000(15) GHz"
+    );
+
+    // no prefix for kilogram or units with an offset, fall back to a power of ten
+    let celsius = catalog().get("DegreeCelsius").unwrap();
+    let t = Quantity::new(1234.5, celsius).with_uncertainty(1.2);
+    assert_eq!(
+        t.format(&FormatOptions::concise().with_auto_prefix()),
+        "1.2345(12)×10^3 degC"
+    );
+}
+
+#[test]
+fn test_format_without_uncertainty() {
+    let m = catalog().get("Meter").unwrap();
+    assert_eq!(Quantity::new(2.5, m).to_string(), "2.5 m");
+    assert_eq!(
+        Quantity::new(2500.0, m).format(&FormatOptions::plus_minus(2.0).with_auto_prefix()),
+        "2.5 km"
+    );
+}
+
+#[test]
+fn test_format_unresolvable_uncertainty() {
+    let m = catalog().get("Meter").unwrap();
+    let q = Quantity::new(1e30, m).with_uncertainty(1e-10);
+    assert_eq!(q.format(&FormatOptions::concise()), "(1e30 ± 1e-10) m");
+    assert_eq!(
+        q.format(&FormatOptions::plus_minus(2.0)),
+        "(1e30 ± 2e-10) m, k=2"
+    );
+}
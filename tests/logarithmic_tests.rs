@@ -0,0 +1,179 @@
+use metrology4rs::expression::DerivedUnit;
+use metrology4rs::logarithmic::*;
+use metrology4rs::quantity::Quantity;
+use metrology4rs::units::{Unit, catalog};
+
+fn unit(expression: &str) -> Unit {
+    DerivedUnit::parse(expression).unwrap().to_unit()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+}
+
+#[test]
+fn test_units() {
+    assert_eq!(LogarithmicUnit::lookup("Np"), Some(NEPER));
+    assert_eq!(LogarithmicUnit::lookup("Decibel"), Some(DECIBEL));
+    assert!(DECIBEL.applies_to(LevelKind::RootPower));
+    assert!(!DECIBEL.applies_to(LevelKind::Ratio));
+    assert!(OCTAVE.applies_to(LevelKind::Ratio));
+
+    let one_bel = LogarithmicQuantity::new(1.0, BEL, LevelKind::Power).unwrap();
+    assert!(close(one_bel.convert_to(DECIBEL).unwrap().level, 10.0));
+    assert!(close(one_bel.ratio(), 10.0));
+    let one_neper = LogarithmicQuantity::new(1.0, NEPER, LevelKind::RootPower).unwrap();
+    assert!(close(one_neper.ratio(), std::f64::consts::E));
+    assert!(close(
+        one_neper.convert_to(DECIBEL).unwrap().level,
+        20.0 / 10f64.ln()
+    ));
+    assert!(one_neper.convert_to(OCTAVE).is_err());
+    assert!(LogarithmicQuantity::new(1.0, OCTAVE, LevelKind::Power).is_err());
+
+    let dbuv = Reference::lookup("dBuV").unwrap();
+    assert_eq!(dbuv.symbol, "dBµV");
+    assert_eq!(dbuv.quantity().unit.name, "MicroVolt");
+    assert!(Reference::lookup("dBq").is_none());
+}
+
+#[test]
+fn test_linear_conversion() {
+    let watts = Quantity::new(2.0, &unit("W")).with_uncertainty(0.02);
+    let dbm = Reference::lookup("dBm").unwrap().quantity();
+    let level = LogarithmicQuantity::from_linear(&watts, &dbm, LevelKind::Power, DECIBEL).unwrap();
+    assert!(close(level.level, 10.0 * 2000f64.log10()));
+    assert!(close(level.uncertainty, 10.0 / 10f64.ln() * 0.01));
+    assert_eq!(level.symbol(), "dBm");
+    let back = level.to_linear().unwrap();
+    assert_eq!(back.unit.name, "MilliWatt");
+    assert!(close(back.value, 2000.0));
+    assert!(close(back.uncertainty, 20.0));
+
+    // 1 Pa is about 94 dB SPL
+    let pressure = Quantity::new(1.0, &unit("Pa"));
+    let spl = Reference::lookup("dB SPL").unwrap();
+    let level =
+        LogarithmicQuantity::from_linear(&pressure, &spl.quantity(), spl.kind, spl.unit).unwrap();
+    assert!((level.level - 93.98).abs() < 0.01);
+    assert_eq!(level.named_reference(), Some(spl));
+
+    let custom = Quantity::new(10.0, &unit("mW"));
+    let level = LogarithmicQuantity::from_linear(&dbm, &custom, LevelKind::Power, NEPER).unwrap();
+    assert_eq!(level.to_string(), format!("{} Np re 10 mW", level.level));
+    assert!(LogarithmicQuantity::from_linear(&pressure, &dbm, LevelKind::Power, DECIBEL).is_err());
+    assert!(
+        LogarithmicQuantity::from_linear(
+            &Quantity::new(0.0, &unit("mW")),
+            &dbm,
+            LevelKind::Power,
+            DECIBEL
+        )
+        .is_err()
+    );
+    let gain = LogarithmicQuantity::new(3.0, DECIBEL, LevelKind::Power).unwrap();
+    assert!(gain.to_linear().is_err());
+}
+
+#[test]
+fn test_ratios() {
+    let hz = unit("Hz");
+    let interval = LogarithmicQuantity::from_linear(
+        &Quantity::new(1760.0, &hz),
+        &Quantity::new(440.0, &hz),
+        LevelKind::Ratio,
+        OCTAVE,
+    )
+    .unwrap();
+    assert!(close(interval.level, 2.0));
+    assert!(close(
+        interval.convert_to(DECADE).unwrap().level,
+        4f64.log10()
+    ));
+    assert!(interval.convert_to(DECIBEL).is_err());
+
+    let acid = Quantity::new(1e-3, &unit("mol/L"));
+    let reference = Reference::lookup("pH").unwrap().quantity();
+    let ph = LogarithmicQuantity::from_linear(&acid, &reference, LevelKind::Ratio, PH).unwrap();
+    assert!(close(ph.level, 3.0));
+    assert_eq!(ph.symbol(), "pH");
+    let neutral = LogarithmicQuantity::parse_level(7.0, "pH").unwrap();
+    assert!(close(neutral.to_linear().unwrap().value, 1e-7));
+    assert_eq!(neutral.to_string(), "pH 7");
+    assert!(neutral.checked_add(&neutral).is_err());
+}
+
+#[test]
+fn test_level_arithmetic() {
+    let a = LogarithmicQuantity::parse_level(60.0, "dB SPL")
+        .unwrap()
+        .with_uncertainty(1.0);
+    let b = LogarithmicQuantity::parse_level(60.0, "dB SPL").unwrap();
+    let sum = a.checked_add(&b).unwrap();
+    assert!(close(sum.level, 60.0 + 10.0 * 2f64.log10()));
+    assert!(close(sum.uncertainty, 0.5));
+    let quiet = LogarithmicQuantity::parse_level(40.0, "dB SPL").unwrap();
+    assert!((a.checked_add(&quiet).unwrap().level - 60.0432).abs() < 1e-4);
+
+    let levels: Vec<_> = [80.0, 85.0, 90.0]
+        .iter()
+        .map(|l| LogarithmicQuantity::parse_level(*l, "dBm").unwrap())
+        .collect();
+    let total = LogarithmicQuantity::sum(&levels).unwrap();
+    let milliwatts = 1e8 + 10f64.powf(8.5) + 1e9;
+    assert!(close(total.level, 10.0 * milliwatts.log10()));
+    assert!(LogarithmicQuantity::sum(&[]).is_err());
+
+    // 0 dBW is 30 dBm
+    let watt = LogarithmicQuantity::parse_level(0.0, "dBW").unwrap();
+    let milliwatt = LogarithmicQuantity::parse_level(0.0, "dBm").unwrap();
+    assert!(close(
+        watt.checked_add(&milliwatt).unwrap().level,
+        10.0 * 1.001f64.log10()
+    ));
+    assert!(close(milliwatt.difference(&watt).unwrap().level, -30.0));
+    assert!(milliwatt.checked_add(&a).is_err());
+    assert!(
+        milliwatt
+            .checked_add(&LogarithmicQuantity::new(1.0, DECIBEL, LevelKind::Power).unwrap())
+            .is_err()
+    );
+
+    let gain = LogarithmicQuantity::new(1.0, NEPER, LevelKind::RootPower).unwrap();
+    let dbuv = LogarithmicQuantity::parse_level(20.0, "dBµV").unwrap();
+    let amplified = dbuv.amplify(&gain).unwrap();
+    assert!(close(
+        amplified.to_linear().unwrap().value,
+        10.0 * std::f64::consts::E
+    ));
+    assert!(dbuv.amplify(&milliwatt).is_err());
+}
+
+#[test]
+fn test_qudt_units() {
+    let units = catalog();
+    let dbm = Quantity::new(-47.0, units.get("DecibelReferredToOneMilliwatt").unwrap())
+        .with_uncertainty(0.5);
+    let level = LogarithmicQuantity::from_quantity(&dbm).unwrap();
+    assert_eq!(level.to_string(), "-47 dBm");
+    assert_eq!(level.uncertainty, 0.5);
+    assert_eq!(
+        level.qudt_unit().unwrap().name,
+        "DecibelReferredToOneMilliwatt"
+    );
+
+    let carrier = Quantity::new(-60.0, units.get("DecibelCarrier").unwrap());
+    let level = LogarithmicQuantity::from_quantity(&carrier).unwrap();
+    assert_eq!(level.reference, None);
+    assert_eq!(level.qudt_unit().unwrap().name, "Decibel");
+    assert!(
+        LogarithmicQuantity::from_quantity(&Quantity::new(1.0, units.get("Meter").unwrap()))
+            .is_err()
+    );
+    assert_eq!(
+        LogarithmicQuantity::parse_level(1.0, "Np")
+            .unwrap()
+            .qudt_unit(),
+        None
+    );
+}
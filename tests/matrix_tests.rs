@@ -1,4 +1,4 @@
-use metrology4rs::matrix::Matrix;
+use metrology4rs::matrix::{Matrix, RowOperation};
 use num::BigInt;
 use num::rational::Rational32;
 use num::traits::FromPrimitive;
@@ -12,6 +12,11 @@ fn test_matrix_creation() {
     // Test empty matrix
     let empty_mat: Matrix<Rational32> = Matrix::new(0, 0);
     assert_eq!(empty_mat.dimensions(), (0, 0));
+
+    let identity: Matrix<f64> = Matrix::identity(3);
+    assert_eq!(identity.dimensions(), (3, 3));
+    assert_eq!(identity[(1, 1)], 1.0);
+    assert_eq!(identity[(1, 2)], 0.0);
 }
 
 #[test]
@@ -321,7 +326,7 @@ fn test_row_echelon_form() {
     ]);
     println!("Original matrix:");
     println!("{}", mat);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     println!("Reduced row echelon form:");
     println!("{}", mat);
     assert_eq!(rank, 2); // This matrix has rank 2
@@ -334,12 +339,12 @@ fn test_row_echelon_form() {
 
     // Test case 2: Matrix with zero rows
     let mut empty_mat: Matrix<Rational32> = Matrix::new(0, 0);
-    let rank = empty_mat.to_row_echelon_form();
+    let rank = empty_mat.to_row_echelon_form().rank;
     assert_eq!(rank, 0);
 
     // Test case 3: Matrix with all zeros
     let mut zero_mat: Matrix<Rational32> = Matrix::new(3, 3);
-    let rank = zero_mat.to_row_echelon_form();
+    let rank = zero_mat.to_row_echelon_form().rank;
     assert_eq!(rank, 0);
 
     // Test case 4: Matrix with linearly dependent rows
@@ -349,7 +354,7 @@ fn test_row_echelon_form() {
     ]);
     println!("Original matrix:");
     println!("{}", mat);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     println!("Reduced row echelon form:");
     println!("{}", mat);
     assert_eq!(rank, 1); // Rows are linearly dependent
@@ -365,7 +370,7 @@ fn test_row_echelon_form() {
     ]);
     println!("Original matrix:");
     println!("{}", mat);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     println!("Reduced row echelon form:");
     println!("{}", mat);
     assert_eq!(rank, 2); // Full rank
@@ -394,7 +399,7 @@ fn test_row_echelon_form() {
     ]);
     println!("Original matrix:");
     println!("{}", mat);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     println!("Reduced row echelon form:");
     println!("{}", mat);
     assert_eq!(rank, 2); // Rank is 2
@@ -639,7 +644,7 @@ fn test_empty_matrix_operations() {
 fn test_row_echelon_edge_cases() {
     // Test with 1x1 matrix
     let mut mat = Matrix::from_rows(vec![vec![Rational32::from_integer(2)]]);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     assert_eq!(rank, 1);
     assert_eq!(mat[(0, 0)], Rational32::from_integer(1));
 
@@ -656,7 +661,7 @@ fn test_row_echelon_edge_cases() {
             Rational32::from_integer(6),
         ],
     ]);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
     assert_eq!(rank, 2);
 
     // Test with tall matrix (more rows than columns)
@@ -665,8 +670,120 @@ fn test_row_echelon_edge_cases() {
         vec![Rational32::from_integer(3), Rational32::from_integer(4)],
         vec![Rational32::from_integer(5), Rational32::from_integer(6)],
     ]);
-    let rank = mat.to_row_echelon_form();
+    let rank = mat.to_row_echelon_form().rank;
+    assert_eq!(rank, 2);
+
+    // A zero first column must not use up a pivot row
+    let r = |rows: Vec<Vec<i32>>| {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|row| row.into_iter().map(Rational32::from_integer).collect())
+                .collect(),
+        )
+    };
+    let mut mat = r(vec![vec![0, 1, 2], vec![0, 2, 3]]);
+    let rank = mat.to_row_echelon_form().rank;
     assert_eq!(rank, 2);
+    assert_eq!(mat, r(vec![vec![0, 1, 0], vec![0, 0, 1]]));
+}
+
+#[test]
+fn test_rref_result() {
+    let r = |rows: Vec<Vec<i32>>| {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|row| row.into_iter().map(Rational32::from_integer).collect())
+                .collect(),
+        )
+    };
+
+    // a zero column, a dependent column and a dependent row
+    let original = r(vec![vec![0, 1, 2, 1], vec![0, 2, 4, 3], vec![0, 1, 2, 2]]);
+    let mut mat = original.clone();
+    let rref = mat.to_row_echelon_form();
+    assert_eq!(rref.rank, 2);
+    assert_eq!(rref.pivots, vec![1, 3]);
+    assert_eq!(rref.free, vec![0, 2]);
+    assert_eq!(rref.pivot_row(3), Some(1));
+    assert_eq!(rref.pivot_row(2), None);
+    assert_eq!(
+        mat,
+        r(vec![vec![0, 1, 2, 0], vec![0, 0, 0, 1], vec![0, 0, 0, 0]])
+    );
+
+    // the largest entry is taken as pivot, so the first operation is a swap
+    assert_eq!(rref.operations[0], RowOperation::Swap(0, 1));
+    assert_eq!(rref.operations[0].to_string(), "R1 ↔ R2");
+    assert_eq!(
+        rref.operations[1],
+        RowOperation::Scale {
+            row: 0,
+            factor: Rational32::new(1, 2)
+        }
+    );
+    assert_eq!(rref.operations[1].to_string(), "R1 ← 1/2·R1");
+    assert_eq!(rref.operations[2].to_string(), "R2 ← R2 + -1·R1");
+
+    // replaying the operations reproduces the reduced form
+    let mut replay = original.clone();
+    for operation in &rref.operations {
+        operation.apply(&mut replay);
+    }
+    assert_eq!(replay, mat);
+
+    // E·A is the reduced form
+    let (rows, cols) = original.dimensions();
+    for i in 0..rows {
+        for j in 0..cols {
+            let product: Rational32 = (0..rows)
+                .map(|k| rref.transform[(i, k)] * original[(k, j)])
+                .sum();
+            assert_eq!(product, mat[(i, j)]);
+        }
+    }
+
+    // already reduced: no operations, the identity
+    let mut identity: Matrix<Rational32> = Matrix::identity(2);
+    let rref = identity.to_row_echelon_form();
+    assert!(rref.operations.is_empty());
+    assert_eq!(rref.transform, Matrix::identity(2));
+    assert_eq!(rref.pivots, vec![0, 1]);
+
+    let mut empty: Matrix<Rational32> = Matrix::new(0, 0);
+    let rref = empty.to_row_echelon_form();
+    assert_eq!((rref.rank, rref.pivots.len(), rref.free.len()), (0, 0, 0));
+    let mut zero: Matrix<Rational32> = Matrix::new(2, 3);
+    assert_eq!(zero.to_row_echelon_form().free, vec![0, 1, 2]);
+}
+
+#[test]
+fn test_null_space() {
+    let r = |rows: Vec<Vec<i32>>| {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|row| row.into_iter().map(Rational32::from_integer).collect())
+                .collect(),
+        )
+    };
+    let int = Rational32::from_integer;
+
+    // rows L and T of length, acceleration and time
+    let mat = r(vec![vec![1, 1, 0], vec![0, -2, 1]]);
+    let basis = mat.null_space();
+    assert_eq!(
+        basis,
+        vec![vec![Rational32::new(-1, 2), Rational32::new(1, 2), int(1)]]
+    );
+
+    // a zero column is free on its own
+    let mat = r(vec![vec![0, 1, 1]]);
+    assert_eq!(
+        mat.null_space(),
+        vec![vec![int(1), int(0), int(0)], vec![int(0), int(-1), int(1)]]
+    );
+
+    // full column rank
+    assert!(r(vec![vec![1, 0], vec![0, 1]]).null_space().is_empty());
 }
 
 #[test]
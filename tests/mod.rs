@@ -1,8 +1,50 @@
+#[cfg(all(test, feature = "cli"))]
+pub mod cli_tests;
+#[cfg(test)]
+pub mod constants_tests;
+#[cfg(test)]
+pub mod currency_tests;
 #[cfg(test)]
 pub mod dimensional_analysis_tests;
 #[cfg(test)]
 pub mod dimensional_tests;
 #[cfg(test)]
+pub mod dimensionless_tests;
+#[cfg(test)]
+pub mod expression_tests;
+#[cfg(test)]
+pub mod fit_tests;
+#[cfg(test)]
+pub mod format_tests;
+#[cfg(test)]
+pub mod logarithmic_tests;
+#[cfg(test)]
 pub mod matrix_tests;
 #[cfg(test)]
-pub mod units_tests;
\ No newline at end of file
+pub mod natural_units_tests;
+#[cfg(test)]
+pub mod prefix_tests;
+#[cfg(all(test, feature = "problem"))]
+pub mod problem_tests;
+#[cfg(test)]
+pub mod quantity_kind_tests;
+#[cfg(test)]
+pub mod quantity_tests;
+#[cfg(test)]
+pub mod rdf_tests;
+#[cfg(test)]
+pub mod repl_tests;
+#[cfg(all(test, feature = "serde"))]
+pub mod serde_tests;
+#[cfg(test)]
+pub mod similitude_tests;
+#[cfg(test)]
+pub mod turtle_tests;
+#[cfg(test)]
+pub mod ucum_tests;
+#[cfg(test)]
+pub mod unece_tests;
+#[cfg(test)]
+pub mod unit_system_tests;
+#[cfg(test)]
+pub mod units_tests;
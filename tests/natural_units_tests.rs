@@ -0,0 +1,133 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::natural_units::*;
+use metrology4rs::quantity::Quantity;
+use metrology4rs::units::*;
+use num::rational::Rational32;
+
+fn close(a: f64, b: f64, rel: f64) -> bool {
+    (a - b).abs() <= rel * b.abs()
+}
+
+#[test]
+fn test_particle_physics_units() {
+    let hep = NaturalUnitSystem::particle_physics();
+    assert_eq!(hep.effective_dimensions(), 4);
+
+    let mass = hep.reduce(&MASS).unwrap();
+    assert_eq!(mass.power("c"), Rational32::from_integer(-2));
+    assert_eq!(mass.power("ħ"), Rational32::from_integer(0));
+    assert_eq!(mass.to_string(), "GigaElectronVolt·c^-2");
+
+    let length = hep.reduce(&LENGTH).unwrap();
+    assert_eq!(length.to_string(), "GigaElectronVolt^-1·c·ħ");
+
+    // half-integer dimensions reduce to half-integer powers
+    let root = hep.reduce(&LENGTH.sqrt()).unwrap();
+    assert_eq!(root.power("ħ"), Rational32::new(1, 2));
+    assert_eq!(root.power("c"), Rational32::new(1, 2));
+    assert!(matches!(
+        hep.natural_unit(&LENGTH.sqrt()),
+        Err(UnitError::NotInSystem { .. })
+    ));
+
+    let units = catalog();
+    let electron = Quantity::new(9.1093837015e-31, units.get("Kilogram").unwrap());
+    let electron = hep.to_natural(&electron).unwrap();
+    assert_eq!(electron.unit.name, "GigaElectronVolt");
+    // QUDT 1.1 still has the CODATA 2006 electronvolt
+    assert!(close(electron.value, 0.51099895e-3, 1e-6));
+
+    // ħc = 0.1973 GeV·fm
+    let fermi = Quantity::new(1e-15, units.get("Meter").unwrap()).with_uncertainty(1e-17);
+    let fermi = hep.to_natural(&fermi).unwrap();
+    assert!(close(fermi.value, 1.0 / 0.1973269804, 1e-6));
+    assert!(close(fermi.uncertainty, 0.01 / 0.1973269804, 1e-6));
+    let back = hep
+        .from_natural(&fermi, units.get("Meter").unwrap())
+        .unwrap();
+    assert!(close(back.value, 1e-15, 1e-12));
+    assert!(close(back.uncertainty, 1e-17, 1e-12));
+
+    // k_B = 1: room temperature is about 1/40 eV
+    let room = Quantity::new(25.0, units.get("DegreeCelsius").unwrap());
+    let room = hep.to_natural(&room).unwrap();
+    assert!(close(room.value, 298.15 * 8.617333262e-5 * 1e-9, 1e-6));
+}
+
+#[test]
+fn test_planck_units() {
+    let planck = NaturalUnitSystem::planck();
+    assert_eq!(planck.effective_dimensions(), 2);
+    let length = planck.reduce(&LENGTH).unwrap();
+    assert_eq!(length.power("ħ"), Rational32::new(1, 2));
+    assert_eq!(length.power("G"), Rational32::new(1, 2));
+    assert_eq!(length.power("c"), Rational32::new(-3, 2));
+    assert_eq!(length.power("k_B"), Rational32::from_integer(0));
+
+    let units = catalog();
+    let planck_length = units.get("PlanckLength").unwrap();
+    let one = planck
+        .to_natural(&Quantity::new(1.0, planck_length))
+        .unwrap();
+    assert_eq!(one.unit.name, "Unitless");
+    assert!(close(one.value, 1.0, 1e-4));
+
+    let second = planck
+        .from_natural(
+            &Quantity::new(1.0, &one.unit),
+            units.get("SecondTime").unwrap(),
+        )
+        .unwrap();
+    assert!(close(second.value, 5.391247e-44, 1e-5));
+}
+
+#[test]
+fn test_atomic_units() {
+    let atomic = NaturalUnitSystem::atomic();
+    assert_eq!(atomic.effective_dimensions(), 3);
+    let units = catalog();
+    let bohr = Quantity::new(5.29177210903e-11, units.get("Meter").unwrap());
+    assert!(close(atomic.to_natural(&bohr).unwrap().value, 1.0, 1e-8));
+    let hartree = Quantity::new(4.3597447222071e-18, units.get("Joule").unwrap());
+    assert!(close(atomic.to_natural(&hartree).unwrap().value, 1.0, 1e-8));
+    assert!(matches!(
+        atomic.to_natural(&Quantity::new(1.0, units.get("Kelvin").unwrap())),
+        Err(UnitError::NotInSystem { .. })
+    ));
+}
+
+#[test]
+fn test_custom_natural_units() {
+    let units = catalog();
+    // c = 1 with metres and kilograms: times in metres
+    let relativity = NaturalUnitSystem::new(
+        "geometrized",
+        vec![speed_of_light()],
+        vec![
+            units.get("Meter").unwrap().clone(),
+            units.get("Kilogram").unwrap().clone(),
+        ],
+    )
+    .unwrap();
+    let second = Quantity::new(1.0, units.get("SecondTime").unwrap());
+    let second = relativity.to_natural(&second).unwrap();
+    assert_eq!(second.unit.name, "Meter");
+    assert_eq!(second.value, 299792458.0);
+
+    assert!(
+        NaturalUnitSystem::new(
+            "dependent",
+            vec![speed_of_light()],
+            vec![
+                units.get("Meter").unwrap().clone(),
+                units.get("SecondTime").unwrap().clone(),
+            ]
+        )
+        .is_err()
+    );
+    let mut twice = speed_of_light();
+    twice.symbol = "c'";
+    assert!(
+        NaturalUnitSystem::new("dependent", vec![speed_of_light(), twice], Vec::new()).is_err()
+    );
+}
@@ -0,0 +1,121 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::prefix::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_prefixes_from_qudt() {
+    assert_eq!(
+        prefixes()
+            .iter()
+            .filter(|p| p.kind == PrefixKind::Decimal)
+            .count(),
+        20
+    );
+    assert_eq!(
+        prefixes()
+            .iter()
+            .filter(|p| p.kind == PrefixKind::Binary)
+            .count(),
+        8
+    );
+
+    let micro = find_prefix("µ").unwrap();
+    assert_eq!(micro.name, "Micro");
+    assert_eq!(micro.exponent, -6);
+    // Greek mu is accepted for the micro sign
+    assert_eq!(find_prefix("μ"), Some(micro));
+    assert_eq!(find_prefix("kilo").unwrap().symbol, "k");
+    assert_eq!(find_prefix("da").unwrap().exponent, 1);
+
+    // QUDT 1.1 lists a wrong multiplier for Pebi
+    let pebi = find_prefix("Pi").unwrap();
+    assert_eq!(pebi.exponent, 50);
+    assert_eq!(pebi.factor(), 1125899906842624.0);
+    assert_eq!(decimal_prefix(9).unwrap().symbol, "G");
+    assert_eq!(decimal_prefix(4), None);
+}
+
+#[test]
+fn test_apply_prefix() {
+    let units = catalog();
+    let micro_newton = find_prefix("Micro")
+        .unwrap()
+        .apply(units.get("Newton").unwrap())
+        .unwrap();
+    assert_eq!(micro_newton.name, "MicroNewton");
+    assert_eq!(micro_newton.display_symbol(), "µN");
+    assert_eq!(micro_newton.conversion_multiplier, Some(1e-6));
+    assert_eq!(micro_newton.dimension, Some(FORCE));
+    assert!(micro_newton.is_si());
+    assert!(!micro_newton.has_type("SIDerivedUnit"));
+
+    // existing prefixed units come from the catalog
+    let km = find_prefix("k")
+        .unwrap()
+        .apply(units.get("Meter").unwrap())
+        .unwrap();
+    assert_eq!(&km, units.get("Kilometer").unwrap());
+
+    let mg = find_prefix("m")
+        .unwrap()
+        .apply(units.get("Gram").unwrap())
+        .unwrap();
+    assert!((mg.conversion_multiplier.unwrap() - 1e-6).abs() < 1e-20);
+    assert_eq!(mg.dimension, Some(MASS));
+}
+
+#[test]
+fn test_rejected_prefixes() {
+    let units = catalog();
+    let kilo = find_prefix("k").unwrap();
+    let rejected = |prefix: &Prefix, unit: &str| {
+        matches!(
+            prefix.apply(units.get(unit).unwrap()),
+            Err(UnitError::PrefixNotAllowed { .. })
+        )
+    };
+    assert!(rejected(kilo, "Kilogram"));
+    assert!(rejected(kilo, "Millimeter"));
+    assert!(rejected(kilo, "Kilo"));
+    assert!(rejected(kilo, "DegreeCelsius"));
+    assert!(rejected(kilo, "Hour"));
+    assert!(rejected(kilo, "Inch"));
+    assert!(rejected(find_prefix("Gi").unwrap(), "Meter"));
+    assert!(!rejected(kilo, "Liter"));
+    assert!(!rejected(kilo, "Byte"));
+}
+
+#[test]
+fn test_parse_prefixed() {
+    let units = catalog();
+    let gib = parse_prefixed("GiB").unwrap();
+    assert_eq!(gib.name, "GibiByte");
+    assert_eq!(
+        gib.convert(1.0, units.get("Byte").unwrap()).unwrap(),
+        1073741824.0
+    );
+
+    let torque = parse_prefixed("µN·m").unwrap();
+    assert_eq!(torque.conversion_multiplier, Some(1e-6));
+    assert_eq!(torque.dimension, Some(ENERGY));
+    assert_eq!(parse_prefixed("μN m").unwrap(), torque);
+
+    // catalog symbols win over prefix splitting
+    assert!(parse_prefixed("min").unwrap().name.starts_with("Minute"));
+    assert_eq!(
+        parse_prefixed("dam").unwrap().conversion_multiplier,
+        Some(10.0)
+    );
+    assert_eq!(
+        parse_prefixed("kkg"),
+        Err(UnitError::PrefixNotAllowed {
+            prefix: "Kilo".to_string(),
+            unit: "Kilogram".to_string(),
+            reason: "prefixes for mass are applied to the gram".to_string()
+        })
+    );
+    assert_eq!(
+        parse_prefixed("qq"),
+        Err(UnitError::UnknownUnit("qq".to_string()))
+    );
+}
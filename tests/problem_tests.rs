@@ -0,0 +1,239 @@
+#![cfg(feature = "problem")]
+use metrology4rs::dimensional::*;
+use metrology4rs::dimensional_analysis::*;
+use metrology4rs::problem::*;
+use metrology4rs::units::UnitError;
+use std::fs;
+
+const PENDULUM: &str = r#"
+title = "Simple pendulum"
+target = { name = "t", unit = "s", description = "period" }
+dependencies = [
+    { name = "l", unit = "m" },
+    { name = "g", kind = "acceleration" },
+    "m = M",
+]
+"#;
+
+#[test]
+fn test_declarations() {
+    let problem = Problem::from_toml(PENDULUM).unwrap();
+    assert_eq!(problem.title.as_deref(), Some("Simple pendulum"));
+    let analysis = problem.analysis().unwrap();
+    assert_eq!(analysis.names(), vec!["t", "l", "g", "m"]);
+    assert_eq!(analysis.target.dimension, TIME);
+    assert_eq!(analysis.dependencies[1].dimension, ACCELERATION);
+    assert_eq!(
+        analysis.dependencies[1].dimension.kind(),
+        Some("Acceleration")
+    );
+    assert_eq!(analysis.dependencies[2].dimension, MASS);
+
+    let declarations: Vec<String> = std::iter::once(&problem.target)
+        .chain(&problem.dependencies)
+        .map(VariableSpec::declaration)
+        .collect();
+    assert_eq!(
+        declarations,
+        vec!["unit: s", "unit: m", "kind: acceleration", "M"]
+    );
+    assert_eq!(problem.target.description(), Some("period"));
+
+    // the same problem in YAML and JSON
+    let yaml = Problem::from_yaml(
+        "
+target: {name: t, unit: s}
+dependencies:
+  - {name: l, dimension: L}
+  - g = LT^-2
+  - {name: m, dimension: {M: 1}}
+",
+    )
+    .unwrap();
+    assert_eq!(yaml.analysis().unwrap(), analysis);
+    let json = Problem::from_json(
+        r#"{"target": "t = T", "dependencies": ["l = L", "g = acceleration", "m = M"]}"#,
+    )
+    .unwrap();
+    assert_eq!(json.analysis().unwrap(), analysis);
+
+    // a serialized NamedAnalysis is a problem file
+    let text = toml::to_string(&analysis).unwrap();
+    assert_eq!(
+        Problem::from_toml(&text).unwrap().analysis().unwrap(),
+        analysis
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        Problem::from_toml("target = \"t = T\"\ndependecies = []"),
+        Err(ProblemError::Parse(_))
+    ));
+    assert!(matches!(
+        Problem::from_yaml("target: [1, 2]\ndependencies: []"),
+        Err(ProblemError::Parse(_))
+    ));
+
+    let both =
+        Problem::from_yaml("target: {name: x, dimension: L, unit: m}\ndependencies: []").unwrap();
+    assert!(matches!(both.solve(), Err(ProblemError::Variable { ref name, .. }) if name == "x"));
+    let unknown = Problem::from_yaml("target: x = nothing\ndependencies: []").unwrap();
+    assert!(matches!(
+        unknown.analysis(),
+        Err(ProblemError::Variable {
+            error: UnitError::InvalidExpression { .. },
+            ..
+        })
+    ));
+    let kind = Problem::from_yaml("target: {name: x, kind: NoSuchKind}\ndependencies: []").unwrap();
+    assert!(matches!(
+        kind.analysis(),
+        Err(ProblemError::Variable {
+            error: UnitError::UnknownQuantityKind(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_reports() {
+    let report = Problem::from_toml(PENDULUM).unwrap().solve().unwrap();
+    assert_eq!(report.relation(), "unique solution: t = C·l^1/2·g^-1/2");
+    assert_eq!(
+        report.pi_groups,
+        vec![PiGroup {
+            exponents: vec![2, -1, 1, 0]
+        }]
+    );
+
+    let json = report.to_json();
+    assert_eq!(json["title"], "Simple pendulum");
+    assert_eq!(json["exponents"]["l"], "1/2");
+    assert_eq!(json["solution"]["type"], "unique_solution");
+
+    let markdown = report.to_markdown();
+    assert!(markdown.starts_with("## Simple pendulum\n"));
+    assert!(markdown.contains("| variable | declared as | dimension | description |"));
+    assert!(markdown.contains("| t | unit: s | T | period |"));
+    assert!(markdown.contains("**Unique solution**: t = C·l^1/2·g^-1/2"));
+
+    let latex = report.to_latex();
+    assert!(latex.starts_with("\\section*{Simple pendulum}"));
+    assert!(latex.contains("$g$ & kind: acceleration & $\\mathsf{L}\\,\\mathsf{T}^{-2}$ \\\\"));
+    assert!(latex.contains("\\[ t = C\\,l^{1/2}\\,g^{-1/2} \\]"));
+
+    let none = Report::new(NamedAnalysis {
+        target: Variable::new("E_k", ENERGY),
+        dependencies: vec![Variable::new("rho", DENSITY), Variable::new("t", TIME)],
+    });
+    assert_eq!(none.solution, DimensionalAnalysisSolution::NoSolution);
+    assert_eq!(report.diagnosis, None);
+    assert!(report.redundancy.is_empty());
+    assert!(!report.to_markdown().contains("Redundant"));
+    assert_eq!(none.diagnosis.as_ref().unwrap().missing, MASS);
+    assert_eq!(
+        none.to_json()["diagnosis"]["inconsistent"],
+        serde_json::json!(["L", "M"])
+    );
+    let tank = Report::new(NamedAnalysis {
+        target: Variable::new("p", PRESSURE),
+        dependencies: vec![
+            Variable::new("h", LENGTH),
+            Variable::new("d", LENGTH),
+            Variable::new("rho", DENSITY),
+        ],
+    });
+    assert_eq!(tank.redundancy.duplicates, vec![vec![0, 1]]);
+    assert_eq!(
+        tank.to_json()["redundancy"]["duplicates"],
+        serde_json::json!([["h", "d"]])
+    );
+    assert!(
+        tank.to_markdown()
+            .contains("\nRedundant dependencies:\n\n- h and d have the same dimension\n")
+    );
+    assert!(
+        tank.to_latex()
+            .contains("\\item h and d have the same dimension\n")
+    );
+    assert!(none.to_markdown().contains(
+        "\nThe exponents of L and M cannot be matched; add a quantity carrying M, e.g. Mass"
+    ));
+    let tank = Report::new(NamedAnalysis {
+        target: Variable::new("p", PRESSURE),
+        dependencies: vec![
+            Variable::new("h", LENGTH),
+            Variable::new("d", LENGTH),
+            Variable::new("rho", DENSITY),
+        ],
+    });
+    assert_eq!(tank.redundancy.duplicates, vec![vec![0, 1]]);
+    assert_eq!(
+        tank.to_json()["redundancy"]["duplicates"],
+        serde_json::json!([["h", "d"]])
+    );
+    assert!(
+        tank.to_markdown()
+            .contains("\nRedundant dependencies:\n\n- h and d have the same dimension\n")
+    );
+    assert!(
+        tank.to_latex()
+            .contains("\\item h and d have the same dimension\n")
+    );
+    assert!(
+        none.to_markdown()
+            .contains("**No solution**: E_k is not a power law")
+    );
+    assert!(
+        none.to_latex()
+            .contains("$E_{\\mathrm{k}}$ & L\\textasciicircum{}2MT\\textasciicircum{}-2")
+    );
+    assert!(none.to_latex().contains("$\\rho$"));
+}
+
+#[test]
+fn test_batch() {
+    let dir = std::env::temp_dir().join("metrology4rs_problem_tests");
+    fs::create_dir_all(&dir).unwrap();
+    let pendulum = dir.join("pendulum.toml");
+    fs::write(&pendulum, PENDULUM).unwrap();
+    let drag = dir.join("drag.yaml");
+    fs::write(
+        &drag,
+        "target: F = force\ndependencies: [rho = density, V = m/s, D = L, mu = DynamicViscosity]\n",
+    )
+    .unwrap();
+
+    let reports = solve_files(&[&pendulum, &drag]).unwrap();
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[1].name(), "drag");
+    assert_eq!(reports[1].pi_groups.len(), 2);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&write_reports(&reports, ReportFormat::Json)).unwrap();
+    assert_eq!(json[1]["pi_groups"][1]["product"], "rho^-1·V^-1·D^-1·mu");
+    assert_eq!(json[1]["pi_groups"][1]["reading"], "Re⁻¹");
+    assert_eq!(reports[1].reading(0), None);
+    assert!(json[1]["source"].as_str().unwrap().ends_with("drag.yaml"));
+    let markdown = write_reports(&reports, ReportFormat::Markdown);
+    assert!(markdown.contains("\n## drag\n"));
+    assert!(markdown.contains("| Π2 | rho^-1·V^-1·D^-1·mu | Re⁻¹ |"));
+    let latex = write_reports(&reports, ReportFormat::Latex);
+    assert!(latex.contains("\\Pi_{1} &= F\\,\\rho^{-1}\\,V^{-2}\\,D^{-2}"));
+    assert!(latex.contains("= \\mathrm{Re}^{-1}"));
+
+    assert_eq!("md".parse(), Ok(ReportFormat::Markdown));
+    assert_eq!("LaTeX".parse(), Ok(ReportFormat::Latex));
+    assert!("html".parse::<ReportFormat>().is_err());
+
+    let missing = dir.join("missing.toml");
+    match solve_files(&[&pendulum, &missing]) {
+        Err(ProblemError::InFile { path, error }) => {
+            assert!(path.ends_with("missing.toml"));
+            assert!(matches!(*error, ProblemError::Io(_)));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
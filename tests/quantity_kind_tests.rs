@@ -0,0 +1,100 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::quantity_kind::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_graph_loads() {
+    let kinds = quantity_kinds();
+    assert!(kinds.len() > 200);
+    let torque = kinds.get("Torque").unwrap();
+    assert_eq!(torque.dimension, Some(ENERGY));
+    assert!(
+        torque
+            .categories
+            .contains(&"MechanicsQuantityKind".to_string())
+    );
+    assert!(
+        torque
+            .exact_matches
+            .contains(&"http://dbpedia.org/resource/Torque".to_string())
+    );
+    assert_eq!(
+        kinds.get("http://qudt.org/vocab/quantity#Torque"),
+        Some(torque)
+    );
+    let links: usize = kinds.iter().map(|k| k.generalizations.len()).sum();
+    assert_eq!(links, 81);
+}
+
+#[test]
+fn test_ancestors_and_descendants() {
+    let kinds = quantity_kinds();
+    let heat = kinds.get("Heat").unwrap();
+    let names = |ks: Vec<&QuantityKind>| ks.iter().map(|k| k.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(heat.ancestors()), ["ThermalEnergy", "EnergyAndWork"]);
+    // Activity -> StochasticProcess -> Frequency
+    assert_eq!(
+        names(kinds.get("Activity").unwrap().ancestors()),
+        ["StochasticProcess", "Frequency"]
+    );
+
+    let energy = kinds.get("EnergyAndWork").unwrap();
+    let descendants = names(energy.descendants());
+    assert!(descendants.contains(&"ThermalEnergy".to_string()));
+    assert!(descendants.contains(&"Heat".to_string()));
+    assert!(!descendants.contains(&"Torque".to_string()));
+    assert!(kinds.get("Torque").unwrap().ancestors().is_empty());
+}
+
+#[test]
+fn test_applicable_units() {
+    let kinds = quantity_kinds();
+    let force = kinds.get("Force").unwrap().applicable_units();
+    assert!(force.iter().any(|u| u.name == "Newton"));
+    assert!(force.iter().any(|u| u.name == "PoundForce"));
+    // no units are linked to Thrust itself, they come from Force
+    let thrust = kinds.get("Thrust").unwrap().applicable_units();
+    assert_eq!(thrust.len(), force.len());
+    let torque = kinds.get("Torque").unwrap().applicable_units();
+    assert!(torque.iter().any(|u| u.name == "NewtonMeter"));
+    assert!(!torque.iter().any(|u| u.name == "Joule"));
+}
+
+#[test]
+fn test_compatibility() {
+    let kinds = quantity_kinds();
+    let get = |name: &str| kinds.get(name).unwrap();
+    assert!(get("Thrust").is_compatible_with(get("Force")));
+    assert!(get("Force").is_compatible_with(get("Thrust")));
+    assert!(get("Heat").is_compatible_with(get("EnergyAndWork")));
+    assert!(!get("Torque").is_compatible_with(get("EnergyAndWork")));
+    assert!(!get("Heat").is_compatible_with(get("KineticEnergy")));
+    // QUDT links AngularMomentum to Momentum, the dimensions disagree
+    assert!(!get("AngularMomentum").is_compatible_with(get("Momentum")));
+}
+
+#[test]
+fn test_check_conversion() {
+    let units = catalog();
+    let get = |name: &str| units.get(name).unwrap();
+    let warning = check_conversion(get("NewtonMeter"), get("Joule")).unwrap();
+    assert_eq!(warning.from_kinds, ["Torque"]);
+    assert_eq!(warning.to_kinds, ["EnergyAndWork"]);
+    assert!(warning.to_string().contains("unrelated quantity kinds"));
+
+    assert_eq!(check_conversion(get("Joule"), get("Kilocalorie")), None);
+    assert_eq!(check_conversion(get("Newton"), get("PoundForce")), None);
+    // incommensurable units are reported by `Unit::convert`, not here
+    assert_eq!(check_conversion(get("Newton"), get("Joule")), None);
+}
+
+#[test]
+fn test_generated_kinds_match_ontology() {
+    let triples =
+        metrology4rs::rdf::parse_rdf_xml(include_str!("../src/ontology/qudt-quantity")).unwrap();
+    let parsed = QuantityKindGraph::from_triples(&triples);
+    assert_eq!(parsed.len(), QUANTITY_KINDS.len());
+    for (parsed, generated) in parsed.iter().zip(quantity_kinds().iter()) {
+        assert_eq!(parsed, generated);
+    }
+}
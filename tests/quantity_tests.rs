@@ -0,0 +1,220 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::quantity::*;
+use metrology4rs::units::*;
+
+fn unit(name: &str) -> &'static Unit {
+    catalog().get(name).unwrap()
+}
+
+#[test]
+fn test_kind_tagging() {
+    let q = Quantity::new(5.0, unit("NewtonMeter"))
+        .with_kind("Torque")
+        .unwrap();
+    assert_eq!(q.kind.as_deref(), Some("Torque"));
+    assert_eq!(q.dimension().unwrap().kind(), Some("Torque"));
+    assert_eq!(q.dimension(), Some(ENERGY));
+
+    assert_eq!(
+        q.clone().with_kind("EnergyAndWork"),
+        Err(UnitError::KindMismatch {
+            from: "Torque".to_string(),
+            to: "EnergyAndWork".to_string()
+        })
+    );
+    assert_eq!(
+        Quantity::new(1.0, unit("Meter")).with_kind("Mass"),
+        Err(UnitError::IncompatibleDimensions {
+            from: "Meter".to_string(),
+            to: "Mass".to_string()
+        })
+    );
+    assert_eq!(
+        Quantity::new(1.0, unit("Meter")).with_kind("Nonsense"),
+        Err(UnitError::UnknownQuantityKind("Nonsense".to_string()))
+    );
+    assert_eq!(
+        q.cast("EnergyAndWork").unwrap().kind.as_deref(),
+        Some("EnergyAndWork")
+    );
+}
+
+#[test]
+fn test_strict_conversion() {
+    let torque = Quantity::new(3.0, unit("NewtonMeter"))
+        .with_kind("Torque")
+        .unwrap();
+    assert!(
+        torque
+            .checked_convert_to(unit("Joule"), KindMode::Strict)
+            .is_err()
+    );
+    let lenient = torque
+        .checked_convert_to(unit("Joule"), KindMode::Lenient)
+        .unwrap();
+    assert_eq!(lenient.value, 3.0);
+    assert_eq!(lenient.kind.as_deref(), Some("Torque"));
+
+    let activity = Quantity::new(10.0, unit("Becquerel"))
+        .with_kind("Activity")
+        .unwrap();
+    assert!(
+        activity
+            .checked_convert_to(unit("Hertz"), KindMode::Strict)
+            .is_err()
+    );
+    assert!(
+        activity
+            .checked_convert_to(unit("Curie"), KindMode::Strict)
+            .is_ok()
+    );
+
+    // newtons apply to a thrust
+    let thrust = Quantity::new(1.0, unit("PoundForce"))
+        .with_kind("Thrust")
+        .unwrap();
+    let newtons = thrust
+        .checked_convert_to(unit("Newton"), KindMode::Strict)
+        .unwrap();
+    assert!((newtons.value - 4.448222).abs() < 1e-6);
+
+    // untagged quantities are never rejected
+    let plain = Quantity::new(3.0, unit("NewtonMeter"));
+    assert!(
+        plain
+            .checked_convert_to(unit("Joule"), KindMode::Strict)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_strict_arithmetic() {
+    let a = Quantity::new(2.0, unit("Joule"))
+        .with_uncertainty(0.3)
+        .with_kind("EnergyAndWork")
+        .unwrap();
+    let b = Quantity::new(1.0, unit("Joule"))
+        .with_uncertainty(0.4)
+        .with_kind("Heat")
+        .unwrap();
+    let sum = a.checked_add(&b, KindMode::Strict).unwrap();
+    assert_eq!(sum.value, 3.0);
+    assert!((sum.uncertainty - 0.5).abs() < 1e-12);
+    assert_eq!(sum.kind.as_deref(), Some("EnergyAndWork"));
+    assert_eq!(a.checked_sub(&b, KindMode::Strict).unwrap().value, 1.0);
+
+    let torque = Quantity::new(1.0, unit("NewtonMeter"))
+        .with_kind("Torque")
+        .unwrap();
+    assert!(matches!(
+        a.checked_add(&torque, KindMode::Strict),
+        Err(UnitError::KindMismatch { .. })
+    ));
+    let mixed = a.checked_add(&torque, KindMode::Lenient).unwrap();
+    assert_eq!(mixed.value, 3.0);
+    assert_eq!(mixed.kind, None);
+
+    // an explicit cast makes the mix acceptable
+    let work = torque.cast("EnergyAndWork").unwrap();
+    assert_eq!(a.checked_add(&work, KindMode::Strict).unwrap().value, 3.0);
+
+    let length = Quantity::new(1.0, unit("Meter"));
+    assert!(matches!(
+        a.checked_add(&length, KindMode::Lenient),
+        Err(UnitError::IncompatibleDimensions { .. })
+    ));
+}
+
+#[test]
+fn test_affine_temperatures() {
+    let (celsius, fahrenheit, kelvin) = (
+        unit("DegreeCelsius"),
+        unit("DegreeFahrenheit"),
+        unit("Kelvin"),
+    );
+    assert_eq!(celsius.interval_unit().name, "Kelvin");
+    assert_eq!(fahrenheit.interval_unit().name, "DegreeRankine");
+    assert_eq!(kelvin.interval_unit(), kelvin.clone());
+    assert_eq!(Quantity::new(1.0, unit("Meter")).affine, Affine::Interval);
+    assert_eq!(Quantity::new(1.0, kelvin).affine, Affine::Absolute);
+
+    let point = Quantity::new(68.0, fahrenheit).with_uncertainty(0.9);
+    let in_celsius = point.convert_to(celsius).unwrap();
+    assert!((in_celsius.value - 20.0).abs() < 1e-9);
+    assert!((in_celsius.uncertainty - 0.5).abs() < 1e-12);
+    let interval = Quantity::interval(9.0, fahrenheit)
+        .convert_to(kelvin)
+        .unwrap();
+    assert!((interval.value - 5.0).abs() < 1e-12);
+    assert_eq!(interval.affine, Affine::Interval);
+
+    // the difference of two points is an interval, uncertainties combined
+    let a = Quantity::new(25.0, celsius).with_uncertainty(0.03);
+    let b = Quantity::new(20.0, celsius).with_uncertainty(0.04);
+    let rise = a.checked_sub(&b, KindMode::Lenient).unwrap();
+    assert_eq!(rise.unit.name, "Kelvin");
+    assert_eq!(rise.affine, Affine::Interval);
+    assert!((rise.value - 5.0).abs() < 1e-12);
+    assert!((rise.uncertainty - 0.05).abs() < 1e-12);
+    let mixed = a
+        .checked_sub(&Quantity::new(293.15, kelvin), KindMode::Lenient)
+        .unwrap();
+    assert!((mixed.value - 5.0).abs() < 1e-9);
+    let rankine = Quantity::new(77.0, fahrenheit)
+        .checked_sub(&Quantity::new(68.0, fahrenheit), KindMode::Lenient);
+    assert_eq!(rankine.unwrap().unit.name, "DegreeRankine");
+
+    // point ± interval is a point in the unit of the point
+    let warmer = b
+        .checked_add(&Quantity::interval(9.0, fahrenheit), KindMode::Lenient)
+        .unwrap();
+    assert_eq!(warmer.affine, Affine::Absolute);
+    assert!((warmer.value - 25.0).abs() < 1e-12);
+    let cooler = b
+        .checked_sub(&Quantity::interval(5.0, kelvin), KindMode::Lenient)
+        .unwrap();
+    assert!((cooler.value - 15.0).abs() < 1e-12);
+    let shifted = Quantity::interval(5.0, kelvin)
+        .checked_add(&b, KindMode::Lenient)
+        .unwrap();
+    assert_eq!(shifted.affine, Affine::Absolute);
+    assert!((shifted.value - 298.15).abs() < 1e-9);
+    let intervals = rise
+        .checked_add(&Quantity::interval(1.0, celsius), KindMode::Lenient)
+        .unwrap();
+    assert!((intervals.value - 6.0).abs() < 1e-12);
+
+    assert!(matches!(
+        a.checked_add(&b, KindMode::Lenient),
+        Err(UnitError::AffineMismatch { .. })
+    ));
+    assert!(matches!(
+        rise.checked_sub(&b, KindMode::Lenient),
+        Err(UnitError::AffineMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_power_product() {
+    use metrology4rs::matrix::Matrix;
+
+    let m = unit("Meter");
+    let a = Quantity::new(3.0, m).with_uncertainty(0.1);
+    let b = Quantity::new(2.0, m).with_uncertainty(0.2);
+    let area = power_product(&[(&a, 1), (&b, 1)], &Matrix::identity(2));
+    assert_eq!(area.value, 6.0);
+    assert!((area.uncertainty - 0.2f64.hypot(0.6)).abs() < 1e-12);
+
+    // a zero factor has a finite uncertainty
+    let zero = Quantity::new(0.0, m).with_uncertainty(0.1);
+    let area = power_product(&[(&zero, 1), (&b, 1)], &Matrix::identity(2));
+    assert_eq!(area.value, 0.0);
+    assert!((area.uncertainty - 0.2).abs() < 1e-12);
+
+    // absolute temperatures enter in kelvin
+    let t = Quantity::new(10.0, unit("DegreeCelsius")).with_uncertainty(0.5);
+    let product = power_product(&[(&t, 1), (&b, 1)], &Matrix::identity(2));
+    assert!((product.value - 566.3).abs() < 1e-9);
+    assert!(product.unit.display_symbol().contains('K'));
+    assert!((product.uncertainty - 1.0f64.hypot(283.15 * 0.2)).abs() < 1e-9);
+}
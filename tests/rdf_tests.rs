@@ -0,0 +1,105 @@
+use metrology4rs::rdf::*;
+
+const DOC: &str = r##"<?xml version="1.0"?>
+<!-- a small QUDT-like document -->
+<rdf:RDF
+    xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+    xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+    xmlns:qudt="http://qudt.org/schema/qudt#"
+  xml:base="http://qudt.org/vocab/unit" >
+  <rdf:Description rdf:about="#Meter">
+    <qudt:symbol rdf:datatype="http://www.w3.org/2001/XMLSchema#string">m</qudt:symbol>
+    <rdf:type rdf:resource="http://qudt.org/schema/qudt#SIBaseUnit"/>
+    <rdfs:comment xml:lang="en">length &amp; distance&#xD;
+unit</rdfs:comment>
+  </rdf:Description>
+  <qudt:LengthUnit rdf:about="http://example.org/units#Span">
+    <qudt:conversionMultiplier rdf:nodeID="A0"/>
+  </qudt:LengthUnit>
+</rdf:RDF>
+"##;
+
+#[test]
+fn test_parse_rdf_xml() {
+    let triples = parse_rdf_xml(DOC).unwrap();
+    assert_eq!(triples.len(), 5);
+
+    let meter = Term::Iri("http://qudt.org/vocab/unit#Meter".to_string());
+    assert_eq!(triples[0].subject, meter);
+    assert_eq!(triples[0].predicate, format!("{}symbol", QUDT_NS));
+    assert_eq!(
+        triples[0].object,
+        Term::Literal {
+            value: "m".to_string(),
+            datatype: Some("http://www.w3.org/2001/XMLSchema#string".to_string()),
+            lang: None,
+        }
+    );
+    assert_eq!(triples[1].predicate, RDF_TYPE);
+    assert_eq!(
+        triples[1].object.as_iri(),
+        Some("http://qudt.org/schema/qudt#SIBaseUnit")
+    );
+    assert_eq!(
+        triples[2].object,
+        Term::Literal {
+            value: "length & distance\nunit".to_string(),
+            datatype: None,
+            lang: Some("en".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_typed_node_element() {
+    let triples = parse_rdf_xml(DOC).unwrap();
+    let span = Term::Iri("http://example.org/units#Span".to_string());
+    assert_eq!(triples[3].subject, span);
+    assert_eq!(triples[3].predicate, RDF_TYPE);
+    assert_eq!(
+        triples[3].object.as_iri(),
+        Some("http://qudt.org/schema/qudt#LengthUnit")
+    );
+    assert_eq!(triples[4].object, Term::BlankNode("A0".to_string()));
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = parse_rdf_xml("<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n<x:Thing/>\n</rdf:RDF>")
+        .unwrap_err();
+    assert_eq!(err.line, 2);
+
+    assert!(
+        parse_rdf_xml("<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_iri_helpers() {
+    assert_eq!(local_name("http://qudt.org/vocab/unit#Meter"), "Meter");
+    assert_eq!(local_name("http://www.openphacts.org/units/Molar"), "Molar");
+    assert_eq!(
+        resolve_iri("http://qudt.org/vocab/unit", "#Meter"),
+        "http://qudt.org/vocab/unit#Meter"
+    );
+    assert_eq!(
+        resolve_iri("http://qudt.org/vocab/unit", "http://example.org/x"),
+        "http://example.org/x"
+    );
+}
+
+#[test]
+fn test_parse_bundled_ontologies() {
+    for src in [
+        include_str!("../src/ontology/unit"),
+        include_str!("../src/ontology/qudt-quantity"),
+        include_str!("../src/ontology/quantity"),
+        include_str!("../src/ontology/qudt"),
+        include_str!("../src/ontology/dimension"),
+        include_str!("../src/ontology/dtype"),
+        include_str!("../src/ontology/ops"),
+    ] {
+        assert!(!parse_rdf_xml(src).unwrap().is_empty());
+    }
+}
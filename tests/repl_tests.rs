@@ -0,0 +1,149 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::repl::*;
+use metrology4rs::units::UnitError;
+
+#[test]
+fn test_pendulum() {
+    let mut session = Session::new();
+    assert_eq!(
+        session.run("L = 2.5 m ± 1 mm").unwrap(),
+        vec!["L = 2.5000(10) m"]
+    );
+    assert_eq!(
+        session
+            .run("g = 9.81 m/s^2; T = 2*pi*sqrt(L/g) in ms")
+            .unwrap(),
+        vec!["g = 9.81 m/s^2", "T = 3171.87(63) ms"]
+    );
+    let period = session.get("T").unwrap();
+    assert_eq!(period.dimension, TIME);
+    assert!((period.value - 3.17187).abs() < 1e-5);
+    // u(T)/T = u(L)/2L
+    assert!((period.uncertainty() / period.value - 0.001 / 5.0).abs() < 1e-12);
+
+    assert_eq!(session.run(":dim L/g").unwrap(), vec!["T^2"]);
+    assert_eq!(session.run("sqrt(L/g)").unwrap(), vec!["0.50482(10) s"]);
+}
+
+#[test]
+fn test_uncertainty_propagation() {
+    let mut session = Session::new();
+    session.run("x = 3 ± 0.4; y = 4 ± 0.3").unwrap();
+    assert_eq!(session.run("sqrt(x^2 + y^2)").unwrap(), vec!["5.00(34)"]);
+    // a variable is correlated with itself
+    let zero = session.evaluate("x*x - x^2").unwrap();
+    assert_eq!(zero.uncertainty(), 0.0);
+    let double = session.evaluate("x + x").unwrap();
+    assert!((double.uncertainty() - 0.8).abs() < 1e-12);
+
+    // the unit of the uncertainty applies to a bare number
+    assert_eq!(session.run("2.5 ± 0.1 m").unwrap(), vec!["2.50(10) m"]);
+    assert_eq!(session.run("(2.5 +/- 0.1) m").unwrap(), vec!["2.50(10) m"]);
+}
+
+#[test]
+fn test_units_and_conversion() {
+    let mut session = Session::new();
+    assert_eq!(session.run("5 km/h").unwrap(), vec!["5 km/hr"]);
+    assert_eq!(session.run("1 kW.h in MJ").unwrap(), vec!["3.6 MJ"]);
+    assert_eq!(session.run("2 (3 + 4)").unwrap(), vec!["14"]);
+    assert_eq!(session.run("1e3 mm in m").unwrap(), vec!["1 m"]);
+
+    // temperatures are readings, their differences are not
+    assert_eq!(
+        session.run("t1 = 20 degC; t2 = 25 degC; t2 - t1").unwrap(),
+        vec!["t1 = 20 degC", "t2 = 25 degC", "5 K"]
+    );
+    assert_eq!(session.run("t1 + 5 K").unwrap(), vec!["25 degC"]);
+    assert_eq!(session.run("t1 in K").unwrap(), vec!["293.15 K"]);
+
+    // fractional powers have no SI unit
+    assert_eq!(session.run("sqrt(4 m)").unwrap(), vec!["2 [L^1/2]"]);
+    session.run("L = 16 m").unwrap();
+    assert_eq!(session.run("sqrt(L)^2").unwrap(), vec!["16 m"]);
+    assert_eq!(session.run("sqrt(sqrt(L))").unwrap(), vec!["2 [L^1/4]"]);
+}
+
+#[test]
+fn test_readings() {
+    let mut session = Session::new();
+    session.run("t = 20 degC").unwrap();
+    assert!(matches!(
+        session.run("t + t"),
+        Err(UnitError::AffineMismatch { .. })
+    ));
+    assert!(matches!(
+        session.run("5 K - t"),
+        Err(UnitError::AffineMismatch { .. })
+    ));
+    assert_eq!(session.run("5 K + t").unwrap(), vec!["25 degC"]);
+    assert_eq!(session.run("t - t").unwrap(), vec!["0 K"]);
+
+    // a reading enters a product in kelvin
+    assert_eq!(session.run("(10 degC) * (2 m)").unwrap(), vec!["566.3 m K"]);
+    assert_eq!(session.run("(10 degC)^2").unwrap(), vec!["80173.9225 K^2"]);
+}
+
+#[test]
+fn test_errors() {
+    let mut session = Session::new();
+    assert!(matches!(
+        session.run("1 m + 1 s"),
+        Err(UnitError::IncompatibleDimensions { .. })
+    ));
+    assert!(matches!(
+        session.run("sin(1 m)"),
+        Err(UnitError::IncompatibleDimensions { .. })
+    ));
+    assert!(matches!(
+        session.run("1 m in s"),
+        Err(UnitError::IncompatibleDimensions { .. })
+    ));
+    assert!(matches!(
+        session.run("1 +"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        session.run("(1 + 2"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        session.run("nosuchunit"),
+        Err(UnitError::UnknownUnit(_))
+    ));
+    assert!(matches!(
+        session.run("pi = 3"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        session.run(":frobnicate"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        session.run("(2 m)^1e10"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn test_completion() {
+    let mut session = Session::new();
+    session.run("length = 2 m").unwrap();
+    let (start, candidates) = session.complete("3 * len", 7);
+    assert_eq!(start, 4);
+    assert_eq!(candidates[0].replacement, "length");
+
+    let (start, candidates) = session.complete("2 kilow", 7);
+    assert_eq!(start, 2);
+    assert!(
+        candidates
+            .iter()
+            .any(|c| c.replacement == "kW" && c.display == "kW (Kilowatt)")
+    );
+
+    let (_, candidates) = session.complete("sq", 2);
+    assert!(candidates.iter().any(|c| c.replacement == "sqrt"));
+    let (start, candidates) = session.complete(":v", 2);
+    assert_eq!((start, candidates[0].replacement.as_str()), (0, ":vars"));
+    assert!(session.complete("2 ", 2).1.is_empty());
+}
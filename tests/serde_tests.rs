@@ -0,0 +1,141 @@
+#![cfg(feature = "serde")]
+use metrology4rs::dimensional::*;
+use metrology4rs::dimensional_analysis::*;
+use metrology4rs::matrix::Matrix;
+use num::rational::Rational32;
+
+#[test]
+fn test_dimension_json() {
+    let json = serde_json::to_string(&FORCE).unwrap();
+    assert_eq!(json, r#"{"L":1,"M":1,"T":-2}"#);
+    let force: Dimension = serde_json::from_str(&json).unwrap();
+    assert_eq!(force, FORCE);
+
+    assert_eq!(serde_json::to_string(&DIMENSIONLESS).unwrap(), "{}");
+    let temperature: Dimension = serde_json::from_str(r#"{"Theta": 1}"#).unwrap();
+    assert_eq!(temperature, TEMPERATURE);
+    assert_eq!(serde_json::to_string(&TEMPERATURE).unwrap(), r#"{"Θ":1}"#);
+
+    // rational exponents and kinds
+    let root = LENGTH.sqrt();
+    let json = serde_json::to_string(&root).unwrap();
+    assert_eq!(json, r#"{"L":"1/2"}"#);
+    assert_eq!(serde_json::from_str::<Dimension>(&json).unwrap(), root);
+
+    let torque = ENERGY.with_kind("Torque");
+    let json = serde_json::to_string(&torque).unwrap();
+    assert_eq!(json, r#"{"L":2,"M":1,"T":-2,"kind":"Torque"}"#);
+    let back: Dimension = serde_json::from_str(&json).unwrap();
+    assert!(back.is_same_kind(&torque));
+    assert_eq!(back.kind(), Some("Torque"));
+
+    let acceleration: Dimension = serde_json::from_str(r#""LT^-2""#).unwrap();
+    assert_eq!(acceleration, ACCELERATION);
+
+    assert!(serde_json::from_str::<Dimension>(r#"{"X": 1}"#).is_err());
+    assert!(serde_json::from_str::<Dimension>(r#""LX""#).is_err());
+    assert!(serde_json::from_str::<Dimension>(r#"{"L": "1/0"}"#).is_err());
+}
+
+#[test]
+fn test_matrix_json() {
+    let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let json = serde_json::to_string(&matrix).unwrap();
+    assert_eq!(json, "[[1.0,2.0,3.0],[4.0,5.0,6.0]]");
+    let back: Matrix<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, matrix);
+    assert_eq!(back.dimensions(), (2, 3));
+
+    assert!(serde_json::from_str::<Matrix<f64>>("[[1.0,2.0],[3.0]]").is_err());
+}
+
+#[test]
+fn test_analysis_round_trip() {
+    let analysis = DimensionalAnalysis {
+        target: FREQUENCY,
+        dependencies: vec![LENGTH, MASS, ACCELERATION],
+    };
+    let json = serde_json::to_string(&analysis).unwrap();
+    assert!(json.starts_with(r#"{"target":{"name":"y","dimension":{"T":-1}}"#));
+    assert!(json.contains(r#"{"name":"x3","dimension":{"L":1,"T":-2}}"#));
+    let back: DimensionalAnalysis = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, analysis);
+
+    // the pendulum, named and written in TOML
+    let pendulum = NamedAnalysis {
+        target: Variable::new("t", TIME),
+        dependencies: vec![Variable::new("l", LENGTH), Variable::new("g", ACCELERATION)],
+    };
+    let text = toml::to_string(&pendulum).unwrap();
+    let back: NamedAnalysis = toml::from_str(&text).unwrap();
+    assert_eq!(back, pendulum);
+
+    let text = r#"
+        [target]
+        name = "t"
+        dimension = { T = 1 }
+
+        [[dependencies]]
+        name = "l"
+        dimension = { L = 1 }
+
+        [[dependencies]]
+        name = "g"
+        dimension = "LT^-2"
+    "#;
+    let named: NamedAnalysis = toml::from_str(text).unwrap();
+    assert_eq!(named, pendulum);
+    let expected = Matrix::from_col(vec![Rational32::new(1, 2), Rational32::new(-1, 2)]);
+    assert_eq!(
+        named.solve(),
+        DimensionalAnalysisSolution::UniqueSolution(expected)
+    );
+}
+
+#[test]
+fn test_solution_round_trip() {
+    let unique = DimensionalAnalysis {
+        target: TIME,
+        dependencies: vec![LENGTH, ACCELERATION],
+    }
+    .solve();
+    let json = serde_json::to_string(&unique).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"unique_solution","exponents":[["1/2"],["-1/2"]]}"#
+    );
+    let back: DimensionalAnalysisSolution = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, unique);
+
+    let none = DimensionalAnalysis {
+        target: FORCE,
+        dependencies: vec![LENGTH, TIME],
+    }
+    .solve();
+    let json = serde_json::to_string(&none).unwrap();
+    assert_eq!(json, r#"{"type":"no_solution"}"#);
+    assert_eq!(
+        serde_json::from_str::<DimensionalAnalysisSolution>(&json).unwrap(),
+        none
+    );
+
+    let multiple = DimensionalAnalysis {
+        target: FORCE,
+        dependencies: vec![DENSITY, VELOCITY, LENGTH, PRESSURE],
+    }
+    .solve();
+    assert!(matches!(
+        multiple,
+        DimensionalAnalysisSolution::MultipleSolutions { .. }
+    ));
+    let json = serde_json::to_string(&multiple).unwrap();
+    assert_eq!(
+        serde_json::from_str::<DimensionalAnalysisSolution>(&json).unwrap(),
+        multiple
+    );
+    let text = toml::to_string(&multiple).unwrap();
+    assert_eq!(
+        toml::from_str::<DimensionalAnalysisSolution>(&text).unwrap(),
+        multiple
+    );
+}
@@ -0,0 +1,132 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::dimensional_analysis::*;
+use metrology4rs::similitude::*;
+use metrology4rs::units::UnitError;
+
+// the drag of a ship hull: Π1 = F/(ρV²L²), Π2 = μ/(ρVL) = 1/Re, Π3 = gL/V² = 1/Fr²
+fn hull() -> NamedAnalysis {
+    NamedAnalysis {
+        target: Variable::new("F", FORCE),
+        dependencies: vec![
+            Variable::new("rho", DENSITY),
+            Variable::new("V", VELOCITY),
+            Variable::new("L", LENGTH),
+            Variable::new("mu", DENSITY * VELOCITY * LENGTH),
+            Variable::new("g", ACCELERATION),
+        ],
+    }
+}
+
+fn model_in_water() -> Similitude {
+    let mut plan = Similitude::new(&hull());
+    plan.set_scale("L", 1.0 / 25.0).unwrap();
+    for same in ["rho", "mu", "g"] {
+        plan.set_scale(same, 1.0).unwrap();
+    }
+    plan.set_prototype("V", 10.0, "m/s").unwrap();
+    plan.set_prototype("L", 100.0, "m").unwrap();
+    plan.set_prototype("F", 2.0, "MN").unwrap();
+    plan
+}
+
+#[test]
+fn test_reynolds_and_froude() {
+    let analysis = hull();
+    assert_eq!(
+        analysis.pi_groups()[2].format(&analysis.names()),
+        "V^-2·L·g"
+    );
+
+    // by default Π2 is matched first: Reynolds similarity
+    let report = model_in_water().solve().unwrap();
+    assert!(!report.is_similar());
+    let v = report.variable("V").unwrap();
+    assert!((v.scale - 25.0).abs() < 1e-9);
+    assert!(!v.chosen);
+    assert!((v.model.unwrap() - 250.0).abs() < 1e-9);
+    assert!(report.variable("L").unwrap().chosen);
+    assert!((report.variable("F").unwrap().scale - 1.0).abs() < 1e-9);
+    let violated = report.violated();
+    assert_eq!(violated.len(), 1);
+    assert_eq!(violated[0].group, analysis.pi_groups()[2]);
+    // gL/V² of the model is 25³ times too small
+    assert!((violated[0].ratio - 25f64.powi(-3)).abs() < 1e-12);
+    assert!((violated[0].deviation() + 1.0 - 25f64.powi(-3)).abs() < 1e-12);
+
+    // Froude similarity, as in a towing tank
+    let mut plan = model_in_water();
+    plan.prefer(2).unwrap();
+    let report = plan.solve().unwrap();
+    assert!((report.variable("V").unwrap().scale - 0.2).abs() < 1e-12);
+    let f = report.variable("F").unwrap();
+    assert!((f.scale - 25f64.powi(-3)).abs() < 1e-15);
+    assert!((f.model.unwrap() - 2.0 / 15625.0).abs() < 1e-12);
+    assert!(report.groups[0].matched && report.groups[2].matched);
+    assert!(!report.groups[1].matched);
+    // μ/(ρVL) of the model is 1/(0.2·0.04) = 125 times that of the prototype
+    assert!((report.groups[1].ratio - 125.0).abs() < 1e-9);
+
+    let text = report.to_string();
+    assert!(text.contains("L × 0.04 (chosen): 100 m -> 4 m"));
+    assert!(text.contains("Π2 = rho^-1·V^-1·L^-1·mu: violated, model/prototype = 125"));
+}
+
+#[test]
+fn test_complete_similarity() {
+    // a model in another fluid can match both groups
+    let mut plan = Similitude::new(&hull());
+    plan.set_scale("L", 0.25).unwrap();
+    plan.set_scale("g", 1.0).unwrap();
+    plan.set_scale("rho", 1.0).unwrap();
+    let report = plan.solve().unwrap();
+    assert!(report.is_similar());
+    assert!((report.variable("V").unwrap().scale - 0.5).abs() < 1e-12);
+    assert!((report.variable("mu").unwrap().scale - 0.125).abs() < 1e-12);
+    assert!(report.variable("mu").unwrap().model.is_none());
+
+    // temperatures are scaled in kelvin
+    let heating = NamedAnalysis {
+        target: Variable::new("T", TEMPERATURE),
+        dependencies: vec![Variable::new("T0", TEMPERATURE)],
+    };
+    let mut plan = Similitude::new(&heating);
+    plan.set_scale("T0", 2.0).unwrap();
+    plan.set_prototype("T", 20.0, "degC").unwrap();
+    let report = plan.solve().unwrap();
+    assert!((report.variable("T").unwrap().model.unwrap() - (2.0 * 293.15 - 273.15)).abs() < 1e-9);
+}
+
+#[test]
+fn test_errors() {
+    let mut plan = Similitude::new(&hull());
+    assert_eq!(
+        plan.set_scale("x", 1.0),
+        Err(SimilitudeError::UnknownVariable("x".to_string()))
+    );
+    assert!(matches!(
+        plan.set_scale("L", 0.0),
+        Err(SimilitudeError::InvalidScale { .. })
+    ));
+    assert!(matches!(
+        plan.set_scale("L", f64::NAN),
+        Err(SimilitudeError::InvalidScale { .. })
+    ));
+    assert!(matches!(
+        plan.set_prototype("L", 1.0, "s"),
+        Err(SimilitudeError::WrongDimension { .. })
+    ));
+    assert!(matches!(
+        plan.set_prototype("L", 1.0, "furlongs"),
+        Err(SimilitudeError::Unit(UnitError::UnknownUnit(_)))
+    ));
+    assert_eq!(plan.prefer(3), Err(SimilitudeError::UnknownGroup(3)));
+
+    // three groups fix three of the six scales: V follows from gL/V², the
+    // fluid is open
+    plan.set_scale("L", 0.1).unwrap();
+    plan.set_scale("g", 1.0).unwrap();
+    match plan.solve() {
+        Err(SimilitudeError::Underdetermined(open)) => assert_eq!(open, vec!["F", "rho", "mu"]),
+        other => panic!("unexpected {:?}", other),
+    }
+}
@@ -0,0 +1,176 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::rdf::*;
+use metrology4rs::turtle::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_ops_matches_rdf_xml() {
+    let mut ttl = parse_turtle(OPS_TURTLE).unwrap();
+    let mut xml = parse_rdf_xml(OPS_RDF_XML).unwrap();
+    assert_eq!(ttl.len(), xml.len());
+    let key = |t: &Triple| format!("{:?}", t);
+    ttl.sort_by_key(key);
+    xml.sort_by_key(key);
+    assert_eq!(ttl, xml);
+}
+
+#[test]
+fn test_syntax() {
+    let triples = parse_turtle(
+        r#"
+PREFIX ex: <http://example.org/>
+@base <http://example.org/base/> .
+ex:a a ex:Thing ;
+    ex:name "tab\there"@en , """two
+lines""" ;
+    ex:count 42 ;
+    ex:ratio 1.5e3 ;
+    ex:flag true ;
+    ex:part [ ex:name 'inner' ] ;
+    ex:other _:x ;
+    ex:rel <relative> .
+"#,
+    )
+    .unwrap();
+    assert_eq!(triples.len(), 10);
+    assert_eq!(triples[0].predicate, RDF_TYPE);
+    assert_eq!(triples[1].object.as_literal(), Some("tab\there"));
+    assert_eq!(triples[2].object.as_literal(), Some("two\nlines"));
+    assert_eq!(
+        triples[3].object,
+        Term::Literal {
+            value: "42".to_string(),
+            datatype: Some("http://www.w3.org/2001/XMLSchema#integer".to_string()),
+            lang: None,
+        }
+    );
+    assert!(
+        triples
+            .iter()
+            .any(|t| t.object.as_iri() == Some("http://example.org/base/relative"))
+    );
+    // the `[ ... ]` node is the subject of its own triple
+    assert!(
+        triples
+            .iter()
+            .any(|t| t.object.as_literal() == Some("inner")
+                && matches!(t.subject, Term::BlankNode(_)))
+    );
+}
+
+#[test]
+fn test_errors() {
+    let err = parse_turtle("@prefix ex: <http://example.org/> .\nex:a ex:b \"open").unwrap_err();
+    assert_eq!(err.line, 2);
+    let err = parse_turtle("\n\nfoo:a foo:b foo:c .").unwrap_err();
+    assert_eq!(err.line, 3);
+    assert!(parse_turtle("<a> <b> (1 2) .").is_err());
+    assert!(parse_turtle("<a> <b> <c>").is_err());
+}
+
+#[test]
+fn test_merge_ops() {
+    let mut units = UnitCatalog::default();
+    assert!(units.merge_rdf_xml(OPS_RDF_XML).unwrap().is_empty());
+    let count = units.len();
+    // both serializations describe the same units
+    assert!(units.merge_turtle(OPS_TURTLE).unwrap().is_empty());
+    assert_eq!(units.len(), count);
+
+    let molar = units.get("Molar").unwrap();
+    assert_eq!(
+        molar.dimension,
+        Some(Dimension::PowerLaw(-3, 0, 0, 0, 0, 1, 0))
+    );
+}
+
+#[test]
+fn test_user_extension() {
+    let mut units = catalog().clone();
+    let conflicts = units
+        .merge_turtle(
+            r#"
+@prefix qudt: <http://qudt.org/schema/qudt#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix ex:   <http://example.org/units#> .
+
+ex:Smoot a qudt:LengthUnit ;
+    rdfs:label "Smoot" ;
+    qudt:symbol "smoot" ;
+    qudt:conversionMultiplier 1.7018 .
+"#,
+        )
+        .unwrap();
+    assert!(conflicts.is_empty());
+    let smoot = units.find_by_symbol("smoot");
+    assert_eq!(smoot.len(), 1);
+    assert_eq!(smoot[0].dimension, Some(LENGTH));
+    let foot = units.get("Foot").unwrap();
+    assert!((smoot[0].convert(1.0, foot).unwrap() - 5.5833).abs() < 1e-3);
+    assert!(catalog().get("Smoot").is_none());
+}
+
+#[test]
+fn test_conflicts() {
+    let mut units = catalog().clone();
+    let conflicts = units
+        .merge_turtle(
+            r#"
+@prefix qudt: <http://qudt.org/schema/qudt#> .
+@prefix unit: <http://qudt.org/vocab/unit#> .
+
+unit:Inch a qudt:LengthUnit ;
+    qudt:conversionMultiplier 0.025 ;
+    qudt:uneceCommonCode "INH" .
+"#,
+        )
+        .unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].property, "conversion_multiplier");
+    assert_eq!(conflicts[0].existing, "0.0254");
+    assert_eq!(conflicts[0].incoming, "0.025");
+    // the existing value is kept, missing values are filled in
+    let inch = units.get("Inch").unwrap();
+    assert_eq!(inch.conversion_multiplier, Some(0.0254));
+    assert_eq!(inch.unece_common_code.as_deref(), Some("INH"));
+}
+
+#[test]
+fn test_unstated_properties() {
+    // only the properties both definitions state are compared
+    let mut units = catalog().clone();
+    let conflicts = units
+        .merge_turtle(
+            r#"
+@prefix qudt: <http://qudt.org/schema/qudt#> .
+@prefix unit: <http://qudt.org/vocab/unit#> .
+
+unit:DegreeCelsius a qudt:TemperatureUnit ;
+    qudt:uneceCommonCode "CEL" .
+unit:Kelvin qudt:uneceCommonCode "KEL" .
+"#,
+        )
+        .unwrap();
+    assert_eq!(conflicts, vec![]);
+    let celsius = units.get("DegreeCelsius").unwrap();
+    assert_eq!(celsius.label, "Degree Celsius");
+    assert_eq!(celsius.conversion_offset, 273.15);
+    assert_eq!(celsius.unece_common_code.as_deref(), Some("CEL"));
+    assert_eq!(
+        units.get("Kelvin").unwrap().unece_common_code.as_deref(),
+        Some("KEL")
+    );
+
+    let conflicts = units
+        .merge_turtle(
+            r#"
+@prefix qudt: <http://qudt.org/schema/qudt#> .
+@prefix unit: <http://qudt.org/vocab/unit#> .
+
+unit:DegreeCelsius qudt:conversionOffset 273.0 .
+"#,
+        )
+        .unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].property, "conversion_offset");
+}
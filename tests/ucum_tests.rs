@@ -0,0 +1,161 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::expression::DerivedUnit;
+use metrology4rs::ucum::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_atoms_exist_in_catalog() {
+    for (code, name) in UCUM_ATOMS {
+        assert!(catalog().get(name).is_some(), "{} -> {}", code, name);
+    }
+}
+
+#[test]
+fn test_parse_ucum() {
+    let newton = parse_ucum("kg.m/s2").unwrap();
+    assert_eq!(newton.dimension(), Some(FORCE));
+    assert_eq!(newton.conversion_multiplier(), Some(1.0));
+
+    let celsius = parse_ucum("Cel").unwrap();
+    assert_eq!(celsius.to_unit().name, "DegreeCelsius");
+
+    let pressure = parse_ucum("mm[Hg]").unwrap();
+    assert_eq!(pressure.dimension(), Some(PRESSURE));
+
+    let count = parse_ucum("10*3/uL").unwrap();
+    assert_eq!(count.dimension(), Some(VOLUME.powi(-1)));
+    assert!((count.conversion_multiplier().unwrap() - 1e12).abs() < 1.0);
+    assert_eq!(parse_ucum("10^3/uL").unwrap(), count);
+
+    let rate = parse_ucum("/min").unwrap();
+    assert_eq!(rate.dimension(), Some(FREQUENCY));
+    assert_eq!(
+        parse_ucum("[in_i]").unwrap().conversion_multiplier(),
+        Some(0.0254)
+    );
+    assert_eq!(parse_ucum("cm[H2O]").unwrap().dimension(), Some(PRESSURE));
+
+    // m[Hg] and m[H2O] are metric atoms and take any prefix
+    let pascal = |code: &str| parse_ucum(code).unwrap().conversion_multiplier().unwrap();
+    assert!((pascal("mm[Hg]") - 133.322387415).abs() < 1e-9);
+    assert!((pascal("m[Hg]") / pascal("mm[Hg]") - 1e3).abs() < 1e-9);
+    assert!((pascal("cm[Hg]") / pascal("mm[Hg]") - 10.0).abs() < 1e-9);
+    assert!((pascal("m[H2O]") / pascal("cm[H2O]") - 1e2).abs() < 1e-9);
+    assert!((pascal("mm[H2O]") / pascal("cm[H2O]") - 0.1).abs() < 1e-12);
+    assert_eq!(
+        parse_ucum("mm[Hg]").unwrap().to_unit().name,
+        "MillimeterOfMercury"
+    );
+}
+
+#[test]
+fn test_annotations() {
+    assert_eq!(parse_ucum("{cells}").unwrap(), DerivedUnit::one());
+    assert_eq!(
+        parse_ucum("{cells}/uL").unwrap(),
+        parse_ucum("/uL").unwrap()
+    );
+    assert_eq!(
+        parse_ucum("mL{total}/min").unwrap(),
+        parse_ucum("mL/min").unwrap()
+    );
+    assert!(parse_ucum("mL{total").is_err());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse_ucum("furlong"),
+        Err(UnitError::UnknownUnit("furlong".to_string()))
+    );
+    assert!(matches!(
+        parse_ucum("kg.m/"),
+        Err(UnitError::InvalidExpression { position: 5, .. })
+    ));
+    assert!(matches!(
+        parse_ucum("(kg.m"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        parse_ucum("[in_i"),
+        Err(UnitError::InvalidExpression { .. })
+    ));
+    // UCUM uses `.`, not spaces
+    assert!(parse_ucum("N m").is_err());
+    assert_eq!(
+        parse_ucum("kkg"),
+        Err(UnitError::UnknownUnit("kkg".to_string()))
+    );
+}
+
+#[test]
+fn test_format_ucum() {
+    let unit = DerivedUnit::parse("W/(m2.K)").unwrap();
+    assert_eq!(format_ucum(&unit).unwrap(), "W/(m2.K)");
+    assert_eq!(
+        format_ucum(&DerivedUnit::parse("µN·m").unwrap()).unwrap(),
+        "uN.m"
+    );
+    assert_eq!(
+        format_ucum(&DerivedUnit::parse("°C").unwrap()).unwrap(),
+        "Cel"
+    );
+    assert_eq!(
+        format_ucum(&DerivedUnit::parse("1/s").unwrap()).unwrap(),
+        "/s"
+    );
+    assert_eq!(format_ucum(&DerivedUnit::one()).unwrap(), "1");
+    assert_eq!(
+        format_ucum(&DerivedUnit::parse("ft-lbf/min").unwrap()),
+        Err(UnitError::NoCode {
+            unit: "FootPoundForcePerMinute".to_string(),
+            system: "UCUM".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    for code in [
+        "m",
+        "kg",
+        "kg.m/s2",
+        "mm[Hg]",
+        "Cel",
+        "[degF]",
+        "10*3/uL",
+        "10*-6.mol/L",
+        "mL/min",
+        "/min",
+        "W/(m2.K)",
+        "J/(mol.K)",
+        "km/h",
+        "GiBy",
+        "ug/dL",
+        "[lb_av]/[in_i]2",
+        "m-1",
+        "cm[H2O]",
+        "m[Hg]",
+        "cm[Hg]",
+        "m[H2O]",
+        "mm[H2O]",
+        "kat/L",
+    ] {
+        let unit = parse_ucum(code).unwrap();
+        let emitted = format_ucum(&unit).unwrap();
+        assert_eq!(
+            parse_ucum(&emitted).unwrap(),
+            unit,
+            "{} -> {}",
+            code,
+            emitted
+        );
+    }
+    // canonical codes are reproduced exactly
+    for code in [
+        "kg.m/s2", "mm[Hg]", "cm[Hg]", "cm[H2O]", "mm[H2O]", "Cel", "10*3/uL", "W/(m2.K)", "/min",
+        "GiBy",
+    ] {
+        assert_eq!(format_ucum(&parse_ucum(code).unwrap()).unwrap(), code);
+    }
+}
@@ -0,0 +1,55 @@
+use metrology4rs::unece::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_qudt_codes() {
+    let meter = Unit::from_unece_code("MTR").unwrap();
+    assert_eq!(meter.name, "Meter");
+    assert_eq!(meter.unece_code(), Some("MTR"));
+    assert_eq!(Unit::from_unece_code("4H").unwrap().name, "Micrometer");
+    assert_eq!(Unit::from_unece_code("NMI").unwrap().name, "NauticalMile");
+}
+
+#[test]
+fn test_supplementary_codes() {
+    for (code, _) in SUPPLEMENTARY_CODES {
+        let unit = Unit::from_unece_code(code).unwrap();
+        assert_eq!(unit.unece_code(), Some(*code), "{}", unit.name);
+    }
+    assert_eq!(Unit::from_unece_code("KGM").unwrap().name, "Kilogram");
+    assert_eq!(Unit::from_unece_code("LTR").unwrap().name, "Liter");
+    assert_eq!(Unit::from_unece_code("C62").unwrap().name, "Unitless");
+
+    // units QUDT lacks are synthesized
+    let mg = Unit::from_unece_code("MGM").unwrap();
+    assert_eq!(mg.display_symbol(), "mg");
+    assert!((mg.conversion_multiplier.unwrap() - 1e-6).abs() < 1e-20);
+    let kwh = Unit::from_unece_code("KWH").unwrap();
+    assert_eq!(
+        kwh.convert(1.0, catalog().get("Joule").unwrap()).unwrap(),
+        3.6e6
+    );
+}
+
+#[test]
+fn test_ambiguous_codes() {
+    assert_eq!(
+        ambiguous_unece_codes(),
+        vec![(
+            "A91".to_string(),
+            vec!["Gon".to_string(), "Grad".to_string()]
+        )]
+    );
+    assert_eq!(
+        Unit::from_unece_code("A91"),
+        Err(UnitError::AmbiguousUnit {
+            symbol: "A91".to_string(),
+            candidates: vec!["Gon".to_string(), "Grad".to_string()]
+        })
+    );
+    assert_eq!(
+        Unit::from_unece_code("XXX"),
+        Err(UnitError::UnknownUnit("XXX".to_string()))
+    );
+    assert_eq!(catalog().get("Fathom").unwrap().unece_code(), None);
+}
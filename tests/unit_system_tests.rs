@@ -0,0 +1,186 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::quantity::Quantity;
+use metrology4rs::unit_system::*;
+use metrology4rs::units::*;
+
+fn dimension_of(unit: &str) -> Dimension {
+    catalog().get(unit).unwrap().dimension.clone().unwrap()
+}
+
+#[test]
+fn test_unit_systems_from_ontology() {
+    assert_eq!(UNIT_SYSTEMS.len(), 11);
+    assert_eq!(unit_systems().len(), UNIT_SYSTEMS.len());
+
+    let si = unit_system("SI").unwrap();
+    let base: Vec<&str> = si.base_units().iter().map(|u| u.name.as_str()).collect();
+    for name in [
+        "Meter",
+        "Kilogram",
+        "SecondTime",
+        "Ampere",
+        "Kelvin",
+        "Mole",
+        "Candela",
+    ] {
+        assert!(base.contains(&name), "{}", name);
+    }
+    assert!(si.derived_units().iter().any(|u| u.name == "Newton"));
+    assert!(si.allowed_units().iter().any(|u| u.name == "Liter"));
+
+    // lookup by abbreviation and IRI
+    assert_eq!(unit_system("Gauss").unwrap().name, "CGS-Gauss");
+    assert_eq!(unit_system(si.iri.as_str()), Some(si));
+    assert_eq!(unit_system("Metric"), None);
+
+    assert!(unit_system("Planck").unwrap().natural);
+    assert!(unit_system("Atomic").unwrap().natural);
+    assert!(!si.natural);
+}
+
+#[test]
+fn test_supplemented_systems() {
+    let gauss = unit_system("CGS-Gauss").unwrap();
+    assert_eq!(gauss.electromagnetism, Electromagnetism::Gaussian);
+    assert!(gauss.base_units().iter().any(|u| u.name == "Centimeter"));
+    assert!(gauss.derived_units().iter().any(|u| u.name == "Dyne"));
+    assert!(
+        gauss
+            .derived_units()
+            .iter()
+            .any(|u| u.name == "Statcoulomb")
+    );
+
+    let us = unit_system("USCustomary").unwrap();
+    assert!(us.base_units().iter().any(|u| u.name == "Foot"));
+    assert!(us.defined_units().len() > 100);
+}
+
+#[test]
+fn test_coherent_units() {
+    let cgs = unit_system("CGS").unwrap();
+    assert_eq!(cgs.coherent_unit(&FORCE).unwrap().name, "Dyne");
+    assert_eq!(
+        cgs.coherent_unit(&VELOCITY).unwrap().name,
+        "CentimeterPerSecond"
+    );
+    assert_eq!(cgs.coherent_unit(&DIMENSIONLESS).unwrap().name, "Unitless");
+
+    // no member unit: the product of base units is synthesized
+    let density = cgs.coherent_unit(&(MASS / LENGTH.powi(3))).unwrap();
+    assert!((density.conversion_multiplier.unwrap() - 1000.0).abs() < 1e-9);
+
+    let si = unit_system("SI").unwrap();
+    assert_eq!(si.coherent_unit(&ENERGY).unwrap().name, "Joule");
+    assert_eq!(
+        si.coherent_unit(&ENERGY.with_kind("Torque")).unwrap().name,
+        "NewtonMeter"
+    );
+
+    // offset units are not coherent
+    let us = unit_system("USCustomary").unwrap();
+    assert_eq!(
+        us.coherent_unit(&TEMPERATURE).unwrap().name,
+        "DegreeRankine"
+    );
+    assert_eq!(us.coherent_unit(&FORCE).unwrap().name, "Poundal");
+
+    let planck = unit_system("Planck").unwrap();
+    assert_eq!(planck.coherent_unit(&FORCE).unwrap().name, "PlanckForce");
+
+    assert!(matches!(
+        cgs.coherent_unit(&TEMPERATURE),
+        Err(UnitError::NotInSystem { .. })
+    ));
+    assert!(matches!(
+        si.coherent_unit(&LENGTH.sqrt()),
+        Err(UnitError::NotInSystem { .. })
+    ));
+}
+
+#[test]
+fn test_express_quantity() {
+    let units = catalog();
+    let cgs = unit_system("CGS").unwrap();
+    let force = Quantity::new(2.0, units.get("Newton").unwrap()).with_uncertainty(0.1);
+    let dyne = cgs.express(&force).unwrap();
+    assert_eq!(dyne.unit.name, "Dyne");
+    assert!((dyne.value - 2e5).abs() < 1e-6);
+    assert!((dyne.uncertainty - 1e4).abs() < 1e-6);
+
+    let gauss = unit_system("CGS-Gauss").unwrap();
+    let b = Quantity::new(1.0, units.get("Tesla").unwrap());
+    let b = gauss.express(&b).unwrap();
+    assert_eq!(b.unit.name, "Gauss");
+    assert!((b.value - 1e4).abs() < 1e-6);
+
+    let price = Quantity::new(1.0, units.get("Euro").unwrap());
+    assert!(cgs.express(&price).is_err());
+}
+
+#[test]
+fn test_is_allowed() {
+    let units = catalog();
+    let cgs = unit_system("CGS").unwrap();
+    assert!(cgs.is_allowed(units.get("Erg").unwrap()));
+    assert!(cgs.is_allowed(units.get("Kilometer").unwrap()));
+    assert!(!cgs.is_allowed(units.get("Joule").unwrap()));
+    // coherent but not listed
+    let g_per_cm3 = metrology4rs::expression::DerivedUnit::parse("g/cm3")
+        .unwrap()
+        .to_unit();
+    assert!(cgs.is_allowed(&g_per_cm3));
+}
+
+#[test]
+fn test_electromagnetic_dimensions() {
+    let e_field = dimension_of("VoltPerMeter");
+    let b_field = dimension_of("Tesla");
+    let charge = dimension_of("Coulomb");
+    let half = |n: i32| num::rational::Rational32::new(n, 2);
+
+    let esu = unit_system("CGS-ESU").unwrap();
+    let q = esu.system_dimension(&charge).unwrap();
+    assert_eq!(
+        q.get_exponents()[..4],
+        [half(3), half(1), half(-2), half(0)]
+    );
+    assert_eq!(esu.coherent_unit(&charge).unwrap().name, "Statcoulomb");
+    // the ESU unit of B is the stattesla, about 3e6 T, not the gauss
+    let stat_tesla = esu.coherent_unit(&b_field).unwrap();
+    assert!((stat_tesla.conversion_multiplier.unwrap() / 2.99792458e6 - 1.0).abs() < 1e-4);
+
+    let emu = unit_system("CGS-EMU").unwrap();
+    assert_eq!(emu.coherent_unit(&charge).unwrap().name, "Abcoulomb");
+    let q = emu.system_dimension(&charge).unwrap();
+    assert_eq!(q.get_exponents()[..4], [half(1), half(1), half(0), half(0)]);
+
+    // Gaussian: E and B share a dimension
+    let gauss = unit_system("CGS-Gauss").unwrap();
+    assert_eq!(
+        gauss.system_dimension(&e_field).unwrap(),
+        gauss.system_dimension(&b_field).unwrap()
+    );
+    let h_field = dimension_of("AmperePerMeter");
+    assert!(matches!(
+        gauss.system_dimension(&h_field),
+        Err(UnitError::NotInSystem { .. })
+    ));
+    let h_field = h_field.with_kind("AuxillaryMagneticField");
+    assert_eq!(
+        gauss.system_dimension(&h_field).unwrap(),
+        gauss.system_dimension(&b_field).unwrap()
+    );
+    assert_eq!(gauss.coherent_unit(&h_field).unwrap().name, "Oersted");
+    // the Gaussian dimension of a charge has half-integer exponents
+    let q = gauss.system_dimension(&charge).unwrap();
+    assert!(matches!(
+        gauss.coherent_unit(&q),
+        Err(UnitError::NotInSystem { .. })
+    ));
+
+    let si = unit_system("SI").unwrap();
+    assert_eq!(si.system_dimension(&charge).unwrap(), charge);
+    let cgs = unit_system("CGS").unwrap();
+    assert!(cgs.system_dimension(&charge).is_err());
+}
@@ -0,0 +1,116 @@
+use metrology4rs::dimensional::*;
+use metrology4rs::units::*;
+
+#[test]
+fn test_catalog_loads_qudt_units() {
+    let units = catalog();
+    assert!(units.len() > 600);
+
+    let meter = units.get("Meter").unwrap();
+    assert_eq!(meter.symbol.as_deref(), Some("m"));
+    assert_eq!(meter.conversion_multiplier, Some(1.0));
+    assert!(meter.has_type("SIBaseUnit"));
+    assert_eq!(meter.dimension, Some(LENGTH));
+
+    // lookup by full IRI
+    let by_iri = units.get("http://qudt.org/vocab/unit#Meter").unwrap();
+    assert_eq!(by_iri, meter);
+}
+
+#[test]
+fn test_unit_dimensions_from_quantity_kinds() {
+    let units = catalog();
+    assert_eq!(units.get("Newton").unwrap().dimension, Some(FORCE));
+    assert_eq!(units.get("Joule").unwrap().dimension, Some(ENERGY));
+    assert_eq!(units.get("Pascal").unwrap().dimension, Some(PRESSURE));
+    assert_eq!(units.get("Watt").unwrap().dimension, Some(POWER));
+    assert_eq!(units.get("Kelvin").unwrap().dimension, Some(TEMPERATURE));
+    // currencies have no physical dimension
+    assert_eq!(units.get("Euro").unwrap().dimension, None);
+}
+
+#[test]
+fn test_unit_conversion() {
+    let units = catalog();
+    let inch = units.get("Inch").unwrap();
+    let mm = units.get("Millimeter").unwrap();
+    assert!((inch.convert(1.0, mm).unwrap() - 25.4).abs() < 1e-9);
+
+    let celsius = units.get("DegreeCelsius").unwrap();
+    let fahrenheit = units.get("DegreeFahrenheit").unwrap();
+    let kelvin = units.get("Kelvin").unwrap();
+    assert!((celsius.convert(100.0, kelvin).unwrap() - 373.15).abs() < 1e-9);
+    // QUDT 1.1 rounds the Fahrenheit offset to 255.370 K, corrected to 255.372 K
+    assert!((celsius.convert(100.0, fahrenheit).unwrap() - 212.0).abs() < 1e-9);
+    assert!((fahrenheit.convert(32.0, kelvin).unwrap() - 273.15).abs() < 1e-9);
+}
+
+#[test]
+fn test_unit_conversion_errors() {
+    let units = catalog();
+    let meter = units.get("Meter").unwrap();
+    let second = units.get("SecondTime").unwrap();
+    assert_eq!(
+        meter.convert(1.0, second),
+        Err(UnitError::IncompatibleDimensions {
+            from: "Meter".to_string(),
+            to: "SecondTime".to_string()
+        })
+    );
+    assert_eq!(
+        units.require("Furlongs"),
+        Err(UnitError::UnknownUnit("Furlongs".to_string()))
+    );
+}
+
+#[test]
+fn test_find_by_symbol() {
+    let units = catalog();
+    let names: Vec<&str> = units
+        .find_by_symbol("Pa")
+        .iter()
+        .map(|u| u.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Pascal"]);
+    // "mi" is both the international and the US survey mile
+    assert!(units.find_by_symbol("mi").len() >= 2);
+}
+
+#[test]
+fn test_quantity_kind_dimension() {
+    assert_eq!(quantity_kind_dimension("Torque"), Some(ENERGY));
+    assert_eq!(quantity_kind_dimension("LinearVelocity"), Some(VELOCITY));
+    assert_eq!(quantity_kind_dimension("Currency"), None);
+}
+
+#[test]
+fn test_generated_constants() {
+    assert_eq!(METER.symbol, Some("m"));
+    assert_eq!(METER.dimension, Some(LENGTH));
+    assert_eq!(POUND_FORCE.dimension, Some(FORCE));
+    assert!((POUND_FORCE.multiplier.unwrap() - 4.448222).abs() < 1e-6);
+    assert_eq!(DEGREE_CELSIUS.offset, 273.15);
+
+    const BOILING: Option<f64> = DEGREE_CELSIUS.to_si(100.0);
+    assert_eq!(BOILING, Some(373.15));
+    assert_eq!(DEGREE_CELSIUS.from_si(273.15), Some(0.0));
+
+    assert_eq!(UNITS.len(), catalog().len());
+    assert_eq!(
+        catalog().get("PoundForce").unwrap(),
+        &Unit::from(&POUND_FORCE)
+    );
+}
+
+#[test]
+fn test_generated_constants_match_ontology() {
+    let parse = |src: &str| metrology4rs::rdf::parse_rdf_xml(src).unwrap();
+    let units = parse(include_str!("../src/ontology/unit"));
+    let mut kinds = parse(include_str!("../src/ontology/qudt-quantity"));
+    kinds.extend(parse(include_str!("../src/ontology/quantity")));
+    let parsed = UnitCatalog::from_triples(&units, &kinds);
+    assert_eq!(parsed.len(), catalog().len());
+    for (parsed, generated) in parsed.iter().zip(catalog().iter()) {
+        assert_eq!(parsed, generated);
+    }
+}